@@ -623,3 +623,163 @@ pub fn llvm_enum(attribute_args: TokenStream, attributee: TokenStream) -> TokenS
     };
     q.into()
 }
+
+/// This derive macro implements `inkwell::types::LlvmType` for a `#[repr(C)]` struct,
+/// whose fields must all implement `LlvmType` themselves. It is re-exported as
+/// `inkwell::types::LlvmType` and should be used from there.
+///
+/// Besides the trait implementation, the following inherent items are generated:
+///
+/// * `struct_type`, which creates the matching `StructType` in a given `Context`
+/// * `const_struct`, which creates a constant `StructValue` from an instance
+/// * One `FIELD_<NAME>` constant per field, holding its index for `build_struct_gep`
+///   and `build_extract_value`. Tuple structs get `FIELD_0`, `FIELD_1` and so on.
+///
+/// ```ignore
+/// #[derive(LlvmType)]
+/// #[repr(C)]
+/// struct Point {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// let struct_type = Point::struct_type(&context);
+/// let y_ptr = builder.build_struct_gep(point_ptr, Point::FIELD_Y, "y_ptr").unwrap();
+/// ```
+///
+/// `#[repr(C, packed)]` is supported as well, in which case all fields must also be `Copy`.
+#[proc_macro_derive(LlvmType)]
+pub fn derive_llvm_type(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+
+    match expand_llvm_type(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand_llvm_type(mut input: syn::DeriveInput) -> Result<proc_macro2::TokenStream> {
+    use syn::{Data, GenericParam, Index, Lit, Meta, NestedMeta};
+
+    let fields = match input.data {
+        Data::Struct(ref data) => data.fields.clone(),
+        _ => return Err(Error::new(input.span(), "LlvmType can only be derived for structs")),
+    };
+
+    // The layout of the Rust struct must be predictable in order to match it in LLVM
+    let mut is_repr_c = false;
+    let mut packed = false;
+
+    for attr in input.attrs.iter().filter(|attr| attr.path.is_ident("repr")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => return Err(Error::new(meta.span(), "Expected #[repr(...)]")),
+        };
+
+        for nested in list.nested.iter() {
+            match nested {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("C") => is_repr_c = true,
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("packed") => packed = true,
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("packed") => match list.nested.first() {
+                    Some(NestedMeta::Lit(Lit::Int(int))) if list.nested.len() == 1 && int.base10_digits() == "1" => {
+                        packed = true
+                    },
+                    _ => return Err(Error::new(list.span(), "LlvmType only supports packed(1)")),
+                },
+                _ => {
+                    return Err(Error::new(
+                        nested.span(),
+                        "LlvmType does not support this representation",
+                    ))
+                },
+            }
+        }
+    }
+
+    if !is_repr_c {
+        return Err(Error::new(
+            input.ident.span(),
+            "LlvmType can only be derived for #[repr(C)] structs",
+        ));
+    }
+
+    let vis = input.vis.clone();
+    let field_types: Vec<_> = fields.iter().map(|field| field.ty.clone()).collect();
+    let mut field_accesses = Vec::with_capacity(fields.len());
+    let mut field_consts = Vec::with_capacity(fields.len());
+
+    for (index, field) in fields.iter().enumerate() {
+        let (access, name) = match field.ident {
+            Some(ref ident) => {
+                let name = ident.to_string();
+
+                (quote!(#ident), name.trim_start_matches("r#").to_uppercase())
+            },
+            None => {
+                let index = Index::from(index);
+
+                (quote!(#index), index.index.to_string())
+            },
+        };
+        let const_ident = Ident::new(&format!("FIELD_{}", name), field.span());
+        let doc = format!(
+            "The index of field `{}` in the `StructType` of this struct.",
+            name.to_lowercase()
+        );
+        let index = index as u32;
+
+        // References to fields of packed structs may be unaligned, so they need to be copied out first
+        field_accesses.push(if packed {
+            quote!(&{ self.#access })
+        } else {
+            quote!(&self.#access)
+        });
+        field_consts.push(quote! {
+            #[doc = #doc]
+            #vis const #const_ident: u32 = #index;
+        });
+    }
+
+    for param in input.generics.params.iter_mut() {
+        if let GenericParam::Type(ref mut param) = param {
+            param.bounds.push(parse_quote!(::inkwell::types::LlvmType));
+        }
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let q = quote! {
+        impl #impl_generics ::inkwell::types::LlvmType for #name #ty_generics #where_clause {
+            fn llvm_type(context: &::inkwell::context::Context) -> ::inkwell::types::BasicTypeEnum<'_> {
+                Self::struct_type(context).into()
+            }
+
+            fn llvm_const<'ctx>(&self, context: &'ctx ::inkwell::context::Context) -> ::inkwell::values::BasicValueEnum<'ctx> {
+                self.const_struct(context).into()
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#field_consts)*
+
+            /// Creates the `StructType` matching the layout of this struct.
+            #vis fn struct_type(context: &::inkwell::context::Context) -> ::inkwell::types::StructType<'_> {
+                context.struct_type(
+                    &[#(<#field_types as ::inkwell::types::LlvmType>::llvm_type(context)),*],
+                    #packed,
+                )
+            }
+
+            /// Creates a constant `StructValue` holding the values of this struct.
+            #vis fn const_struct<'ctx>(&self, context: &'ctx ::inkwell::context::Context) -> ::inkwell::values::StructValue<'ctx> {
+                context.const_struct(
+                    &[#(::inkwell::types::LlvmType::llvm_const(#field_accesses, context)),*],
+                    #packed,
+                )
+            }
+        }
+    };
+
+    Ok(q)
+}
@@ -0,0 +1,821 @@
+//! Lowering of C function signatures to LLVM function signatures, following the calling convention
+//! of a particular platform.
+//!
+//! LLVM leaves much of the C calling convention to the frontend: small structs have to be coerced
+//! into the registers they are passed in, large ones have to be passed through memory, and small
+//! integers have to be extended. A `FunctionAbi` works all of that out for a C signature, and its
+//! builder helpers convert between C level values and the lowered parameters and return value.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use inkwell::abi::{AbiTarget, CType, FunctionAbi, PassMode};
+//! use inkwell::context::Context;
+//! use inkwell::targets::{TargetData, TargetTriple};
+//!
+//! let context = Context::create();
+//! let module = context.create_module("abi");
+//! let builder = context.create_builder();
+//! let target = AbiTarget::from_triple(&TargetTriple::create("x86_64-unknown-linux-gnu")).unwrap();
+//! let target_data = TargetData::create("e-m:e-i64:64-f80:128-n8:16:32:64-S128");
+//! let f64_type = context.f64_type();
+//! let point_type = context.struct_type(&[f64_type.into(), f64_type.into()], false);
+//!
+//! // struct point scale(struct point p, double factor);
+//! let point = CType::Struct(point_type);
+//! let scale_abi = FunctionAbi::new(&context, target, &target_data, Some(point), &[point, CType::Float(f64_type)]);
+//!
+//! assert_eq!(scale_abi.get_params()[0].get_mode(), PassMode::Coerce(point_type.into()));
+//!
+//! let scale = module.add_function("scale", scale_abi.get_signature(), None);
+//!
+//! // struct point double_it(struct point p) { return scale(p, 2.0); }
+//! let double_abi = FunctionAbi::new(&context, target, &target_data, Some(point), &[point]);
+//! let double_it = module.add_function("double_it", double_abi.get_signature(), None);
+//! let entry = context.append_basic_block(double_it, "entry");
+//!
+//! builder.position_at_end(entry);
+//!
+//! let p = double_abi.get_param_values(&builder, double_it)[0];
+//! let two = f64_type.const_float(2.0);
+//! let result = scale_abi.build_call(&builder, scale, &[p, two.into()], "result").unwrap();
+//!
+//! double_abi.build_return(&builder, double_it, Some(result));
+//! ```
+
+use llvm_sys::core::{LLVMCreateBuilderInContext, LLVMGetTypeContext};
+
+use crate::attributes::AttributeLoc;
+use crate::builder::Builder;
+use crate::context::Context;
+use crate::targets::{TargetData, TargetTriple};
+use crate::types::{
+    AsTypeRef, BasicType, BasicTypeEnum, FloatType, FunctionSignature, IntType, ParamAttribute, PointerType,
+    StructType, VoidType,
+};
+use crate::values::{
+    BasicMetadataValueEnum, BasicValue, BasicValueEnum, FunctionValue, InstructionValue, PointerValue,
+};
+use crate::AddressSpace;
+
+/// A platform calling convention for C functions.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum AbiTarget {
+    /// The System V AMD64 ABI, used by x86-64 Linux, the BSDs and macOS.
+    X86_64SysV,
+    /// The procedure call standard for AArch64, as used by AArch64 Linux.
+    AArch64Aapcs,
+    /// Apple's variant of the AArch64 procedure call standard, which also extends small integers.
+    AArch64Darwin,
+}
+
+impl AbiTarget {
+    /// Gets the `AbiTarget` used by a `TargetTriple`, if it is supported.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::abi::AbiTarget;
+    /// use inkwell::targets::TargetTriple;
+    ///
+    /// let triple = TargetTriple::create("arm64-apple-macosx11.0.0");
+    ///
+    /// assert_eq!(AbiTarget::from_triple(&triple), Some(AbiTarget::AArch64Darwin));
+    /// assert_eq!(AbiTarget::from_triple(&TargetTriple::create("x86_64-pc-windows-msvc")), None);
+    /// ```
+    pub fn from_triple(triple: &TargetTriple) -> Option<Self> {
+        let triple = triple.as_str().to_string_lossy();
+        let mut components = triple.split('-');
+        let arch = components.next().unwrap_or_default();
+        let rest: Vec<&str> = components.collect();
+        let has_component = |prefixes: &[&str]| {
+            rest.iter()
+                .any(|component| prefixes.iter().any(|prefix| component.starts_with(prefix)))
+        };
+
+        if has_component(&["windows", "win32", "mingw", "cygwin", "uefi"]) {
+            return None;
+        }
+
+        match arch {
+            "x86_64" | "amd64" => Some(AbiTarget::X86_64SysV),
+            "aarch64" | "arm64" if has_component(&["apple", "darwin", "ios", "macos", "tvos", "watchos"]) => {
+                Some(AbiTarget::AArch64Darwin)
+            },
+            "aarch64" | "arm64" => Some(AbiTarget::AArch64Aapcs),
+            _ => None,
+        }
+    }
+}
+
+/// The type of a C parameter or return value.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CType<'ctx> {
+    /// A signed integer, such as `int` or `signed char`.
+    SignedInt(IntType<'ctx>),
+    /// An unsigned integer, such as `unsigned int`, `size_t` or `bool` (as an `i1` or `i8`).
+    UnsignedInt(IntType<'ctx>),
+    /// A floating point number. `long double` and `_Float128` are only supported outside of structs.
+    Float(FloatType<'ctx>),
+    /// Any pointer.
+    Pointer(PointerType<'ctx>),
+    /// A struct, whose fields may themselves be structs or arrays. Fields of vector types are not supported.
+    Struct(StructType<'ctx>),
+}
+
+impl<'ctx> CType<'ctx> {
+    /// Gets the LLVM type which holds values of this C type.
+    pub fn get_llvm_type(self) -> BasicTypeEnum<'ctx> {
+        match self {
+            CType::SignedInt(ty) | CType::UnsignedInt(ty) => ty.into(),
+            CType::Float(ty) => ty.into(),
+            CType::Pointer(ty) => ty.into(),
+            CType::Struct(ty) => ty.into(),
+        }
+    }
+
+    fn get_extend_attribute(self) -> ParamAttribute<'ctx> {
+        match self {
+            CType::SignedInt(_) => ParamAttribute::SignExt,
+            _ => ParamAttribute::ZeroExt,
+        }
+    }
+}
+
+/// How a C parameter or return value is passed at the LLVM level.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PassMode<'ctx> {
+    /// Passed as its own LLVM type.
+    Direct,
+    /// Passed as its own LLVM type, after being sign or zero extended to 32 bits (`signext` or `zeroext`).
+    Extend,
+    /// Passed as a value of another LLVM type, which holds the same bytes. When the other type is a
+    /// struct, each of its fields is passed as a separate parameter, but it is returned as a whole.
+    Coerce(BasicTypeEnum<'ctx>),
+    /// Passed as a pointer to a copy in memory which is aligned to `align` bytes. With `byval`, the
+    /// copy is made in the argument area of the stack. A return value is written through an `sret`
+    /// pointer given as the first parameter.
+    Indirect {
+        /// Whether the parameter is `byval`.
+        byval: bool,
+        /// The alignment of the copy in bytes.
+        align: u32,
+    },
+    /// Not passed at all, as it takes up no space.
+    Ignore,
+}
+
+/// The lowering of a single C parameter or return value.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ArgAbi<'ctx> {
+    c_type: CType<'ctx>,
+    mode: PassMode<'ctx>,
+    // A stack slot type big enough for both the C type and any coerced type, and its alignment
+    slot_type: BasicTypeEnum<'ctx>,
+    align: u32,
+}
+
+impl<'ctx> ArgAbi<'ctx> {
+    /// Gets the C type of this parameter or return value.
+    pub fn get_c_type(self) -> CType<'ctx> {
+        self.c_type
+    }
+
+    /// Gets how this parameter or return value is passed.
+    pub fn get_mode(self) -> PassMode<'ctx> {
+        self.mode
+    }
+}
+
+/// The lowering of a C function signature for a particular `AbiTarget`. Variadic functions are not supported.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FunctionAbi<'ctx> {
+    return_abi: Option<ArgAbi<'ctx>>,
+    param_abis: Vec<ArgAbi<'ctx>>,
+    void_type: VoidType<'ctx>,
+}
+
+impl<'ctx> FunctionAbi<'ctx> {
+    /// Lowers a C function returning `return_type`, or `void` if it is `None`, and taking parameters
+    /// of `param_types`. `target_data` must describe the data layout of `target`.
+    pub fn new(
+        context: &'ctx Context,
+        target: AbiTarget,
+        target_data: &TargetData,
+        return_type: Option<CType<'ctx>>,
+        param_types: &[CType<'ctx>],
+    ) -> Self {
+        let mut lowering = Lowering {
+            context,
+            target,
+            target_data,
+            free_int_registers: 6,
+            free_sse_registers: 8,
+        };
+        let return_abi = return_type.map(|c_type| lowering.lower(c_type, true));
+        let param_abis = param_types
+            .iter()
+            .map(|&c_type| lowering.lower(c_type, false))
+            .collect();
+
+        FunctionAbi {
+            return_abi,
+            param_abis,
+            void_type: context.void_type(),
+        }
+    }
+
+    /// Gets the lowering of the return value, or `None` if the function returns `void`.
+    pub fn get_return(&self) -> Option<ArgAbi<'ctx>> {
+        self.return_abi
+    }
+
+    /// Gets the lowering of each parameter.
+    pub fn get_params(&self) -> &[ArgAbi<'ctx>] {
+        &self.param_abis
+    }
+
+    /// Gets the lowered `FunctionSignature`, with all of the attributes the calling convention needs.
+    pub fn get_signature(&self) -> FunctionSignature<'ctx> {
+        let mut signature = match self.get_lowered_return_type() {
+            Some(return_type) => FunctionSignature::new(return_type),
+            None => FunctionSignature::new(self.void_type),
+        };
+
+        for attribute in self.get_return_attributes() {
+            signature = signature.return_attribute(attribute);
+        }
+
+        for (ty, attributes) in self.get_lowered_params() {
+            signature = signature.param_with_attributes("", ty, &attributes);
+        }
+
+        signature
+    }
+
+    /// Builds a call to `function`, which must have been declared with `get_signature`, passing the C
+    /// level values `args`. Returns the C level return value, if there is one. Any temporaries are
+    /// allocated in the entry block of the current function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of `args` does not match the number of parameters.
+    pub fn build_call(
+        &self,
+        builder: &Builder<'ctx>,
+        function: FunctionValue<'ctx>,
+        args: &[BasicValueEnum<'ctx>],
+        name: &str,
+    ) -> Option<BasicValueEnum<'ctx>> {
+        assert_eq!(args.len(), self.param_abis.len(), "Wrong number of arguments.");
+
+        let mut lowered_args: Vec<BasicMetadataValueEnum<'ctx>> = Vec::new();
+        let sret_slot = match self.return_abi {
+            Some(
+                abi @ ArgAbi {
+                    mode: PassMode::Indirect { .. },
+                    ..
+                },
+            ) => {
+                let slot = build_entry_alloca(builder, abi.slot_type, abi.align);
+
+                lowered_args.push(slot.into());
+
+                Some(slot)
+            },
+            _ => None,
+        };
+
+        for (abi, &arg) in self.param_abis.iter().zip(args) {
+            match abi.mode {
+                PassMode::Direct | PassMode::Extend => lowered_args.push(arg.into()),
+                PassMode::Coerce(ty) => {
+                    let coerced = build_reinterpret(builder, abi, arg, ty, "");
+
+                    if let BasicTypeEnum::StructType(struct_type) = ty {
+                        for index in 0..struct_type.count_fields() {
+                            let field = builder.build_extract_value(coerced.into_struct_value(), index, "");
+
+                            lowered_args.push(field.unwrap().into());
+                        }
+                    } else {
+                        lowered_args.push(coerced.into());
+                    }
+                },
+                PassMode::Indirect { .. } => {
+                    let slot = build_entry_alloca(builder, abi.slot_type, abi.align);
+
+                    set_alignment(builder.build_store(slot, arg), abi.align);
+                    lowered_args.push(slot.into());
+                },
+                PassMode::Ignore => {},
+            }
+        }
+
+        let call = builder.build_call(function, &lowered_args, name);
+        let context = function.get_type().get_context();
+
+        for (index, (_, attributes)) in self.get_lowered_params().into_iter().enumerate() {
+            for attribute in attributes {
+                call.add_attribute(AttributeLoc::Param(index as u32), attribute.create_attribute(&context));
+            }
+        }
+
+        for attribute in self.get_return_attributes() {
+            call.add_attribute(AttributeLoc::Return, attribute.create_attribute(&context));
+        }
+
+        let abi = self.return_abi?;
+        let value = match abi.mode {
+            PassMode::Direct | PassMode::Extend => call.try_as_basic_value().left().unwrap(),
+            PassMode::Coerce(_) => {
+                let coerced = call.try_as_basic_value().left().unwrap();
+
+                build_reinterpret(builder, &abi, coerced, abi.c_type.get_llvm_type(), name)
+            },
+            PassMode::Indirect { .. } => build_aligned_load(builder, sret_slot.unwrap(), abi.align, name),
+            PassMode::Ignore => abi.c_type.get_llvm_type().into_struct_type().get_undef().into(),
+        };
+
+        Some(value)
+    }
+
+    /// Gets the C level values of the parameters of `function`, which must have been declared with
+    /// `get_signature`. `builder` must be positioned within `function`, ideally in its entry block.
+    pub fn get_param_values(
+        &self,
+        builder: &Builder<'ctx>,
+        function: FunctionValue<'ctx>,
+    ) -> Vec<BasicValueEnum<'ctx>> {
+        let mut params = function.get_params().into_iter();
+
+        if let Some(ArgAbi {
+            mode: PassMode::Indirect { .. },
+            ..
+        }) = self.return_abi
+        {
+            params.next();
+        }
+
+        self.param_abis
+            .iter()
+            .map(|abi| match abi.mode {
+                PassMode::Direct | PassMode::Extend => params.next().unwrap(),
+                PassMode::Coerce(ty) => {
+                    let coerced = if let BasicTypeEnum::StructType(struct_type) = ty {
+                        (0..struct_type.count_fields())
+                            .fold(struct_type.get_undef(), |aggregate, index| {
+                                builder
+                                    .build_insert_value(aggregate, params.next().unwrap(), index, "")
+                                    .unwrap()
+                                    .into_struct_value()
+                            })
+                            .into()
+                    } else {
+                        params.next().unwrap()
+                    };
+
+                    build_reinterpret(builder, abi, coerced, abi.c_type.get_llvm_type(), "")
+                },
+                PassMode::Indirect { .. } => {
+                    let ptr = params.next().unwrap().into_pointer_value();
+
+                    build_aligned_load(builder, ptr, abi.align, "")
+                },
+                PassMode::Ignore => abi.c_type.get_llvm_type().into_struct_type().get_undef().into(),
+            })
+            .collect()
+    }
+
+    /// Builds a return of the C level `value` from `function`, which must have been declared with
+    /// `get_signature`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a value is given for a `void` function, or none is given otherwise.
+    pub fn build_return(
+        &self,
+        builder: &Builder<'ctx>,
+        function: FunctionValue<'ctx>,
+        value: Option<BasicValueEnum<'ctx>>,
+    ) -> InstructionValue<'ctx> {
+        let (abi, value) = match (self.return_abi, value) {
+            (None, None) => return builder.build_return(None),
+            (Some(abi), Some(value)) => (abi, value),
+            _ => panic!("Return value does not match the signature."),
+        };
+
+        match abi.mode {
+            PassMode::Direct | PassMode::Extend => builder.build_return(Some(&value)),
+            PassMode::Coerce(ty) => {
+                let coerced = build_reinterpret(builder, &abi, value, ty, "");
+
+                builder.build_return(Some(&coerced))
+            },
+            PassMode::Indirect { .. } => {
+                let sret = function.get_first_param().unwrap().into_pointer_value();
+
+                set_alignment(builder.build_store(sret, value), abi.align);
+                builder.build_return(None)
+            },
+            PassMode::Ignore => builder.build_return(None),
+        }
+    }
+
+    fn get_lowered_return_type(&self) -> Option<BasicTypeEnum<'ctx>> {
+        let abi = self.return_abi?;
+
+        match abi.mode {
+            PassMode::Direct | PassMode::Extend => Some(abi.c_type.get_llvm_type()),
+            PassMode::Coerce(ty) => Some(ty),
+            PassMode::Indirect { .. } | PassMode::Ignore => None,
+        }
+    }
+
+    fn get_return_attributes(&self) -> Vec<ParamAttribute<'ctx>> {
+        match self.return_abi {
+            Some(abi) if abi.mode == PassMode::Extend => vec![abi.c_type.get_extend_attribute()],
+            _ => Vec::new(),
+        }
+    }
+
+    fn get_lowered_params(&self) -> Vec<(BasicTypeEnum<'ctx>, Vec<ParamAttribute<'ctx>>)> {
+        let mut params = Vec::new();
+
+        if let Some(
+            abi @ ArgAbi {
+                mode: PassMode::Indirect { .. },
+                ..
+            },
+        ) = self.return_abi
+        {
+            let c_type = abi.c_type.get_llvm_type();
+
+            params.push((
+                c_type.ptr_type(AddressSpace::Generic).into(),
+                vec![ParamAttribute::StructRet(c_type), ParamAttribute::NoAlias],
+            ));
+        }
+
+        for abi in &self.param_abis {
+            let c_type = abi.c_type.get_llvm_type();
+
+            match abi.mode {
+                PassMode::Direct => params.push((c_type, Vec::new())),
+                PassMode::Extend => params.push((c_type, vec![abi.c_type.get_extend_attribute()])),
+                PassMode::Coerce(BasicTypeEnum::StructType(struct_type)) => {
+                    params.extend(struct_type.get_field_types().into_iter().map(|ty| (ty, Vec::new())))
+                },
+                PassMode::Coerce(ty) => params.push((ty, Vec::new())),
+                PassMode::Indirect { byval, align } => {
+                    let attributes = if byval {
+                        vec![ParamAttribute::ByVal(c_type), ParamAttribute::Align(align)]
+                    } else {
+                        Vec::new()
+                    };
+
+                    params.push((c_type.ptr_type(AddressSpace::Generic).into(), attributes));
+                },
+                PassMode::Ignore => {},
+            }
+        }
+
+        params
+    }
+}
+
+// Classifications of an eightbyte in the System V AMD64 ABI
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum X86_64Class {
+    NoClass,
+    Integer,
+    Sse,
+    Memory,
+}
+
+impl X86_64Class {
+    fn merge(self, other: X86_64Class) -> X86_64Class {
+        use X86_64Class::*;
+
+        match (self, other) {
+            (class, NoClass) | (NoClass, class) => class,
+            (Memory, _) | (_, Memory) => Memory,
+            (Integer, _) | (_, Integer) => Integer,
+            (Sse, Sse) => Sse,
+        }
+    }
+}
+
+struct Lowering<'a, 'ctx> {
+    context: &'ctx Context,
+    target: AbiTarget,
+    target_data: &'a TargetData,
+    free_int_registers: u32,
+    free_sse_registers: u32,
+}
+
+impl<'a, 'ctx> Lowering<'a, 'ctx> {
+    fn lower(&mut self, c_type: CType<'ctx>, is_return: bool) -> ArgAbi<'ctx> {
+        let mode = match self.target {
+            AbiTarget::X86_64SysV => self.lower_x86_64(c_type, is_return),
+            AbiTarget::AArch64Aapcs | AbiTarget::AArch64Darwin => self.lower_aarch64(c_type, is_return),
+        };
+        let llvm_type = c_type.get_llvm_type();
+        let mut slot_type = llvm_type;
+        let mut align = self.target_data.get_abi_alignment(&llvm_type);
+
+        match mode {
+            PassMode::Coerce(ty) => {
+                if self.target_data.get_abi_size(&ty) > self.target_data.get_abi_size(&llvm_type) {
+                    slot_type = ty;
+                }
+
+                align = align.max(self.target_data.get_abi_alignment(&ty));
+            },
+            PassMode::Indirect {
+                align: indirect_align, ..
+            } => align = indirect_align,
+            _ => {},
+        }
+
+        ArgAbi {
+            c_type,
+            mode,
+            slot_type,
+            align,
+        }
+    }
+
+    fn lower_x86_64(&mut self, c_type: CType<'ctx>, is_return: bool) -> PassMode<'ctx> {
+        let llvm_type = c_type.get_llvm_type();
+        let size = self.target_data.get_abi_size(&llvm_type);
+        let align = self.target_data.get_abi_alignment(&llvm_type);
+
+        let struct_type = match c_type {
+            CType::Struct(struct_type) => struct_type,
+            _ => return self.lower_x86_64_scalar(c_type, size, is_return),
+        };
+
+        if size == 0 {
+            return PassMode::Ignore;
+        }
+
+        let memory = PassMode::Indirect {
+            byval: !is_return,
+            align: if is_return { align } else { align.max(8) },
+        };
+
+        if size > 16 {
+            return self.pass_x86_64_in_memory(memory, is_return);
+        }
+
+        let mut scalars = Vec::new();
+        let mut classes = [X86_64Class::NoClass; 2];
+
+        flatten_scalars(self.target_data, struct_type.into(), 0, &mut scalars);
+
+        for &(offset, ty) in &scalars {
+            let scalar_size = self.target_data.get_abi_size(&ty);
+            let class = match ty {
+                _ if offset % u64::from(self.target_data.get_abi_alignment(&ty)) != 0 => X86_64Class::Memory,
+                BasicTypeEnum::FloatType(_) if scalar_size <= 8 => X86_64Class::Sse,
+                BasicTypeEnum::FloatType(_) => X86_64Class::Memory,
+                _ => X86_64Class::Integer,
+            };
+
+            for eightbyte in offset / 8..=(offset + scalar_size.max(1) - 1) / 8 {
+                classes[eightbyte as usize] = classes[eightbyte as usize].merge(class);
+            }
+        }
+
+        if classes.contains(&X86_64Class::Memory) {
+            return self.pass_x86_64_in_memory(memory, is_return);
+        }
+
+        if scalars.is_empty() {
+            return PassMode::Ignore;
+        }
+
+        let classes = &mut classes[..if size > 8 { 2 } else { 1 }];
+
+        for class in classes.iter_mut() {
+            if *class == X86_64Class::NoClass {
+                *class = X86_64Class::Integer;
+            }
+        }
+
+        let int_registers = classes.iter().filter(|&&class| class == X86_64Class::Integer).count() as u32;
+        let sse_registers = classes.len() as u32 - int_registers;
+
+        if !is_return {
+            if int_registers > self.free_int_registers || sse_registers > self.free_sse_registers {
+                return memory;
+            }
+
+            self.free_int_registers -= int_registers;
+            self.free_sse_registers -= sse_registers;
+        }
+
+        let eightbyte_types: Vec<BasicTypeEnum<'ctx>> = classes
+            .iter()
+            .enumerate()
+            .map(|(index, &class)| {
+                let start = index as u64 * 8;
+
+                if class == X86_64Class::Integer {
+                    return self.int_type((size - start).min(8)).into();
+                }
+
+                let floats: Vec<BasicTypeEnum<'ctx>> = scalars
+                    .iter()
+                    .filter(|(offset, _)| *offset >= start && *offset < start + 8)
+                    .map(|&(_, ty)| ty)
+                    .collect();
+
+                match floats[..] {
+                    [ty] => ty,
+                    [first, ..] if floats.iter().all(|&ty| ty == first) => {
+                        first.into_float_type().vec_type(floats.len() as u32).into()
+                    },
+                    _ => self.context.f64_type().into(),
+                }
+            })
+            .collect();
+
+        match eightbyte_types[..] {
+            [ty] => PassMode::Coerce(ty),
+            _ => PassMode::Coerce(self.context.struct_type(&eightbyte_types, false).into()),
+        }
+    }
+
+    // Scalars are left to LLVM, but still use up registers which later structs could have used
+    fn lower_x86_64_scalar(&mut self, c_type: CType<'ctx>, size: u64, is_return: bool) -> PassMode<'ctx> {
+        let (int_registers, sse_registers, mode) = match c_type {
+            CType::SignedInt(ty) | CType::UnsignedInt(ty) if ty.get_bit_width() < 32 => (1, 0, PassMode::Extend),
+            CType::SignedInt(ty) | CType::UnsignedInt(ty) if ty.get_bit_width() > 64 => (2, 0, PassMode::Direct),
+            CType::SignedInt(_) | CType::UnsignedInt(_) | CType::Pointer(_) => (1, 0, PassMode::Direct),
+            CType::Float(_) if size <= 8 => (0, 1, PassMode::Direct),
+            // fp128 is passed in a single SSE register, while x86_fp80 goes on the stack
+            CType::Float(ty) if ty == self.context.f128_type() => (0, 1, PassMode::Direct),
+            CType::Float(_) => (0, 0, PassMode::Direct),
+            CType::Struct(_) => unreachable!(),
+        };
+
+        if !is_return {
+            self.free_int_registers = self.free_int_registers.saturating_sub(int_registers);
+            self.free_sse_registers = self.free_sse_registers.saturating_sub(sse_registers);
+        }
+
+        mode
+    }
+
+    fn pass_x86_64_in_memory(&mut self, memory: PassMode<'ctx>, is_return: bool) -> PassMode<'ctx> {
+        // The sret pointer is passed in the first integer register
+        if is_return {
+            self.free_int_registers -= 1;
+        }
+
+        memory
+    }
+
+    fn lower_aarch64(&mut self, c_type: CType<'ctx>, is_return: bool) -> PassMode<'ctx> {
+        let struct_type = match c_type {
+            CType::SignedInt(ty) | CType::UnsignedInt(ty) => {
+                return if ty.get_bit_width() < 32 && self.target == AbiTarget::AArch64Darwin {
+                    PassMode::Extend
+                } else {
+                    PassMode::Direct
+                };
+            },
+            CType::Float(_) | CType::Pointer(_) => return PassMode::Direct,
+            CType::Struct(struct_type) => struct_type,
+        };
+
+        let size = self.target_data.get_abi_size(&struct_type);
+        let align = self.target_data.get_abi_alignment(&struct_type);
+
+        if size == 0 {
+            return PassMode::Ignore;
+        }
+
+        // Homogeneous floating point aggregates of up to four members go in SIMD registers
+        if size <= 64 {
+            let mut scalars = Vec::new();
+
+            flatten_scalars(self.target_data, struct_type.into(), 0, &mut scalars);
+
+            if let Some(&(_, first)) = scalars.first() {
+                let is_hfa = first.is_float_type()
+                    && scalars.len() <= 4
+                    && scalars.iter().all(|&(_, ty)| ty == first)
+                    && size == scalars.len() as u64 * self.target_data.get_abi_size(&first);
+
+                if is_hfa {
+                    return PassMode::Coerce(first.array_type(scalars.len() as u32).into());
+                }
+            }
+        }
+
+        if size > 16 {
+            return PassMode::Indirect { byval: false, align };
+        }
+
+        let coerced = if is_return && size <= 8 {
+            self.int_type(size).into()
+        } else if size <= 8 {
+            self.context.i64_type().into()
+        } else if align >= 16 {
+            self.context.i128_type().into()
+        } else {
+            self.context.i64_type().array_type(2).into()
+        };
+
+        PassMode::Coerce(coerced)
+    }
+
+    fn int_type(&self, bytes: u64) -> IntType<'ctx> {
+        self.context.custom_width_int_type(bytes as u32 * 8)
+    }
+}
+
+// Collects the scalar leaves of `ty`, along with their offsets
+fn flatten_scalars<'ctx>(
+    target_data: &TargetData,
+    ty: BasicTypeEnum<'ctx>,
+    offset: u64,
+    scalars: &mut Vec<(u64, BasicTypeEnum<'ctx>)>,
+) {
+    match ty {
+        BasicTypeEnum::StructType(struct_type) => {
+            for (index, field_type) in struct_type.get_field_types().into_iter().enumerate() {
+                let field_offset = target_data.offset_of_element(&struct_type, index as u32).unwrap();
+
+                flatten_scalars(target_data, field_type, offset + field_offset, scalars);
+            }
+        },
+        BasicTypeEnum::ArrayType(array_type) => {
+            let element_type = array_type.get_element_type();
+            let element_size = target_data.get_abi_size(&element_type);
+
+            for index in 0..u64::from(array_type.len()) {
+                flatten_scalars(target_data, element_type, offset + index * element_size, scalars);
+            }
+        },
+        BasicTypeEnum::VectorType(_) => panic!("Vector types are not supported in structs."),
+        _ => scalars.push((offset, ty)),
+    }
+}
+
+// Allocates a stack slot at the start of the entry block of the builder's current function
+fn build_entry_alloca<'ctx>(builder: &Builder<'ctx>, ty: BasicTypeEnum<'ctx>, align: u32) -> PointerValue<'ctx> {
+    let entry = builder
+        .get_insert_block()
+        .and_then(|block| block.get_parent())
+        .and_then(|function| function.get_first_basic_block())
+        .expect("Builder must be positioned within a function.");
+    let entry_builder = unsafe { Builder::new(LLVMCreateBuilderInContext(LLVMGetTypeContext(ty.as_type_ref()))) };
+
+    match entry.get_first_instruction() {
+        Some(instruction) => entry_builder.position_before(&instruction),
+        None => entry_builder.position_at_end(entry),
+    }
+
+    let slot = entry_builder.build_alloca(ty, "");
+
+    set_alignment(slot.as_instruction().unwrap(), align);
+    slot
+}
+
+// Reinterprets the bytes of `value` as `to_type` by way of a stack slot
+fn build_reinterpret<'ctx>(
+    builder: &Builder<'ctx>,
+    abi: &ArgAbi<'ctx>,
+    value: BasicValueEnum<'ctx>,
+    to_type: BasicTypeEnum<'ctx>,
+    name: &str,
+) -> BasicValueEnum<'ctx> {
+    let slot = build_entry_alloca(builder, abi.slot_type, abi.align);
+    let value_ptr = builder.build_pointer_cast(slot, value.get_type().ptr_type(AddressSpace::Generic), "");
+    let to_ptr = builder.build_pointer_cast(slot, to_type.ptr_type(AddressSpace::Generic), "");
+
+    set_alignment(builder.build_store(value_ptr, value), abi.align);
+    build_aligned_load(builder, to_ptr, abi.align, name)
+}
+
+fn build_aligned_load<'ctx>(
+    builder: &Builder<'ctx>,
+    ptr: PointerValue<'ctx>,
+    align: u32,
+    name: &str,
+) -> BasicValueEnum<'ctx> {
+    let value = builder.build_load(ptr, name);
+
+    set_alignment(value.as_instruction_value().unwrap(), align);
+    value
+}
+
+fn set_alignment(instruction: InstructionValue, align: u32) {
+    instruction
+        .set_alignment(align)
+        .expect("Alignment should be a power of two");
+}
@@ -0,0 +1,696 @@
+use llvm_sys::core::LLVMGetSuccessor;
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use crate::basic_block::BasicBlock;
+use crate::values::{AsValueRef, BasicValueUse, FunctionValue, InstructionOpcode, InstructionValue, PhiValue};
+
+/// The dominator tree of a `FunctionValue`. A block dominates another when every path from the
+/// entry block to the latter goes through the former.
+///
+/// Following LLVM, blocks unreachable from the entry block are dominated by every block but
+/// dominate none themselves.
+///
+/// # Example
+///
+/// ```no_run
+/// use inkwell::analysis::DominatorTree;
+/// use inkwell::context::Context;
+///
+/// let context = Context::create();
+/// let builder = context.create_builder();
+/// let module = context.create_module("my_mod");
+/// let bool_type = context.bool_type();
+/// let fn_type = context.void_type().fn_type(&[bool_type.into()], false);
+/// let function = module.add_function("diamond", fn_type, None);
+/// let entry = context.append_basic_block(function, "entry");
+/// let then_block = context.append_basic_block(function, "then");
+/// let else_block = context.append_basic_block(function, "else");
+/// let exit = context.append_basic_block(function, "exit");
+/// let condition = function.get_first_param().unwrap().into_int_value();
+///
+/// builder.position_at_end(entry);
+/// builder.build_conditional_branch(condition, then_block, else_block);
+/// builder.position_at_end(then_block);
+/// builder.build_unconditional_branch(exit);
+/// builder.position_at_end(else_block);
+/// builder.build_unconditional_branch(exit);
+/// builder.position_at_end(exit);
+/// builder.build_return(None);
+///
+/// let dominator_tree = DominatorTree::compute(function);
+///
+/// assert!(dominator_tree.dominates(entry, exit));
+/// assert!(!dominator_tree.dominates(then_block, exit));
+/// assert_eq!(dominator_tree.idom(exit), Some(entry));
+/// assert_eq!(dominator_tree.dominance_frontier(then_block), vec![exit]);
+/// ```
+#[derive(Debug)]
+pub struct DominatorTree<'ctx> {
+    tree: Tree<'ctx>,
+}
+
+impl<'ctx> DominatorTree<'ctx> {
+    /// Computes the dominator tree of a `FunctionValue` from its current control flow graph.
+    pub fn compute(function: FunctionValue<'ctx>) -> Self {
+        let blocks = function.get_basic_blocks();
+        let indices = index_blocks(&blocks);
+        let edges = blocks
+            .iter()
+            .map(|block| {
+                block
+                    .get_successors()
+                    .iter()
+                    .map(|successor| indices[successor])
+                    .collect()
+            })
+            .collect();
+        let roots = if blocks.is_empty() { Vec::new() } else { vec![0] };
+
+        DominatorTree {
+            tree: Tree::new(blocks, indices, edges, roots),
+        }
+    }
+
+    /// Gets the entry block at the root of the tree, if the function has a body.
+    pub fn get_root(&self) -> Option<BasicBlock<'ctx>> {
+        self.tree.blocks.first().copied()
+    }
+
+    /// Determines whether a block is reachable from the entry block.
+    pub fn is_reachable(&self, block: BasicBlock<'ctx>) -> bool {
+        self.tree.node(block).is_some()
+    }
+
+    /// Determines whether `a` dominates `b`. Every block dominates itself.
+    pub fn dominates(&self, a: BasicBlock<'ctx>, b: BasicBlock<'ctx>) -> bool {
+        self.tree.dominates(a, b)
+    }
+
+    /// Determines whether `a` dominates `b` and they are distinct blocks.
+    pub fn strictly_dominates(&self, a: BasicBlock<'ctx>, b: BasicBlock<'ctx>) -> bool {
+        a != b && self.dominates(a, b)
+    }
+
+    /// Gets the immediate dominator of a block, which is its parent in the tree. The entry block
+    /// and unreachable blocks have none.
+    pub fn idom(&self, block: BasicBlock<'ctx>) -> Option<BasicBlock<'ctx>> {
+        self.tree.idom(block)
+    }
+
+    /// Gets the blocks immediately dominated by a block, in layout order.
+    pub fn get_children(&self, block: BasicBlock<'ctx>) -> Vec<BasicBlock<'ctx>> {
+        self.tree.children(block)
+    }
+
+    /// Gets the closest block dominating both `a` and `b`, or `None` if either is unreachable.
+    pub fn nearest_common_dominator(&self, a: BasicBlock<'ctx>, b: BasicBlock<'ctx>) -> Option<BasicBlock<'ctx>> {
+        self.tree.nearest_common_dominator(a, b)
+    }
+
+    /// Gets the dominance frontier of a block, in layout order: the blocks where its dominance
+    /// ends, which is where SSA construction places phi nodes for values defined in it.
+    pub fn dominance_frontier(&self, block: BasicBlock<'ctx>) -> Vec<BasicBlock<'ctx>> {
+        self.tree.frontier(block)
+    }
+
+    /// Determines whether the value defined by `def` is available at `user`, that is, whether
+    /// `def` strictly precedes `user` on every path to it.
+    ///
+    /// As in LLVM, a phi `user` must be dominated from the start of its block, and the result of an
+    /// `invoke` is only available once its normal destination is reached. For the availability of an
+    /// operand of a phi, which depends on the incoming block, see `dominates_use`.
+    pub fn instruction_dominates(&self, def: InstructionValue<'ctx>, user: InstructionValue<'ctx>) -> bool {
+        let (def_block, user_block) = match (def.get_parent(), user.get_parent()) {
+            (Some(def_block), Some(user_block)) => (def_block, user_block),
+            _ => return false,
+        };
+
+        // Any unreachable use is dominated, even by itself
+        if !self.is_reachable(user_block) {
+            return true;
+        }
+
+        if !self.is_reachable(def_block) || def == user {
+            return false;
+        }
+
+        if defines_on_edge(def) || user.get_opcode() == InstructionOpcode::Phi {
+            return self.dominates_block_from_instruction(def, def_block, user_block);
+        }
+
+        if def_block != user_block {
+            return self.dominates(def_block, user_block);
+        }
+
+        comes_before(def, user)
+    }
+
+    /// Determines whether the value defined by `def` is available at one of its uses. A use by a
+    /// phi happens at the end of the corresponding incoming block. Uses outside of instructions,
+    /// such as in constants, are never dominated.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::analysis::DominatorTree;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let builder = context.create_builder();
+    /// let module = context.create_module("my_mod");
+    /// let i32_type = context.i32_type();
+    /// let fn_type = i32_type.fn_type(&[i32_type.into()], false);
+    /// let function = module.add_function("my_fn", fn_type, None);
+    /// let entry = context.append_basic_block(function, "entry");
+    /// let param = function.get_first_param().unwrap().into_int_value();
+    ///
+    /// builder.position_at_end(entry);
+    ///
+    /// let double = builder.build_int_add(param, param, "double");
+    /// let ret = builder.build_return(Some(&double));
+    /// let double = double.as_instruction().unwrap();
+    ///
+    /// let dominator_tree = DominatorTree::compute(function);
+    ///
+    /// assert!(dominator_tree.dominates_use(double, ret.get_operand_use(0).unwrap()));
+    /// assert!(!dominator_tree.instruction_dominates(ret, double));
+    /// ```
+    pub fn dominates_use(&self, def: InstructionValue<'ctx>, use_: BasicValueUse<'ctx>) -> bool {
+        let user = match use_.get_user().as_instruction_value() {
+            Some(user) => user,
+            None => return false,
+        };
+        let (def_block, user_block) = match (def.get_parent(), user.get_parent()) {
+            (Some(def_block), Some(user_block)) => (def_block, user_block),
+            _ => return false,
+        };
+        let incoming_block = PhiValue::try_from(user).ok().and_then(|phi| {
+            let index = (0..user.get_num_operands()).find(|&index| user.get_operand_use(index) == Some(use_))?;
+
+            phi.get_incoming(index).map(|(_, block)| block)
+        });
+        let use_block = incoming_block.unwrap_or(user_block);
+
+        if !self.is_reachable(use_block) {
+            return true;
+        }
+
+        if !self.is_reachable(def_block) {
+            return false;
+        }
+
+        if let Some(destination) = get_edge_destination(def) {
+            // A phi at the destination reading the value along the defining edge itself
+            if incoming_block == Some(def_block) && user_block == destination {
+                return true;
+            }
+
+            return self.edge_dominates(def_block, destination, use_block);
+        }
+
+        if def_block != use_block {
+            return self.dominates(def_block, use_block);
+        }
+
+        // Phis use their operands at the very end of the incoming block
+        incoming_block.is_some() || comes_before(def, user)
+    }
+
+    fn dominates_block_from_instruction(
+        &self,
+        def: InstructionValue<'ctx>,
+        def_block: BasicBlock<'ctx>,
+        block: BasicBlock<'ctx>,
+    ) -> bool {
+        if def_block == block {
+            return false;
+        }
+
+        match get_edge_destination(def) {
+            Some(destination) => self.edge_dominates(def_block, destination, block),
+            None => self.dominates(def_block, block),
+        }
+    }
+
+    // Whether every path to `block` goes through the edge from `start` to `end`
+    fn edge_dominates(&self, start: BasicBlock<'ctx>, end: BasicBlock<'ctx>, block: BasicBlock<'ctx>) -> bool {
+        if !self.dominates(end, block) {
+            return false;
+        }
+
+        let predecessors = match self.tree.indices.get(&end) {
+            Some(&end) => &self.tree.predecessors[end],
+            None => return false,
+        };
+
+        if predecessors.len() == 1 {
+            return true;
+        }
+
+        let mut edges_from_start = 0;
+
+        for &predecessor in predecessors {
+            let predecessor = self.tree.blocks[predecessor];
+
+            if predecessor == start {
+                // Duplicate edges can't be told apart, so neither dominates
+                edges_from_start += 1;
+
+                if edges_from_start > 1 {
+                    return false;
+                }
+            } else if !self.dominates(end, predecessor) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// The post-dominator tree of a `FunctionValue`. A block post-dominates another when every path
+/// from the latter to an exit of the function goes through the former. The exits are the blocks
+/// without successors, such as those ending in `ret` or `unreachable`.
+///
+/// Blocks which can't reach an exit, such as those in infinite loops, are post-dominated by every
+/// block but post-dominate none themselves.
+///
+/// # Example
+///
+/// ```no_run
+/// use inkwell::analysis::PostDominatorTree;
+/// use inkwell::context::Context;
+///
+/// let context = Context::create();
+/// let builder = context.create_builder();
+/// let module = context.create_module("my_mod");
+/// let bool_type = context.bool_type();
+/// let fn_type = context.void_type().fn_type(&[bool_type.into()], false);
+/// let function = module.add_function("early_exit", fn_type, None);
+/// let entry = context.append_basic_block(function, "entry");
+/// let work = context.append_basic_block(function, "work");
+/// let exit = context.append_basic_block(function, "exit");
+/// let condition = function.get_first_param().unwrap().into_int_value();
+///
+/// builder.position_at_end(entry);
+/// builder.build_conditional_branch(condition, work, exit);
+/// builder.position_at_end(work);
+/// builder.build_unconditional_branch(exit);
+/// builder.position_at_end(exit);
+/// builder.build_return(None);
+///
+/// let post_dominator_tree = PostDominatorTree::compute(function);
+///
+/// assert!(post_dominator_tree.dominates(exit, entry));
+/// assert!(!post_dominator_tree.dominates(work, entry));
+/// assert_eq!(post_dominator_tree.idom(entry), Some(exit));
+/// assert_eq!(post_dominator_tree.dominance_frontier(work), vec![entry]);
+/// ```
+#[derive(Debug)]
+pub struct PostDominatorTree<'ctx> {
+    tree: Tree<'ctx>,
+}
+
+impl<'ctx> PostDominatorTree<'ctx> {
+    /// Computes the post-dominator tree of a `FunctionValue` from its current control flow graph.
+    pub fn compute(function: FunctionValue<'ctx>) -> Self {
+        let blocks = function.get_basic_blocks();
+        let indices = index_blocks(&blocks);
+        let mut edges = vec![Vec::new(); blocks.len()];
+        let mut roots = Vec::new();
+
+        for (index, block) in blocks.iter().enumerate() {
+            let successors = block.get_successors();
+
+            if successors.is_empty() {
+                roots.push(index);
+            }
+
+            for successor in successors {
+                edges[indices[&successor]].push(index);
+            }
+        }
+
+        PostDominatorTree {
+            tree: Tree::new(blocks, indices, edges, roots),
+        }
+    }
+
+    /// Gets the exit blocks at the roots of the tree, in layout order.
+    pub fn get_roots(&self) -> Vec<BasicBlock<'ctx>> {
+        self.tree.roots()
+    }
+
+    /// Determines whether an exit of the function is reachable from a block.
+    pub fn is_reachable(&self, block: BasicBlock<'ctx>) -> bool {
+        self.tree.node(block).is_some()
+    }
+
+    /// Determines whether `a` post-dominates `b`. Every block post-dominates itself.
+    pub fn dominates(&self, a: BasicBlock<'ctx>, b: BasicBlock<'ctx>) -> bool {
+        self.tree.dominates(a, b)
+    }
+
+    /// Determines whether `a` post-dominates `b` and they are distinct blocks.
+    pub fn strictly_dominates(&self, a: BasicBlock<'ctx>, b: BasicBlock<'ctx>) -> bool {
+        a != b && self.dominates(a, b)
+    }
+
+    /// Gets the immediate post-dominator of a block. Exit blocks, blocks which can't reach an exit
+    /// and blocks which may lead to several exits have none.
+    pub fn idom(&self, block: BasicBlock<'ctx>) -> Option<BasicBlock<'ctx>> {
+        self.tree.idom(block)
+    }
+
+    /// Gets the blocks immediately post-dominated by a block, in layout order.
+    pub fn get_children(&self, block: BasicBlock<'ctx>) -> Vec<BasicBlock<'ctx>> {
+        self.tree.children(block)
+    }
+
+    /// Gets the closest block post-dominating both `a` and `b`, or `None` if there's none, as when
+    /// they lead to different exits.
+    pub fn nearest_common_dominator(&self, a: BasicBlock<'ctx>, b: BasicBlock<'ctx>) -> Option<BasicBlock<'ctx>> {
+        self.tree.nearest_common_dominator(a, b)
+    }
+
+    /// Gets the post-dominance frontier of a block, in layout order: the blocks whose branches
+    /// decide whether it executes, which it is said to be control dependent on.
+    pub fn dominance_frontier(&self, block: BasicBlock<'ctx>) -> Vec<BasicBlock<'ctx>> {
+        self.tree.frontier(block)
+    }
+
+    /// Determines whether `a` strictly follows `b` on every path from `b` to an exit.
+    pub fn instruction_dominates(&self, a: InstructionValue<'ctx>, b: InstructionValue<'ctx>) -> bool {
+        let (a_block, b_block) = match (a.get_parent(), b.get_parent()) {
+            (Some(a_block), Some(b_block)) => (a_block, b_block),
+            _ => return false,
+        };
+
+        if !self.is_reachable(b_block) {
+            return true;
+        }
+
+        if a_block != b_block {
+            return self.dominates(a_block, b_block);
+        }
+
+        comes_before(b, a)
+    }
+}
+
+// A dominator tree over the blocks of a function, or over the reversed graph for post dominators.
+// Nodes are indices into `blocks`, with an extra virtual root above the real roots, so that the
+// post dominator tree is a single tree even when there are several exits.
+#[derive(Debug)]
+struct Tree<'ctx> {
+    blocks: Vec<BasicBlock<'ctx>>,
+    indices: HashMap<BasicBlock<'ctx>, usize>,
+    roots: Vec<usize>,
+    predecessors: Vec<Vec<usize>>,
+    // `None` for nodes unreachable from the root, which is its own immediate dominator
+    idoms: Vec<Option<usize>>,
+    children: Vec<Vec<usize>>,
+    frontiers: Vec<Vec<usize>>,
+    // Preorder and postorder numbers in the tree, which make dominance queries constant time
+    preorder_numbers: Vec<usize>,
+    postorder_numbers: Vec<usize>,
+}
+
+impl<'ctx> Tree<'ctx> {
+    // Uses the iterative algorithm of Cooper, Harvey and Kennedy, "A Simple, Fast Dominance Algorithm"
+    fn new(
+        blocks: Vec<BasicBlock<'ctx>>,
+        indices: HashMap<BasicBlock<'ctx>, usize>,
+        mut successors: Vec<Vec<usize>>,
+        roots: Vec<usize>,
+    ) -> Self {
+        let root = blocks.len();
+        let node_count = root + 1;
+
+        successors.push(roots.clone());
+
+        let mut predecessors = vec![Vec::new(); node_count];
+
+        for (node, node_successors) in successors.iter().enumerate() {
+            for &successor in node_successors {
+                predecessors[successor].push(node);
+            }
+        }
+
+        let postorder = depth_first_postorder(root, &successors);
+        let mut cfg_postorder_numbers = vec![0; node_count];
+
+        for (number, &node) in postorder.iter().enumerate() {
+            cfg_postorder_numbers[node] = number;
+        }
+
+        let mut idoms = vec![None; node_count];
+        let mut changed = true;
+
+        idoms[root] = Some(root);
+
+        while changed {
+            changed = false;
+
+            // In reverse postorder, skipping the root which comes last in postorder
+            for &node in postorder.iter().rev().skip(1) {
+                let mut new_idom = None;
+
+                for &predecessor in &predecessors[node] {
+                    if idoms[predecessor].is_none() {
+                        continue;
+                    }
+
+                    new_idom = Some(match new_idom {
+                        Some(other) => intersect(&idoms, &cfg_postorder_numbers, predecessor, other),
+                        None => predecessor,
+                    });
+                }
+
+                if idoms[node] != new_idom {
+                    idoms[node] = new_idom;
+                    changed = true;
+                }
+            }
+        }
+
+        let mut children = vec![Vec::new(); node_count];
+
+        for (node, idom) in idoms.iter().enumerate().take(root) {
+            if let Some(idom) = *idom {
+                children[idom].push(node);
+            }
+        }
+
+        let mut frontiers = vec![Vec::new(); node_count];
+
+        for (node, idom) in idoms.iter().enumerate().take(root) {
+            let idom = match *idom {
+                Some(idom) => idom,
+                None => continue,
+            };
+
+            for &predecessor in &predecessors[node] {
+                if idoms[predecessor].is_none() {
+                    continue;
+                }
+
+                let mut runner = predecessor;
+
+                while runner != idom {
+                    if !frontiers[runner].contains(&node) {
+                        frontiers[runner].push(node);
+                    }
+
+                    runner = idoms[runner].expect("Dominators of reachable nodes should be reachable");
+                }
+            }
+        }
+
+        let mut preorder_numbers = vec![0; node_count];
+        let mut postorder_numbers = vec![0; node_count];
+        let mut next_preorder_number = 1;
+        let mut next_postorder_number = 0;
+        let mut stack = vec![(root, 0)];
+
+        while let Some((node, next_child)) = stack.last_mut() {
+            let node = *node;
+
+            match children[node].get(*next_child) {
+                Some(&child) => {
+                    *next_child += 1;
+                    preorder_numbers[child] = next_preorder_number;
+                    next_preorder_number += 1;
+                    stack.push((child, 0));
+                },
+                None => {
+                    postorder_numbers[node] = next_postorder_number;
+                    next_postorder_number += 1;
+                    stack.pop();
+                },
+            }
+        }
+
+        Tree {
+            blocks,
+            indices,
+            roots,
+            predecessors,
+            idoms,
+            children,
+            frontiers,
+            preorder_numbers,
+            postorder_numbers,
+        }
+    }
+
+    // The node of a block reachable from the root
+    fn node(&self, block: BasicBlock<'ctx>) -> Option<usize> {
+        let node = *self.indices.get(&block)?;
+
+        self.idoms[node].map(|_| node)
+    }
+
+    fn dominates(&self, a: BasicBlock<'ctx>, b: BasicBlock<'ctx>) -> bool {
+        let b = match self.node(b) {
+            Some(b) => b,
+            None => return true,
+        };
+
+        match self.node(a) {
+            Some(a) => self.node_dominates(a, b),
+            None => false,
+        }
+    }
+
+    fn node_dominates(&self, a: usize, b: usize) -> bool {
+        self.preorder_numbers[a] <= self.preorder_numbers[b] && self.postorder_numbers[b] <= self.postorder_numbers[a]
+    }
+
+    fn idom(&self, block: BasicBlock<'ctx>) -> Option<BasicBlock<'ctx>> {
+        let idom = self.idoms[self.node(block)?]?;
+
+        // The virtual root isn't a block
+        self.blocks.get(idom).copied()
+    }
+
+    fn children(&self, block: BasicBlock<'ctx>) -> Vec<BasicBlock<'ctx>> {
+        match self.node(block) {
+            Some(node) => self.to_blocks(&self.children[node]),
+            None => Vec::new(),
+        }
+    }
+
+    fn roots(&self) -> Vec<BasicBlock<'ctx>> {
+        self.to_blocks(&self.roots)
+    }
+
+    fn frontier(&self, block: BasicBlock<'ctx>) -> Vec<BasicBlock<'ctx>> {
+        match self.node(block) {
+            Some(node) => self.to_blocks(&self.frontiers[node]),
+            None => Vec::new(),
+        }
+    }
+
+    fn nearest_common_dominator(&self, a: BasicBlock<'ctx>, b: BasicBlock<'ctx>) -> Option<BasicBlock<'ctx>> {
+        let mut a = self.node(a)?;
+        let b = self.node(b)?;
+
+        while !self.node_dominates(a, b) {
+            a = self.idoms[a]?;
+        }
+
+        self.blocks.get(a).copied()
+    }
+
+    fn to_blocks(&self, nodes: &[usize]) -> Vec<BasicBlock<'ctx>> {
+        nodes.iter().map(|&node| self.blocks[node]).collect()
+    }
+}
+
+fn index_blocks<'ctx>(blocks: &[BasicBlock<'ctx>]) -> HashMap<BasicBlock<'ctx>, usize> {
+    blocks
+        .iter()
+        .enumerate()
+        .map(|(index, block)| (*block, index))
+        .collect()
+}
+
+fn depth_first_postorder(root: usize, successors: &[Vec<usize>]) -> Vec<usize> {
+    let mut visited = vec![false; successors.len()];
+    let mut postorder = Vec::with_capacity(successors.len());
+    let mut stack = vec![(root, 0)];
+
+    visited[root] = true;
+
+    while let Some((node, next_successor)) = stack.last_mut() {
+        let node = *node;
+
+        match successors[node].get(*next_successor) {
+            Some(&successor) => {
+                *next_successor += 1;
+
+                if !visited[successor] {
+                    visited[successor] = true;
+                    stack.push((successor, 0));
+                }
+            },
+            None => {
+                postorder.push(node);
+                stack.pop();
+            },
+        }
+    }
+
+    postorder
+}
+
+fn intersect(idoms: &[Option<usize>], postorder_numbers: &[usize], mut a: usize, mut b: usize) -> usize {
+    while a != b {
+        while postorder_numbers[a] < postorder_numbers[b] {
+            a = idoms[a].expect("Processed nodes should have a dominator");
+        }
+
+        while postorder_numbers[b] < postorder_numbers[a] {
+            b = idoms[b].expect("Processed nodes should have a dominator");
+        }
+    }
+
+    a
+}
+
+// Whether `a` comes strictly before `b` in their basic block
+fn comes_before(a: InstructionValue, b: InstructionValue) -> bool {
+    let mut next = a.get_next_instruction();
+
+    while let Some(instruction) = next {
+        if instruction == b {
+            return true;
+        }
+
+        next = instruction.get_next_instruction();
+    }
+
+    false
+}
+
+// The result of an invoke or callbr is only available along the edge to its normal destination
+fn get_edge_destination<'ctx>(def: InstructionValue<'ctx>) -> Option<BasicBlock<'ctx>> {
+    if !defines_on_edge(def) {
+        return None;
+    }
+
+    unsafe { BasicBlock::new(LLVMGetSuccessor(def.as_value_ref(), 0)) }
+}
+
+#[llvm_versions(3.6..=8.0)]
+fn defines_on_edge(def: InstructionValue) -> bool {
+    def.get_opcode() == InstructionOpcode::Invoke
+}
+
+#[llvm_versions(9.0..=latest)]
+fn defines_on_edge(def: InstructionValue) -> bool {
+    matches!(def.get_opcode(), InstructionOpcode::Invoke | InstructionOpcode::CallBr)
+}
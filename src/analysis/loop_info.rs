@@ -0,0 +1,411 @@
+use llvm_sys::core::{LLVMGetMDKindIDInContext, LLVMGetTypeContext, LLVMTypeOf};
+#[llvm_versions(7.0..=latest)]
+use llvm_sys::core::{LLVMMDNodeInContext2, LLVMMetadataAsValue};
+#[llvm_versions(7.0..=latest)]
+use llvm_sys::debuginfo::{LLVMMetadataReplaceAllUsesWith, LLVMTemporaryMDNode};
+use llvm_sys::prelude::LLVMContextRef;
+#[llvm_versions(7.0..=latest)]
+use llvm_sys::prelude::LLVMMetadataRef;
+
+use std::collections::{HashMap, HashSet};
+#[llvm_versions(7.0..=latest)]
+use std::iter::once;
+
+use crate::analysis::DominatorTree;
+use crate::basic_block::BasicBlock;
+use crate::values::{AsValueRef, FunctionValue, InstructionValue, MetadataValue};
+
+/// The natural loops of a `FunctionValue` and how they nest.
+///
+/// A natural loop is formed by the back edges to a header block which dominates their sources,
+/// the latches. Its body is every block which can reach a latch without going through the header.
+/// Back edges sharing a header form a single loop. Cycles with several entries, which are
+/// irreducible, aren't natural loops and are ignored.
+///
+/// # Example
+///
+/// ```no_run
+/// use inkwell::analysis::LoopInfo;
+/// use inkwell::context::Context;
+///
+/// let context = Context::create();
+/// let builder = context.create_builder();
+/// let module = context.create_module("my_mod");
+/// let bool_type = context.bool_type();
+/// let fn_type = context.void_type().fn_type(&[bool_type.into()], false);
+/// let function = module.add_function("spin", fn_type, None);
+/// let entry = context.append_basic_block(function, "entry");
+/// let body = context.append_basic_block(function, "body");
+/// let exit = context.append_basic_block(function, "exit");
+/// let condition = function.get_first_param().unwrap().into_int_value();
+///
+/// builder.position_at_end(entry);
+/// builder.build_unconditional_branch(body);
+/// builder.position_at_end(body);
+/// builder.build_conditional_branch(condition, body, exit);
+/// builder.position_at_end(exit);
+/// builder.build_return(None);
+///
+/// let loop_info = LoopInfo::compute(function);
+/// let body_loop = loop_info.get_loop_for(body).unwrap();
+///
+/// assert_eq!(loop_info.get_loops().len(), 1);
+/// assert_eq!(body_loop.get_header(), body);
+/// assert_eq!(body_loop.get_latch(), Some(body));
+/// assert_eq!(body_loop.get_preheader(), Some(entry));
+/// assert_eq!(body_loop.get_exit_blocks(), vec![exit]);
+/// assert_eq!(loop_info.get_loop_depth(body), 1);
+/// assert_eq!(loop_info.get_loop_depth(exit), 0);
+/// ```
+#[derive(Debug)]
+pub struct LoopInfo<'ctx> {
+    // Ordered so that loops come before the loops they contain
+    loops: Vec<Loop<'ctx>>,
+    innermost_loops: HashMap<BasicBlock<'ctx>, usize>,
+}
+
+impl<'ctx> LoopInfo<'ctx> {
+    /// Finds the loops of a `FunctionValue` in its current control flow graph.
+    pub fn compute(function: FunctionValue<'ctx>) -> Self {
+        let dominator_tree = DominatorTree::compute(function);
+        // Headers dominate their loop bodies, so outer loops' headers come first in this order
+        let reverse_postorder: Vec<_> = function.get_reverse_postorder_blocks().collect();
+        let mut loops = Vec::new();
+
+        for &header in &reverse_postorder {
+            let predecessors = header.get_predecessors();
+            let latches: Vec<_> = reverse_postorder
+                .iter()
+                .copied()
+                .filter(|&block| predecessors.contains(&block) && dominator_tree.dominates(header, block))
+                .collect();
+
+            if latches.is_empty() {
+                continue;
+            }
+
+            let mut block_set = HashSet::new();
+            let mut worklist = latches.clone();
+
+            block_set.insert(header);
+
+            while let Some(block) = worklist.pop() {
+                if !block_set.insert(block) {
+                    continue;
+                }
+
+                worklist.extend(
+                    block
+                        .get_predecessors()
+                        .into_iter()
+                        .filter(|&predecessor| dominator_tree.is_reachable(predecessor)),
+                );
+            }
+
+            let blocks = reverse_postorder
+                .iter()
+                .copied()
+                .filter(|block| block_set.contains(block))
+                .collect();
+
+            loops.push(Loop {
+                header,
+                latches,
+                blocks,
+                block_set,
+                depth: 1,
+                parent: None,
+                sub_loops: Vec::new(),
+            });
+        }
+
+        // The parent is the smallest other loop containing the header
+        for index in 0..loops.len() {
+            let parent = (0..index)
+                .filter(|&other| loops[other].contains(loops[index].header))
+                .min_by_key(|&other| loops[other].blocks.len());
+
+            if let Some(parent) = parent {
+                loops[index].depth = loops[parent].depth + 1;
+                loops[index].parent = Some(parent);
+                loops[parent].sub_loops.push(index);
+            }
+        }
+
+        let mut innermost_loops = HashMap::new();
+
+        // Inner loops come later and override their parents
+        for (index, loop_) in loops.iter().enumerate() {
+            for &block in &loop_.blocks {
+                innermost_loops.insert(block, index);
+            }
+        }
+
+        LoopInfo { loops, innermost_loops }
+    }
+
+    /// Gets every loop of the function. Loops come before the loops nested in them.
+    pub fn get_loops(&self) -> &[Loop<'ctx>] {
+        &self.loops
+    }
+
+    /// Gets the loops which aren't nested in another loop.
+    pub fn get_top_level_loops(&self) -> Vec<&Loop<'ctx>> {
+        self.loops.iter().filter(|loop_| loop_.parent.is_none()).collect()
+    }
+
+    /// Gets the innermost loop containing a block, if any.
+    pub fn get_loop_for(&self, block: BasicBlock<'ctx>) -> Option<&Loop<'ctx>> {
+        self.innermost_loops.get(&block).map(|&index| &self.loops[index])
+    }
+
+    /// Determines whether a block is the header of a loop.
+    pub fn is_loop_header(&self, block: BasicBlock<'ctx>) -> bool {
+        self.get_loop_for(block).map(Loop::get_header) == Some(block)
+    }
+
+    /// Gets the number of loops containing a block, which is zero outside of loops.
+    pub fn get_loop_depth(&self, block: BasicBlock<'ctx>) -> u32 {
+        self.get_loop_for(block).map_or(0, |loop_| loop_.depth)
+    }
+
+    /// Gets the loop immediately containing a loop, if any.
+    pub fn get_parent_loop(&self, loop_: &Loop<'ctx>) -> Option<&Loop<'ctx>> {
+        loop_.parent.map(|index| &self.loops[index])
+    }
+
+    /// Gets the loops immediately nested in a loop.
+    pub fn get_sub_loops(&self, loop_: &Loop<'ctx>) -> Vec<&Loop<'ctx>> {
+        loop_.sub_loops.iter().map(|&index| &self.loops[index]).collect()
+    }
+
+    /// Gets the back edges of every loop, as pairs of latch and header.
+    pub fn get_back_edges(&self) -> Vec<(BasicBlock<'ctx>, BasicBlock<'ctx>)> {
+        self.loops.iter().flat_map(|loop_| loop_.get_back_edges()).collect()
+    }
+}
+
+/// A natural loop found by `LoopInfo`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Loop<'ctx> {
+    header: BasicBlock<'ctx>,
+    latches: Vec<BasicBlock<'ctx>>,
+    blocks: Vec<BasicBlock<'ctx>>,
+    block_set: HashSet<BasicBlock<'ctx>>,
+    depth: u32,
+    parent: Option<usize>,
+    sub_loops: Vec<usize>,
+}
+
+impl<'ctx> Loop<'ctx> {
+    /// Gets the header, which is the single entry of the loop and the target of its back edges.
+    pub fn get_header(&self) -> BasicBlock<'ctx> {
+        self.header
+    }
+
+    /// Gets the blocks of the loop, including those of nested loops. The header comes first, and
+    /// blocks come before their successors, back edges aside.
+    pub fn get_blocks(&self) -> &[BasicBlock<'ctx>] {
+        &self.blocks
+    }
+
+    /// Determines whether a block is part of the loop or of a loop nested in it.
+    pub fn contains(&self, block: BasicBlock<'ctx>) -> bool {
+        self.block_set.contains(&block)
+    }
+
+    /// Gets the number of loops containing this one, including itself.
+    pub fn get_depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// Gets the latches, which are the blocks branching back to the header, in the same order
+    /// as `get_blocks`.
+    pub fn get_latches(&self) -> &[BasicBlock<'ctx>] {
+        &self.latches
+    }
+
+    /// Gets the latch of the loop, if it has exactly one.
+    pub fn get_latch(&self) -> Option<BasicBlock<'ctx>> {
+        match *self.latches.as_slice() {
+            [latch] => Some(latch),
+            _ => None,
+        }
+    }
+
+    /// Gets the back edges of the loop, as pairs of latch and header.
+    pub fn get_back_edges(&self) -> Vec<(BasicBlock<'ctx>, BasicBlock<'ctx>)> {
+        self.latches.iter().map(|&latch| (latch, self.header)).collect()
+    }
+
+    /// Gets the blocks of the loop which may branch out of it.
+    pub fn get_exiting_blocks(&self) -> Vec<BasicBlock<'ctx>> {
+        self.blocks
+            .iter()
+            .copied()
+            .filter(|block| {
+                block
+                    .get_successors()
+                    .iter()
+                    .any(|&successor| !self.contains(successor))
+            })
+            .collect()
+    }
+
+    /// Gets the blocks outside of the loop which it may branch to, each once.
+    pub fn get_exit_blocks(&self) -> Vec<BasicBlock<'ctx>> {
+        let mut exit_blocks = Vec::new();
+
+        for block in &self.blocks {
+            for successor in block.get_successors() {
+                if !self.contains(successor) && !exit_blocks.contains(&successor) {
+                    exit_blocks.push(successor);
+                }
+            }
+        }
+
+        exit_blocks
+    }
+
+    /// Gets the preheader of the loop: its only predecessor outside of the loop, provided the
+    /// header is that block's only successor. Hoisted code belongs there.
+    pub fn get_preheader(&self) -> Option<BasicBlock<'ctx>> {
+        let mut outside_predecessors = unique_predecessors(self.header)
+            .into_iter()
+            .filter(|&predecessor| !self.contains(predecessor));
+        let preheader = outside_predecessors.next()?;
+
+        if outside_predecessors.next().is_some() || preheader.get_successors() != [self.header] {
+            return None;
+        }
+
+        Some(preheader)
+    }
+
+    /// Gets the `llvm.loop` metadata of the loop, which is found on its latches' terminators,
+    /// provided they all agree.
+    pub fn get_loop_metadata(&self) -> Option<MetadataValue<'ctx>> {
+        let mut loop_ids = self.get_latch_terminators().into_iter().map(|terminator| {
+            let kind_id = get_loop_kind_id(terminator);
+
+            terminator.get_metadata(kind_id)
+        });
+        let loop_id = loop_ids.next()??;
+
+        if loop_ids.all(|other| other == Some(loop_id)) {
+            Some(loop_id)
+        } else {
+            None
+        }
+    }
+
+    /// Sets the `llvm.loop` metadata of the loop on all of its latches' terminators, as
+    /// optimization passes expect. The node should refer to itself as its first operand, which
+    /// `set_loop_properties` takes care of.
+    pub fn set_loop_metadata(&self, loop_id: MetadataValue<'ctx>) -> Result<(), &'static str> {
+        let terminators = self.get_latch_terminators();
+
+        if terminators.len() != self.latches.len() {
+            return Err("Every latch of the loop must have a terminator.");
+        }
+
+        for terminator in terminators {
+            terminator.set_metadata(loop_id, get_loop_kind_id(terminator))?;
+        }
+
+        Ok(())
+    }
+
+    /// Creates a loop ID, a distinct node whose first operand is itself followed by `properties`,
+    /// and sets it as the `llvm.loop` metadata of the loop. Returns the loop ID.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::analysis::LoopInfo;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let builder = context.create_builder();
+    /// let module = context.create_module("my_mod");
+    /// let bool_type = context.bool_type();
+    /// let fn_type = context.void_type().fn_type(&[bool_type.into()], false);
+    /// let function = module.add_function("spin", fn_type, None);
+    /// let entry = context.append_basic_block(function, "entry");
+    /// let body = context.append_basic_block(function, "body");
+    /// let exit = context.append_basic_block(function, "exit");
+    /// let condition = function.get_first_param().unwrap().into_int_value();
+    ///
+    /// builder.position_at_end(entry);
+    /// builder.build_unconditional_branch(body);
+    /// builder.position_at_end(body);
+    /// builder.build_conditional_branch(condition, body, exit);
+    /// builder.position_at_end(exit);
+    /// builder.build_return(None);
+    ///
+    /// let loop_info = LoopInfo::compute(function);
+    /// let body_loop = &loop_info.get_loops()[0];
+    /// let unroll_disable = context.metadata_node(&[context.metadata_string("llvm.loop.unroll.disable").into()]);
+    /// let loop_id = body_loop.set_loop_properties(&[unroll_disable]).unwrap();
+    ///
+    /// assert_eq!(body_loop.get_loop_metadata(), Some(loop_id));
+    /// assert!(module.verify().is_ok());
+    /// ```
+    #[llvm_versions(7.0..=latest)]
+    pub fn set_loop_properties(&self, properties: &[MetadataValue<'ctx>]) -> Result<MetadataValue<'ctx>, &'static str> {
+        let context = get_context(
+            self.header
+                .get_terminator()
+                .ok_or("The loop header must have a terminator.")?,
+        );
+
+        let loop_id = unsafe {
+            let placeholder = LLVMTemporaryMDNode(context, std::ptr::null_mut(), 0);
+            let mut operands: Vec<LLVMMetadataRef> = once(placeholder)
+                .chain(properties.iter().map(|property| property.as_metadata_ref()))
+                .collect();
+            let loop_id = LLVMMDNodeInContext2(context, operands.as_mut_ptr(), operands.len());
+
+            // Making the node refer to itself also makes it distinct, and frees the placeholder
+            LLVMMetadataReplaceAllUsesWith(placeholder, loop_id);
+
+            MetadataValue::new(LLVMMetadataAsValue(context, loop_id))
+        };
+
+        self.set_loop_metadata(loop_id)?;
+
+        Ok(loop_id)
+    }
+
+    fn get_latch_terminators(&self) -> Vec<InstructionValue<'ctx>> {
+        self.latches.iter().filter_map(|latch| latch.get_terminator()).collect()
+    }
+}
+
+fn unique_predecessors(block: BasicBlock) -> Vec<BasicBlock> {
+    let mut predecessors = Vec::new();
+
+    for predecessor in block.get_predecessors() {
+        if !predecessors.contains(&predecessor) {
+            predecessors.push(predecessor);
+        }
+    }
+
+    predecessors
+}
+
+fn get_context(instruction: InstructionValue) -> LLVMContextRef {
+    unsafe { LLVMGetTypeContext(LLVMTypeOf(instruction.as_value_ref())) }
+}
+
+fn get_loop_kind_id(instruction: InstructionValue) -> u32 {
+    let key = "llvm.loop";
+
+    unsafe {
+        LLVMGetMDKindIDInContext(
+            get_context(instruction),
+            key.as_ptr() as *const ::libc::c_char,
+            key.len() as u32,
+        )
+    }
+}
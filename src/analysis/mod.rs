@@ -0,0 +1,11 @@
+//! Analyses of the control flow graph of a `FunctionValue`.
+//!
+//! LLVM's C API doesn't expose its own analyses, so these are computed in Rust from the
+//! `BasicBlock`s and `InstructionValue`s of a function. They are snapshots: modifying the
+//! control flow graph afterwards requires computing them again.
+
+mod dominator_tree;
+mod loop_info;
+
+pub use crate::analysis::dominator_tree::{DominatorTree, PostDominatorTree};
+pub use crate::analysis::loop_info::{Loop, LoopInfo};
@@ -1,18 +1,26 @@
 //! A `BasicBlock` is a container of instructions.
 
 #[llvm_versions(3.9..=latest)]
-use llvm_sys::core::LLVMGetBasicBlockName;
+use llvm_sys::core::{
+    LLVMAppendBasicBlockInContext, LLVMBuildBr, LLVMCreateBuilderInContext, LLVMDisposeBuilder, LLVMGetBasicBlockName,
+    LLVMInsertBasicBlockInContext, LLVMPositionBuilderAtEnd,
+};
 use llvm_sys::core::{
     LLVMBasicBlockAsValue, LLVMBlockAddress, LLVMDeleteBasicBlock, LLVMGetBasicBlockParent,
     LLVMGetBasicBlockTerminator, LLVMGetFirstInstruction, LLVMGetFirstUse, LLVMGetLastInstruction,
-    LLVMGetNextBasicBlock, LLVMGetPreviousBasicBlock, LLVMGetTypeContext, LLVMIsABasicBlock, LLVMIsConstant,
-    LLVMMoveBasicBlockAfter, LLVMMoveBasicBlockBefore, LLVMPrintTypeToString, LLVMPrintValueToString,
-    LLVMRemoveBasicBlockFromParent, LLVMReplaceAllUsesWith, LLVMTypeOf,
+    LLVMGetNextBasicBlock, LLVMGetNumSuccessors, LLVMGetPreviousBasicBlock, LLVMGetSuccessor, LLVMGetTypeContext,
+    LLVMIsABasicBlock, LLVMIsConstant, LLVMMoveBasicBlockAfter, LLVMMoveBasicBlockBefore, LLVMPrintTypeToString,
+    LLVMPrintValueToString, LLVMRemoveBasicBlockFromParent, LLVMReplaceAllUsesWith, LLVMTypeOf,
 };
 use llvm_sys::prelude::{LLVMBasicBlockRef, LLVMValueRef};
 
 use crate::context::ContextRef;
-use crate::values::{AsValueRef, BasicValueUse, FunctionValue, InstructionValue, PointerValue};
+use crate::support::to_c_str;
+#[llvm_versions(3.9..=latest)]
+use crate::values::InstructionOpcode;
+use crate::values::{
+    AsValueRef, BasicValueUse, BasicValueUseIter, FunctionValue, InstructionValue, PointerValue, UserIter,
+};
 #[cfg(feature = "internal-getters")]
 use crate::LLVMReference;
 
@@ -273,6 +281,51 @@ impl<'ctx> BasicBlock<'ctx> {
         unsafe { Some(InstructionValue::new(value)) }
     }
 
+    /// Iterates over the instructions in this `BasicBlock`, from either end.
+    ///
+    /// The instruction following the one just yielded is looked up before yielding it, so
+    /// the yielded instruction may be erased or moved elsewhere without disturbing the iteration.
+    /// Removing any other instruction of this block while iterating is not supported.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::values::InstructionOpcode;
+    ///
+    /// let context = Context::create();
+    /// let builder = context.create_builder();
+    /// let module = context.create_module("my_module");
+    /// let i32_type = context.i32_type();
+    /// let fn_type = i32_type.fn_type(&[i32_type.into()], false);
+    /// let function = module.add_function("identity", fn_type, None);
+    /// let basic_block = context.append_basic_block(function, "entry");
+    ///
+    /// builder.position_at_end(basic_block);
+    ///
+    /// let x = function.get_first_param().unwrap().into_int_value();
+    /// let zero = i32_type.const_zero();
+    ///
+    /// builder.build_int_add(x, zero, "unused");
+    /// builder.build_return(Some(&x));
+    ///
+    /// // Erasing while iterating is fine
+    /// for instruction in basic_block.get_instructions() {
+    ///     if instruction.get_first_use().is_none() && instruction.get_opcode() == InstructionOpcode::Add {
+    ///         instruction.erase_from_basic_block();
+    ///     }
+    /// }
+    ///
+    /// let opcodes: Vec<_> = basic_block.get_instructions().rev().map(|i| i.get_opcode()).collect();
+    ///
+    /// assert_eq!(opcodes, vec![InstructionOpcode::Return]);
+    /// ```
+    pub fn get_instructions(self) -> InstructionIter<'ctx> {
+        InstructionIter {
+            front: self.get_first_instruction(),
+            back: self.get_last_instruction(),
+        }
+    }
+
     /// Obtains the terminating `InstructionValue` in this `BasicBlock`, if any. A `BasicBlock` must have a terminating instruction to be valid.
     ///
     /// # Example
@@ -309,6 +362,180 @@ impl<'ctx> BasicBlock<'ctx> {
         unsafe { Some(InstructionValue::new(value)) }
     }
 
+    /// Gets the `BasicBlock`s this block's terminator may branch to, in operand order. A block targeted
+    /// by several edges, such as a conditional branch with identical destinations, appears once per
+    /// edge. Blocks without a terminator have no successors.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let builder = context.create_builder();
+    /// let module = context.create_module("my_mod");
+    /// let bool_type = context.bool_type();
+    /// let fn_type = context.void_type().fn_type(&[bool_type.into()], false);
+    /// let function = module.add_function("my_fn", fn_type, None);
+    /// let entry = context.append_basic_block(function, "entry");
+    /// let then_block = context.append_basic_block(function, "then");
+    /// let else_block = context.append_basic_block(function, "else");
+    /// let condition = function.get_first_param().unwrap().into_int_value();
+    ///
+    /// builder.position_at_end(entry);
+    /// builder.build_conditional_branch(condition, then_block, else_block);
+    ///
+    /// assert_eq!(entry.get_successors(), vec![then_block, else_block]);
+    /// assert!(then_block.get_successors().is_empty());
+    /// ```
+    pub fn get_successors(self) -> Vec<BasicBlock<'ctx>> {
+        let terminator = match self.get_terminator() {
+            Some(terminator) => terminator.as_value_ref(),
+            None => return Vec::new(),
+        };
+        let count = unsafe { LLVMGetNumSuccessors(terminator) };
+
+        (0..count)
+            .map(|index| unsafe {
+                BasicBlock::new(LLVMGetSuccessor(terminator, index)).expect("Successor should be a basic block")
+            })
+            .collect()
+    }
+
+    /// Gets the `BasicBlock`s whose terminators may branch to this block, in no particular order.
+    /// As with `get_successors`, a predecessor appears once per edge.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let builder = context.create_builder();
+    /// let module = context.create_module("my_mod");
+    /// let fn_type = context.void_type().fn_type(&[], false);
+    /// let function = module.add_function("my_fn", fn_type, None);
+    /// let entry = context.append_basic_block(function, "entry");
+    /// let exit = context.append_basic_block(function, "exit");
+    ///
+    /// builder.position_at_end(entry);
+    /// builder.build_unconditional_branch(exit);
+    ///
+    /// assert_eq!(exit.get_predecessors(), vec![entry]);
+    /// assert!(entry.get_predecessors().is_empty());
+    /// ```
+    pub fn get_predecessors(self) -> Vec<BasicBlock<'ctx>> {
+        // Only terminators use blocks as instruction operands; other users are blockaddress constants
+        self.users()
+            .filter_map(|user| user.as_instruction_value()?.get_parent())
+            .collect()
+    }
+
+    /// Splits this `BasicBlock` in two at `instruction`, which must belong to it and not be a phi,
+    /// updating the phis in the successors in place so that handles to them stay valid.
+    /// `instruction` and everything after it move to a new block named `name`, placed right after
+    /// this one, which then ends with an unconditional branch to the new block. Returns the new block.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::values::InstructionOpcode;
+    ///
+    /// let context = Context::create();
+    /// let builder = context.create_builder();
+    /// let module = context.create_module("my_mod");
+    /// let i32_type = context.i32_type();
+    /// let fn_type = i32_type.fn_type(&[i32_type.into()], false);
+    /// let function = module.add_function("my_fn", fn_type, None);
+    /// let entry = context.append_basic_block(function, "entry");
+    /// let x = function.get_first_param().unwrap().into_int_value();
+    ///
+    /// builder.position_at_end(entry);
+    ///
+    /// let sum = builder.build_int_add(x, x, "sum");
+    /// let product = builder.build_int_mul(sum, x, "product");
+    ///
+    /// builder.build_return(Some(&product));
+    ///
+    /// let tail = entry.split_at(product.as_instruction().unwrap(), "tail").unwrap();
+    ///
+    /// assert_eq!(entry.get_successors(), vec![tail]);
+    /// assert_eq!(entry.get_terminator().unwrap().get_opcode(), InstructionOpcode::Br);
+    /// assert_eq!(tail.get_first_instruction(), product.as_instruction());
+    /// assert!(module.verify().is_ok());
+    /// ```
+    #[llvm_versions(3.9..=latest)]
+    pub fn split_at(self, instruction: InstructionValue<'ctx>, name: &str) -> Result<BasicBlock<'ctx>, &'static str> {
+        if instruction.get_parent() != Some(self) {
+            return Err("The instruction must belong to this basic block.");
+        }
+
+        if instruction.get_opcode() == InstructionOpcode::Phi {
+            return Err("Phis must stay at the start of their basic block.");
+        }
+
+        let function = self.get_parent().ok_or("The basic block must belong to a function.")?;
+        let context = unsafe { LLVMGetTypeContext(LLVMTypeOf(LLVMBasicBlockAsValue(self.basic_block))) };
+        let c_string = to_c_str(name);
+        let new_block = unsafe {
+            let new_block = match self.get_next_basic_block() {
+                Some(next_block) => LLVMInsertBasicBlockInContext(context, next_block.basic_block, c_string.as_ptr()),
+                None => LLVMAppendBasicBlockInContext(context, function.as_value_ref(), c_string.as_ptr()),
+            };
+
+            BasicBlock::new(new_block).expect("Inserting a basic block should never fail")
+        };
+
+        // The C API can't change the incoming blocks of a phi, but replacing the uses of a block
+        // also moves the phis in its successors over to the replacement. Bouncing the uses off the
+        // still empty new block leaves only those phis pointing at it, with nothing else about
+        // them changed. Branches back to this block become branches to the new one before its
+        // successors are visited, so its own phis wait in the new block meanwhile.
+        let phis: Vec<_> = self
+            .get_instructions()
+            .take_while(|instruction| instruction.get_opcode() == InstructionOpcode::Phi)
+            .collect();
+
+        for phi in &phis {
+            phi.move_to_end_of(new_block);
+        }
+
+        unsafe {
+            let old_value = LLVMBasicBlockAsValue(self.basic_block);
+            let new_value = LLVMBasicBlockAsValue(new_block.basic_block);
+
+            LLVMReplaceAllUsesWith(old_value, new_value);
+            LLVMReplaceAllUsesWith(new_value, old_value);
+        }
+
+        let first_instruction = self
+            .get_first_instruction()
+            .expect("Split block should keep its instructions");
+
+        for phi in phis {
+            phi.move_before(first_instruction)
+                .expect("Instruction should belong to a basic block");
+        }
+
+        let mut next = Some(instruction);
+
+        while let Some(current) = next {
+            next = current.get_next_instruction();
+            current.move_to_end_of(new_block);
+        }
+
+        unsafe {
+            let builder = LLVMCreateBuilderInContext(context);
+
+            LLVMPositionBuilderAtEnd(builder, self.basic_block);
+            LLVMBuildBr(builder, new_block.basic_block);
+            LLVMDisposeBuilder(builder);
+        }
+
+        Ok(new_block)
+    }
+
     /// Removes this `BasicBlock` from its parent `FunctionValue`.
     /// It returns `Err(())` when it has no parent to remove from.
     ///
@@ -423,6 +650,47 @@ impl<'ctx> BasicBlock<'ctx> {
         unsafe { CStr::from_ptr(ptr) }
     }
 
+    /// Set name of the `BasicBlock`.
+    #[llvm_versions(3.6..=6.0)]
+    pub fn set_name(&self, name: &str) {
+        use llvm_sys::core::LLVMSetValueName;
+
+        let c_string = to_c_str(name);
+
+        unsafe { LLVMSetValueName(LLVMBasicBlockAsValue(self.basic_block), c_string.as_ptr()) };
+    }
+
+    /// Set name of the `BasicBlock`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let void_type = context.void_type();
+    /// let fn_type = void_type.fn_type(&[], false);
+    /// let fn_val = module.add_function("my_fn", fn_type, None);
+    /// let bb = context.append_basic_block(fn_val, "entry");
+    ///
+    /// bb.set_name("start");
+    ///
+    /// assert_eq!(bb.get_name().to_str(), Ok("start"));
+    /// ```
+    #[llvm_versions(7.0..=latest)]
+    pub fn set_name(&self, name: &str) {
+        use llvm_sys::core::LLVMSetValueName2;
+
+        unsafe {
+            LLVMSetValueName2(
+                LLVMBasicBlockAsValue(self.basic_block),
+                name.as_ptr() as *const ::libc::c_char,
+                name.len(),
+            )
+        };
+    }
+
     /// Replaces all uses of this basic block with another.
     ///
     /// # Example
@@ -492,6 +760,16 @@ impl<'ctx> BasicBlock<'ctx> {
         unsafe { Some(BasicValueUse::new(use_)) }
     }
 
+    /// Iterates over every use of this `BasicBlock`, such as the branches targeting it.
+    pub fn uses(self) -> BasicValueUseIter<'ctx> {
+        BasicValueUseIter::new(self.get_first_use())
+    }
+
+    /// Iterates over the users of this `BasicBlock`, which are mostly the terminators of its predecessors.
+    pub fn users(self) -> UserIter<'ctx> {
+        UserIter::new(self.get_first_use())
+    }
+
     /// Gets the address of this `BasicBlock` if possible. Returns `None` if `self` is the entry block to a function.
     ///
     /// # Safety
@@ -529,6 +807,45 @@ impl<'ctx> BasicBlock<'ctx> {
     }
 }
 
+/// An iterator over the instructions of a `BasicBlock`, created by `BasicBlock::get_instructions`.
+#[derive(Debug)]
+pub struct InstructionIter<'ctx> {
+    front: Option<InstructionValue<'ctx>>,
+    back: Option<InstructionValue<'ctx>>,
+}
+
+impl<'ctx> Iterator for InstructionIter<'ctx> {
+    type Item = InstructionValue<'ctx>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let instruction = self.front?;
+
+        if self.front == self.back {
+            self.front = None;
+            self.back = None;
+        } else {
+            self.front = instruction.get_next_instruction();
+        }
+
+        Some(instruction)
+    }
+}
+
+impl<'ctx> DoubleEndedIterator for InstructionIter<'ctx> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let instruction = self.back?;
+
+        if self.front == self.back {
+            self.front = None;
+            self.back = None;
+        } else {
+            self.back = instruction.get_previous_instruction();
+        }
+
+        Some(instruction)
+    }
+}
+
 impl fmt::Debug for BasicBlock<'_> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let llvm_value = unsafe { CStr::from_ptr(LLVMPrintValueToString(self.basic_block as LLVMValueRef)) };
@@ -24,25 +24,33 @@ use llvm_sys::core::{
 };
 #[llvm_versions(8.0..=latest)]
 use llvm_sys::core::{LLVMBuildIntCast2, LLVMBuildMemCpy, LLVMBuildMemMove, LLVMBuildMemSet};
+#[llvm_versions(12.0..=latest)]
+use llvm_sys::core::{LLVMGetGlobalParent, LLVMGetIntrinsicDeclaration, LLVMLookupIntrinsicID};
 use llvm_sys::prelude::{LLVMBuilderRef, LLVMValueRef};
 
 use crate::basic_block::BasicBlock;
 #[llvm_versions(7.0..=latest)]
 use crate::debug_info::DILocation;
 use crate::support::to_c_str;
-use crate::types::{AsTypeRef, BasicType, FloatMathType, IntMathType, PointerMathType, PointerType};
+#[llvm_versions(12.0..=latest)]
+use crate::types::IntType;
+use crate::types::{
+    ArrayType, AsTypeRef, BasicType, BasicTypeEnum, FloatMathType, IntMathType, PointeeType, PointerMathType,
+    PointerType, StructType,
+};
 use crate::values::CallableValue;
 #[llvm_versions(3.9..=latest)]
 use crate::values::StructValue;
 use crate::values::{
     AggregateValue, AggregateValueEnum, AsValueRef, BasicMetadataValueEnum, BasicValue, BasicValueEnum, CallSiteValue,
     FloatMathValue, FunctionValue, GlobalValue, InstructionOpcode, InstructionValue, IntMathValue, IntValue, PhiValue,
-    PointerMathValue, PointerValue, VectorValue,
+    PointerMathValue, PointerValue, TypedPointerValue, VectorValue,
 };
 #[cfg(feature = "internal-getters")]
 use crate::LLVMReference;
 use crate::{AtomicOrdering, AtomicRMWBinOp, FloatPredicate, IntPredicate};
 
+use std::convert::TryFrom;
 use std::marker::PhantomData;
 
 #[derive(Debug)]
@@ -773,6 +781,289 @@ impl<'ctx> Builder<'ctx> {
         unsafe { PointerValue::new(value) }
     }
 
+    /// Builds a stack allocation of `ty`, returning a pointer which remembers that it points to a `ty`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("typed_alloca");
+    /// let builder = context.create_builder();
+    /// let i64_type = context.i64_type();
+    /// let fn_value = module.add_function("typed_alloca", i64_type.fn_type(&[], false), None);
+    /// let entry = context.append_basic_block(fn_value, "entry");
+    ///
+    /// builder.position_at_end(entry);
+    ///
+    /// let ptr = builder.build_typed_alloca(i64_type, "ptr");
+    ///
+    /// builder.build_typed_store(ptr, i64_type.const_int(42, false)).unwrap();
+    ///
+    /// let value = builder.build_typed_load(ptr, "value").unwrap();
+    ///
+    /// builder.build_return(Some(&value));
+    /// ```
+    pub fn build_typed_alloca<T: BasicType<'ctx> + Copy>(&self, ty: T, name: &str) -> TypedPointerValue<'ctx, T> {
+        let ptr = self.build_alloca(ty, name);
+
+        unsafe { TypedPointerValue::new_unchecked(ptr, ty) }
+    }
+
+    /// Builds a load of the pointee type of a `TypedPointerValue`, producing the matching value type.
+    /// Returns an error if the loaded value is not of the pointee type, which can't happen unless
+    /// `ptr` was created with `TypedPointerValue::new_unchecked`.
+    pub fn build_typed_load<T: PointeeType<'ctx>>(
+        &self,
+        ptr: TypedPointerValue<'ctx, T>,
+        name: &str,
+    ) -> Result<T::ValueType, &'static str> {
+        let ptr_value = self.build_typed_pointer_operand(ptr);
+        let value = self.build_load_of_type(ptr.get_pointee_type(), ptr_value, name);
+
+        T::ValueType::try_from(value).map_err(|_| "Loaded value does not match the pointee type.")
+    }
+
+    #[llvm_versions(3.6..8.0)]
+    fn build_load_of_type<T: BasicType<'ctx>>(
+        &self,
+        _ty: T,
+        ptr: PointerValue<'ctx>,
+        name: &str,
+    ) -> BasicValueEnum<'ctx> {
+        self.build_load(ptr, name)
+    }
+
+    #[llvm_versions(8.0..=latest)]
+    fn build_load_of_type<T: BasicType<'ctx>>(
+        &self,
+        ty: T,
+        ptr: PointerValue<'ctx>,
+        name: &str,
+    ) -> BasicValueEnum<'ctx> {
+        use llvm_sys::core::LLVMBuildLoad2;
+
+        let c_string = to_c_str(name);
+        let value = unsafe { LLVMBuildLoad2(self.builder, ty.as_type_ref(), ptr.as_value_ref(), c_string.as_ptr()) };
+
+        unsafe { BasicValueEnum::new(value) }
+    }
+
+    #[llvm_versions(3.6..8.0)]
+    unsafe fn build_gep_of_type<T: BasicType<'ctx>>(
+        &self,
+        _ty: T,
+        ptr: PointerValue<'ctx>,
+        ordered_indexes: &[IntValue<'ctx>],
+        name: &str,
+    ) -> PointerValue<'ctx> {
+        self.build_gep(ptr, ordered_indexes, name)
+    }
+
+    #[llvm_versions(8.0..=latest)]
+    unsafe fn build_gep_of_type<T: BasicType<'ctx>>(
+        &self,
+        ty: T,
+        ptr: PointerValue<'ctx>,
+        ordered_indexes: &[IntValue<'ctx>],
+        name: &str,
+    ) -> PointerValue<'ctx> {
+        use llvm_sys::core::LLVMBuildGEP2;
+
+        let c_string = to_c_str(name);
+        let mut index_values: Vec<LLVMValueRef> = ordered_indexes.iter().map(|val| val.as_value_ref()).collect();
+        let value = LLVMBuildGEP2(
+            self.builder,
+            ty.as_type_ref(),
+            ptr.as_value_ref(),
+            index_values.as_mut_ptr(),
+            index_values.len() as u32,
+            c_string.as_ptr(),
+        );
+
+        PointerValue::new(value)
+    }
+
+    #[llvm_versions(3.6..8.0)]
+    unsafe fn build_struct_gep_of_type(
+        &self,
+        _ty: StructType<'ctx>,
+        ptr: PointerValue<'ctx>,
+        index: u32,
+        name: &str,
+    ) -> PointerValue<'ctx> {
+        let c_string = to_c_str(name);
+
+        PointerValue::new(LLVMBuildStructGEP(
+            self.builder,
+            ptr.as_value_ref(),
+            index,
+            c_string.as_ptr(),
+        ))
+    }
+
+    #[llvm_versions(8.0..=latest)]
+    unsafe fn build_struct_gep_of_type(
+        &self,
+        ty: StructType<'ctx>,
+        ptr: PointerValue<'ctx>,
+        index: u32,
+        name: &str,
+    ) -> PointerValue<'ctx> {
+        use llvm_sys::core::LLVMBuildStructGEP2;
+
+        let c_string = to_c_str(name);
+        let value = LLVMBuildStructGEP2(
+            self.builder,
+            ty.as_type_ref(),
+            ptr.as_value_ref(),
+            index,
+            c_string.as_ptr(),
+        );
+
+        PointerValue::new(value)
+    }
+
+    // Under typed pointers LLVM derives the pointee type of loads, stores and GEPs from the pointer
+    // itself, so a pointer to anything other than the tracked type is cast first.
+    fn build_typed_pointer_operand<T: BasicType<'ctx> + Copy>(
+        &self,
+        ptr: TypedPointerValue<'ctx, T>,
+    ) -> PointerValue<'ctx> {
+        let ptr_value = ptr.as_pointer_value();
+        let ptr_type = ptr
+            .get_pointee_type()
+            .ptr_type(ptr_value.get_type().get_address_space());
+
+        if ptr_value.get_type() == ptr_type {
+            return ptr_value;
+        }
+
+        self.build_pointer_cast(ptr_value, ptr_type, "")
+    }
+
+    /// Builds a store through a `TypedPointerValue`. Returns an error if `value` is not of the
+    /// pointee type, such as storing an `i64` through a pointer to an `i32`.
+    pub fn build_typed_store<T: PointeeType<'ctx>>(
+        &self,
+        ptr: TypedPointerValue<'ctx, T>,
+        value: T::ValueType,
+    ) -> Result<InstructionValue<'ctx>, &'static str> {
+        if value.as_basic_value_enum().get_type() != ptr.get_pointee_type().as_basic_type_enum() {
+            return Err("Value does not match the pointee type.");
+        }
+
+        Ok(self.build_store(self.build_typed_pointer_operand(ptr), value))
+    }
+
+    /// Builds a GEP which offsets a `TypedPointerValue` by `offset` elements of its pointee type.
+    /// The result points to the same type.
+    ///
+    /// # Safety
+    ///
+    /// Like `build_gep`, this is very likely to segfault if `offset` is used incorrectly. Tracking
+    /// the pointee type does not make an unchecked offset any safer.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::types::IntType;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("typed_gep");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let fn_value = module.add_function("typed_gep", i32_type.fn_type(&[], false), None);
+    /// let entry = context.append_basic_block(fn_value, "entry");
+    ///
+    /// builder.position_at_end(entry);
+    ///
+    /// let array_ptr = builder.build_typed_alloca(i32_type.array_type(4), "array_ptr");
+    /// let first_ptr = unsafe { builder.build_typed_array_gep(array_ptr, i32_type.const_zero(), "first_ptr") };
+    /// let first_ptr = first_ptr.downcast::<IntType>().unwrap();
+    /// let last_ptr = unsafe { builder.build_typed_gep(first_ptr, i32_type.const_int(3, false), "last_ptr") };
+    /// let last = builder.build_typed_load(last_ptr, "last").unwrap();
+    ///
+    /// builder.build_return(Some(&last));
+    /// ```
+    pub unsafe fn build_typed_gep<T: BasicType<'ctx> + Copy>(
+        &self,
+        ptr: TypedPointerValue<'ctx, T>,
+        offset: IntValue<'ctx>,
+        name: &str,
+    ) -> TypedPointerValue<'ctx, T> {
+        let ptr_value = self.build_typed_pointer_operand(ptr);
+        let ptr_value = self.build_gep_of_type(ptr.get_pointee_type(), ptr_value, &[offset], name);
+
+        TypedPointerValue::new_unchecked(ptr_value, ptr.get_pointee_type())
+    }
+
+    /// Builds a GEP to the field at `index` of the struct pointed to by a `TypedPointerValue`.
+    /// Returns an error if the struct has no such field.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::types::FloatType;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("typed_struct_gep");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let f32_type = context.f32_type();
+    /// let struct_type = context.struct_type(&[i32_type.into(), f32_type.into()], false);
+    /// let fn_value = module.add_function("typed_struct_gep", f32_type.fn_type(&[], false), None);
+    /// let entry = context.append_basic_block(fn_value, "entry");
+    ///
+    /// builder.position_at_end(entry);
+    ///
+    /// let struct_ptr = builder.build_typed_alloca(struct_type, "struct_ptr");
+    /// let field_ptr = builder.build_typed_struct_gep(struct_ptr, 1, "field_ptr").unwrap();
+    /// let field_ptr = field_ptr.downcast::<FloatType>().unwrap();
+    /// let field = builder.build_typed_load(field_ptr, "field").unwrap();
+    ///
+    /// builder.build_return(Some(&field));
+    ///
+    /// assert!(builder.build_typed_struct_gep(struct_ptr, 2, "field_ptr").is_err());
+    /// ```
+    pub fn build_typed_struct_gep(
+        &self,
+        ptr: TypedPointerValue<'ctx, StructType<'ctx>>,
+        index: u32,
+        name: &str,
+    ) -> Result<TypedPointerValue<'ctx, BasicTypeEnum<'ctx>>, &'static str> {
+        let field_type = ptr
+            .get_pointee_type()
+            .get_field_type_at_index(index)
+            .ok_or("Struct has no field at the given index.")?;
+        let ptr_value = self.build_typed_pointer_operand(ptr);
+        let ptr_value = unsafe { self.build_struct_gep_of_type(ptr.get_pointee_type(), ptr_value, index, name) };
+
+        unsafe { Ok(TypedPointerValue::new_unchecked(ptr_value, field_type)) }
+    }
+
+    /// Builds a GEP to the element at `index` of the array pointed to by a `TypedPointerValue`.
+    ///
+    /// # Safety
+    ///
+    /// Like `build_gep`, this is very likely to segfault if `index` is used incorrectly, as it is
+    /// not checked against the length of the array.
+    pub unsafe fn build_typed_array_gep(
+        &self,
+        ptr: TypedPointerValue<'ctx, ArrayType<'ctx>>,
+        index: IntValue<'ctx>,
+        name: &str,
+    ) -> TypedPointerValue<'ctx, BasicTypeEnum<'ctx>> {
+        let zero = index.get_type().const_zero();
+        let ptr_value = self.build_typed_pointer_operand(ptr);
+        let ptr_value = self.build_gep_of_type(ptr.get_pointee_type(), ptr_value, &[zero, index], name);
+
+        TypedPointerValue::new_unchecked(ptr_value, ptr.get_pointee_type().get_element_type())
+    }
+
     // TODOC: Stack allocation
     pub fn build_array_alloca<T: BasicType<'ctx>>(
         &self,
@@ -898,6 +1189,57 @@ impl<'ctx> Builder<'ctx> {
         unsafe { Ok(PointerValue::new(value)) }
     }
 
+    /// Build a call to the [vscale](https://llvm.org/docs/LangRef.html#llvm-vscale-intrinsic) intrinsic,
+    /// which returns the runtime multiple of the number of elements in scalable vectors.
+    ///
+    /// The `Builder` must be positioned within a function, so that the intrinsic can be
+    /// declared in its `Module`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("sve");
+    /// let builder = context.create_builder();
+    /// let i64_type = context.i64_type();
+    /// let function = module.add_function("lanes", i64_type.fn_type(&[], false), None);
+    ///
+    /// builder.position_at_end(context.append_basic_block(function, "entry"));
+    ///
+    /// // The number of f32 lanes in a <vscale x 4 x float>
+    /// let vscale = builder.build_vscale(i64_type, "vscale").unwrap();
+    /// let lanes = builder.build_int_mul(vscale, i64_type.const_int(4, false), "lanes");
+    ///
+    /// builder.build_return(Some(&lanes));
+    /// ```
+    #[llvm_versions(12.0..=latest)]
+    pub fn build_vscale(&self, int_type: IntType<'ctx>, name: &str) -> Result<IntValue<'ctx>, &'static str> {
+        let function = self
+            .get_insert_block()
+            .and_then(|block| block.get_parent())
+            .ok_or("The builder must be positioned within a function to build vscale.")?;
+        let intrinsic_name = "llvm.vscale";
+
+        let declaration = unsafe {
+            let id = LLVMLookupIntrinsicID(intrinsic_name.as_ptr() as *const ::libc::c_char, intrinsic_name.len());
+            let mut param_types = [int_type.as_type_ref()];
+
+            FunctionValue::new(LLVMGetIntrinsicDeclaration(
+                LLVMGetGlobalParent(function.as_value_ref()),
+                id,
+                param_types.as_mut_ptr(),
+                param_types.len(),
+            ))
+            .expect("llvm.vscale should be declarable for any integer type")
+        };
+
+        let call = self.build_call(declaration, &[], name);
+
+        Ok(call.try_as_basic_value().left().unwrap().into_int_value())
+    }
+
     // TODOC: Heap allocation
     pub fn build_malloc<T: BasicType<'ctx>>(&self, ty: T, name: &str) -> Result<PointerValue<'ctx>, &'static str> {
         // LLVMBulidMalloc segfaults if ty is unsized
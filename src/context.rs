@@ -3,25 +3,28 @@
 #[llvm_versions(7.0..=latest)]
 use crate::InlineAsmDialect;
 use libc::c_void;
+#[llvm_versions(11.0..=latest)]
+use llvm_sys::core::LLVMBFloatTypeInContext;
 #[llvm_versions(3.6..7.0)]
 use llvm_sys::core::LLVMConstInlineAsm;
-#[llvm_versions(12.0..=latest)]
-use llvm_sys::core::LLVMCreateTypeAttribute;
 #[llvm_versions(7.0..=latest)]
 use llvm_sys::core::LLVMGetInlineAsm;
-#[llvm_versions(6.0..=latest)]
-use llvm_sys::core::LLVMMetadataTypeInContext;
 use llvm_sys::core::{
     LLVMAppendBasicBlockInContext, LLVMConstStringInContext, LLVMConstStructInContext, LLVMContextCreate,
     LLVMContextDispose, LLVMContextSetDiagnosticHandler, LLVMCreateBuilderInContext, LLVMDoubleTypeInContext,
     LLVMFP128TypeInContext, LLVMFloatTypeInContext, LLVMGetGlobalContext, LLVMGetMDKindIDInContext,
     LLVMHalfTypeInContext, LLVMInsertBasicBlockInContext, LLVMInt16TypeInContext, LLVMInt1TypeInContext,
-    LLVMInt32TypeInContext, LLVMInt64TypeInContext, LLVMInt8TypeInContext, LLVMIntTypeInContext, LLVMMDNodeInContext,
-    LLVMMDStringInContext, LLVMModuleCreateWithNameInContext, LLVMPPCFP128TypeInContext, LLVMStructCreateNamed,
-    LLVMStructTypeInContext, LLVMVoidTypeInContext, LLVMX86FP80TypeInContext,
+    LLVMInt32TypeInContext, LLVMInt64TypeInContext, LLVMInt8TypeInContext, LLVMIntTypeInContext,
+    LLVMLabelTypeInContext, LLVMMDNodeInContext, LLVMMDStringInContext, LLVMModuleCreateWithNameInContext,
+    LLVMPPCFP128TypeInContext, LLVMStructCreateNamed, LLVMStructTypeInContext, LLVMVoidTypeInContext,
+    LLVMX86FP80TypeInContext,
 };
 #[llvm_versions(3.9..=latest)]
 use llvm_sys::core::{LLVMCreateEnumAttribute, LLVMCreateStringAttribute};
+#[llvm_versions(12.0..=latest)]
+use llvm_sys::core::{LLVMCreateTypeAttribute, LLVMX86AMXTypeInContext};
+#[llvm_versions(6.0..=latest)]
+use llvm_sys::core::{LLVMMetadataTypeInContext, LLVMTokenTypeInContext};
 use llvm_sys::ir_reader::LLVMParseIRInContext;
 use llvm_sys::prelude::{LLVMContextRef, LLVMDiagnosticInfoRef, LLVMTypeRef, LLVMValueRef};
 use llvm_sys::target::{LLVMIntPtrTypeForASInContext, LLVMIntPtrTypeInContext};
@@ -36,9 +39,13 @@ use crate::memory_buffer::MemoryBuffer;
 use crate::module::Module;
 use crate::support::{to_c_str, LLVMString};
 use crate::targets::TargetData;
+#[llvm_versions(12.0..=latest)]
+use crate::types::X86AMXType;
+use crate::types::{
+    AnyTypeEnum, AsTypeRef, BasicTypeEnum, FloatType, FunctionType, IntType, LabelType, StructType, VoidType,
+};
 #[llvm_versions(6.0..=latest)]
-use crate::types::MetadataType;
-use crate::types::{AnyTypeEnum, AsTypeRef, BasicTypeEnum, FloatType, FunctionType, IntType, StructType, VoidType};
+use crate::types::{MetadataType, TokenType};
 use crate::values::{
     AsValueRef, BasicMetadataValueEnum, BasicValueEnum, FunctionValue, MetadataValue, PointerValue, StructValue,
     VectorValue,
@@ -501,6 +508,56 @@ impl Context {
         unsafe { MetadataType::new(LLVMMetadataTypeInContext(self.context)) }
     }
 
+    /// Gets the `TokenType`. It will be assigned the current context.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let token_type = context.token_type();
+    ///
+    /// assert_eq!(*token_type.get_context(), context);
+    /// ```
+    #[llvm_versions(6.0..=latest)]
+    pub fn token_type(&self) -> TokenType<'_> {
+        unsafe { TokenType::new(LLVMTokenTypeInContext(self.context)) }
+    }
+
+    /// Gets the `LabelType`. It will be assigned the current context.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let label_type = context.label_type();
+    ///
+    /// assert_eq!(*label_type.get_context(), context);
+    /// ```
+    pub fn label_type(&self) -> LabelType<'_> {
+        unsafe { LabelType::new(LLVMLabelTypeInContext(self.context)) }
+    }
+
+    /// Gets the `X86AMXType`. It will be assigned the current context.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let x86_amx_type = context.x86_amx_type();
+    ///
+    /// assert_eq!(*x86_amx_type.get_context(), context);
+    /// ```
+    #[llvm_versions(12.0..=latest)]
+    pub fn x86_amx_type(&self) -> X86AMXType<'_> {
+        unsafe { X86AMXType::new(LLVMX86AMXTypeInContext(self.context)) }
+    }
+
     /// Gets the `IntType` representing a bit width of a pointer. It will be assigned the referenced context.
     ///
     /// # Example
@@ -546,6 +603,25 @@ impl Context {
         unsafe { FloatType::new(LLVMHalfTypeInContext(self.context)) }
     }
 
+    /// Gets the `FloatType` representing a 16 bit wide "brain" float, which has the same exponent
+    /// range as a 32 bit float. It will be assigned the current context.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    ///
+    /// let bf16_type = context.bf16_type();
+    ///
+    /// assert_eq!(*bf16_type.get_context(), context);
+    /// ```
+    #[llvm_versions(11.0..=latest)]
+    pub fn bf16_type(&self) -> FloatType<'_> {
+        unsafe { FloatType::new(LLVMBFloatTypeInContext(self.context)) }
+    }
+
     /// Gets the `FloatType` representing a 32 bit width. It will be assigned the current context.
     ///
     /// # Example
@@ -17,6 +17,11 @@ extern crate inkwell_internals;
 #[macro_use]
 pub mod support;
 #[deny(missing_docs)]
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+pub mod abi;
+#[deny(missing_docs)]
+pub mod analysis;
+#[deny(missing_docs)]
 pub mod attributes;
 #[deny(missing_docs)]
 pub mod basic_block;
@@ -4,15 +4,24 @@ use llvm_sys::analysis::{LLVMVerifierFailureAction, LLVMVerifyModule};
 #[allow(deprecated)]
 use llvm_sys::bit_reader::LLVMParseBitcodeInContext;
 use llvm_sys::bit_writer::{LLVMWriteBitcodeToFile, LLVMWriteBitcodeToMemoryBuffer};
+#[llvm_versions(7.0..=latest)]
+use llvm_sys::core::LLVMDeleteBasicBlock;
 use llvm_sys::core::{
     LLVMAddFunction, LLVMAddGlobal, LLVMAddGlobalInAddressSpace, LLVMAddNamedMetadataOperand, LLVMCloneModule,
-    LLVMDisposeModule, LLVMDumpModule, LLVMGetFirstFunction, LLVMGetFirstGlobal, LLVMGetLastFunction,
-    LLVMGetLastGlobal, LLVMGetModuleContext, LLVMGetNamedFunction, LLVMGetNamedGlobal, LLVMGetNamedMetadataNumOperands,
-    LLVMGetNamedMetadataOperands, LLVMGetTarget, LLVMGetTypeByName, LLVMPrintModuleToFile, LLVMPrintModuleToString,
-    LLVMSetDataLayout, LLVMSetTarget,
+    LLVMConstStringInContext, LLVMDeleteFunction, LLVMDeleteGlobal, LLVMDisposeModule, LLVMDumpModule,
+    LLVMFunctionType, LLVMGetBasicBlockParent, LLVMGetConstOpcode, LLVMGetFirstFunction, LLVMGetFirstGlobal,
+    LLVMGetFirstUse, LLVMGetGlobalParent, LLVMGetInstructionParent, LLVMGetLastFunction, LLVMGetLastGlobal,
+    LLVMGetModuleContext, LLVMGetNamedFunction, LLVMGetNamedGlobal, LLVMGetNamedMetadataNumOperands,
+    LLVMGetNamedMetadataOperands, LLVMGetNextUse, LLVMGetOperand, LLVMGetTarget, LLVMGetTypeByName, LLVMGetUndef,
+    LLVMGetUser, LLVMInt32TypeInContext, LLVMInt8TypeInContext, LLVMIsAConstant, LLVMIsAConstantExpr, LLVMIsAFunction,
+    LLVMIsAGlobalValue, LLVMIsAGlobalVariable, LLVMIsAInstruction, LLVMPointerType, LLVMPrintModuleToFile,
+    LLVMPrintModuleToString, LLVMReplaceAllUsesWith, LLVMSetDataLayout, LLVMSetInitializer, LLVMSetTarget,
+    LLVMStructTypeInContext, LLVMTypeOf, LLVMVoidTypeInContext,
 };
 #[llvm_versions(7.0..=latest)]
-use llvm_sys::core::{LLVMAddModuleFlag, LLVMGetModuleFlag};
+use llvm_sys::core::{
+    LLVMAddModuleFlag, LLVMGetFirstNamedMetadata, LLVMGetModuleFlag, LLVMGetNamedMetadataName, LLVMGetNextNamedMetadata,
+};
 #[llvm_versions(3.9..=latest)]
 use llvm_sys::core::{LLVMGetModuleIdentifier, LLVMSetModuleIdentifier};
 #[llvm_versions(8.0..=latest)]
@@ -20,22 +29,29 @@ use llvm_sys::error::LLVMGetErrorMessage;
 use llvm_sys::execution_engine::{
     LLVMCreateExecutionEngineForModule, LLVMCreateInterpreterForModule, LLVMCreateJITCompilerForModule,
 };
-use llvm_sys::prelude::{LLVMModuleRef, LLVMValueRef};
+#[llvm_versions(7.0..=latest)]
+use llvm_sys::prelude::{LLVMComdatRef, LLVMNamedMDNodeRef};
+use llvm_sys::prelude::{LLVMModuleRef, LLVMTypeRef, LLVMValueRef};
 #[llvm_versions(13.0..=latest)]
 use llvm_sys::transforms::pass_builder::LLVMRunPasses;
-use llvm_sys::LLVMLinkage;
 #[llvm_versions(7.0..=latest)]
 use llvm_sys::LLVMModuleFlagBehavior;
+use llvm_sys::{LLVMLinkage, LLVMOpcode};
 
 use std::cell::{Cell, Ref, RefCell};
-use std::ffi::CStr;
+use std::convert::TryFrom;
+use std::ffi::{CStr, CString};
 use std::fs::File;
 use std::marker::PhantomData;
-use std::mem::{forget, MaybeUninit};
+use std::mem::{align_of, forget, size_of_val, MaybeUninit};
 use std::path::Path;
 use std::ptr;
 use std::rc::Rc;
+use std::slice;
+use std::vec;
 
+#[llvm_versions(7.0..=latest)]
+use crate::basic_block::BasicBlock;
 #[llvm_versions(7.0..=latest)]
 use crate::comdat::Comdat;
 use crate::context::{Context, ContextRef};
@@ -46,12 +62,19 @@ use crate::execution_engine::ExecutionEngine;
 use crate::memory_buffer::MemoryBuffer;
 #[llvm_versions(13.0..=latest)]
 use crate::passes::PassBuilderOptions;
+#[llvm_versions(7.0..=latest)]
+use crate::passes::PassManager;
 use crate::support::{to_c_str, LLVMString};
-use crate::targets::{InitializationConfig, Target, TargetMachine, TargetTriple};
-use crate::types::{AsTypeRef, BasicType, FunctionType, StructType};
+use crate::targets::{ByteOrdering, InitializationConfig, Target, TargetData, TargetMachine, TargetTriple};
+use crate::types::{AsTypeRef, BasicType, FunctionSignature, IntType, PointerType, StructType};
 #[llvm_versions(7.0..=latest)]
 use crate::values::BasicValue;
-use crate::values::{AsValueRef, FunctionValue, GlobalValue, MetadataValue};
+use crate::values::{
+    AggregateValue, AnyValueEnum, ArrayValue, AsValueRef, ConstantDataElement, FunctionValue, GlobalValue,
+    MetadataValue,
+};
+#[llvm_versions(7.0..=latest)]
+use crate::GlobalVisibility;
 #[cfg(feature = "internal-getters")]
 use crate::LLVMReference;
 use crate::{AddressSpace, OptimizationLevel};
@@ -204,17 +227,50 @@ impl<'ctx> Module<'ctx> {
     /// assert_eq!(fn_val.get_name().to_str(), Ok("my_function"));
     /// assert_eq!(fn_val.get_linkage(), Linkage::External);
     /// ```
-    pub fn add_function(&self, name: &str, ty: FunctionType<'ctx>, linkage: Option<Linkage>) -> FunctionValue<'ctx> {
+    ///
+    /// A `FunctionSignature` may be given instead of a `FunctionType`, in which case the parameters
+    /// are also named and given their attributes, along with the return value and calling convention.
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::types::{FunctionSignature, ParamAttribute};
+    /// use inkwell::AddressSpace;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    /// let i64_type = context.i64_type();
+    /// let signature = FunctionSignature::new(context.void_type())
+    ///     .param_with_attributes("out", i64_type.ptr_type(AddressSpace::Generic), &[ParamAttribute::NoAlias])
+    ///     .param("value", i64_type);
+    ///
+    /// let fn_val = module.add_function("store", signature, None);
+    /// let out = fn_val.get_first_param().unwrap().into_pointer_value();
+    ///
+    /// assert_eq!(out.get_name().to_str(), Ok("out"));
+    /// ```
+    pub fn add_function<S: Into<FunctionSignature<'ctx>>>(
+        &self,
+        name: &str,
+        ty: S,
+        linkage: Option<Linkage>,
+    ) -> FunctionValue<'ctx> {
+        let signature = ty.into();
         let c_string = to_c_str(name);
         let fn_value = unsafe {
-            FunctionValue::new(LLVMAddFunction(self.module.get(), c_string.as_ptr(), ty.as_type_ref()))
-                .expect("add_function should always succeed in adding a new function")
+            FunctionValue::new(LLVMAddFunction(
+                self.module.get(),
+                c_string.as_ptr(),
+                signature.get_fn_type().as_type_ref(),
+            ))
+            .expect("add_function should always succeed in adding a new function")
         };
 
         if let Some(linkage) = linkage {
             fn_value.set_linkage(linkage)
         }
 
+        signature.apply(fn_value);
+
         fn_value
     }
 
@@ -614,6 +670,70 @@ impl<'ctx> Module<'ctx> {
         unsafe { GlobalValue::new(value) }
     }
 
+    /// Creates a constant `GlobalValue` holding the bytes of `data` in the host's byte order, as an
+    /// `i8` array aligned for `T`. Unlike `ArrayValue::from_slice`, which builds one constant per
+    /// element for anything wider than a byte, the table is handed to LLVM in one piece and
+    /// nothing is interned per element, so this is the way to embed large tables.
+    ///
+    /// Cast the global's pointer to an array of `T` to index into it.
+    ///
+    /// Returns an error, adding nothing, if the module's data layout has a different byte order
+    /// than the host or if `data` is 4 GiB or larger. A module without a data layout is little
+    /// endian, so set it before embedding tables for a big endian target.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::AddressSpace;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("mod");
+    /// let table = module.add_global_data_table(&[1u32, 2, 3], None, "table").unwrap();
+    /// let table_ptr = table
+    ///     .as_pointer_value()
+    ///     .const_cast(context.i32_type().array_type(3).ptr_type(AddressSpace::Generic));
+    /// let bytes = table.get_initializer().unwrap().into_array_value();
+    ///
+    /// assert_eq!(bytes.get_type(), context.i8_type().array_type(12));
+    /// assert_eq!(table.get_alignment(), 4);
+    /// ```
+    pub fn add_global_data_table<T: ConstantDataElement>(
+        &self,
+        data: &[T],
+        address_space: Option<AddressSpace>,
+        name: &str,
+    ) -> Result<GlobalValue<'ctx>, &'static str> {
+        let host_byte_ordering = if cfg!(target_endian = "big") {
+            ByteOrdering::BigEndian
+        } else {
+            ByteOrdering::LittleEndian
+        };
+        let target_data = TargetData::create(&self.get_data_layout().as_str().to_string_lossy());
+
+        if target_data.get_byte_ordering() != host_byte_ordering {
+            return Err("The module's byte order differs from the host's.");
+        }
+
+        let len = u32::try_from(size_of_val(data)).map_err(|_| "The table must be smaller than 4 GiB.")?;
+
+        let initializer = unsafe {
+            ArrayValue::new(LLVMConstStringInContext(
+                LLVMGetModuleContext(self.module.get()),
+                data.as_ptr() as *const ::libc::c_char,
+                len,
+                1,
+            ))
+        };
+        let global = self.add_global(initializer.get_type(), address_space, name);
+
+        global.set_initializer(&initializer);
+        global.set_constant(true);
+        global.set_alignment(align_of::<T>() as u32);
+
+        Ok(global)
+    }
+
     /// Writes a `Module` to a `Path`.
     ///
     /// # Example
@@ -989,14 +1109,17 @@ impl<'ctx> Module<'ctx> {
     /// assert_eq!(md_1[1].into_float_value(), f32_val);
     /// ```
     pub fn get_global_metadata(&self, key: &str) -> Vec<MetadataValue<'ctx>> {
-        let c_string = to_c_str(key);
-        let count = self.get_global_metadata_size(key) as usize;
+        self.get_named_metadata_operands(&to_c_str(key))
+    }
+
+    fn get_named_metadata_operands(&self, name: &CStr) -> Vec<MetadataValue<'ctx>> {
+        let count = unsafe { LLVMGetNamedMetadataNumOperands(self.module.get(), name.as_ptr()) } as usize;
 
         let mut vec: Vec<LLVMValueRef> = Vec::with_capacity(count);
         let ptr = vec.as_mut_ptr();
 
         unsafe {
-            LLVMGetNamedMetadataOperands(self.module.get(), c_string.as_ptr(), ptr);
+            LLVMGetNamedMetadataOperands(self.module.get(), name.as_ptr(), ptr);
 
             vec.set_len(count);
         };
@@ -1089,75 +1212,84 @@ impl<'ctx> Module<'ctx> {
         unsafe { Some(GlobalValue::new(value)) }
     }
 
-    /// Creates a new `Module` from a `MemoryBuffer`.
+    /// An iterator over the global variables in this `Module`.
     ///
     /// # Example
     ///
     /// ```no_run
     /// use inkwell::context::Context;
-    /// use inkwell::module::Module;
-    /// use inkwell::memory_buffer::MemoryBuffer;
-    /// use std::path::Path;
     ///
-    /// let path = Path::new("foo/bar.bc");
     /// let context = Context::create();
-    /// let buffer = MemoryBuffer::create_from_file(&path).unwrap();
-    /// let module = Module::parse_bitcode_from_buffer(&buffer, &context);
-    ///
-    /// assert_eq!(*module.unwrap().get_context(), context);
+    /// let module = context.create_module("my_mod");
+    /// let i8_type = context.i8_type();
+    /// let global1 = module.add_global(i8_type, None, "global1");
+    /// let global2 = module.add_global(i8_type, None, "global2");
     ///
+    /// assert_eq!(module.get_globals().collect::<Vec<_>>(), vec![global1, global2]);
     /// ```
-    pub fn parse_bitcode_from_buffer(buffer: &MemoryBuffer, context: &'ctx Context) -> Result<Self, LLVMString> {
-        let mut module = MaybeUninit::uninit();
-        let mut err_string = MaybeUninit::uninit();
-
-        // LLVM has a newer version of this function w/o the error result since 3.8 but this deprecated function
-        // hasen't yet been removed even in LLVM 8. Seems fine to use instead of switching to their
-        // error diagnostics handler for now.
-        #[allow(deprecated)]
-        let success = unsafe {
-            LLVMParseBitcodeInContext(
-                context.context,
-                buffer.memory_buffer,
-                module.as_mut_ptr(),
-                err_string.as_mut_ptr(),
-            )
-        };
+    pub fn get_globals(&self) -> GlobalIterator<'ctx> {
+        GlobalIterator::from_module(self)
+    }
 
-        if success != 0 {
-            unsafe {
-                return Err(LLVMString::new(err_string.assume_init()));
-            }
+    /// An iterator over every global variable, then every function, and then every alias and ifunc
+    /// in this `Module`, as `GlobalValue`s. Aliases and ifuncs can't be listed before LLVM 7.0 and
+    /// LLVM 9.0 respectively.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let fn_type = context.void_type().fn_type(&[], false);
+    /// let function = module.add_function("my_fn", fn_type, None);
+    /// let global = module.add_global(context.i8_type(), None, "my_global");
+    ///
+    /// assert_eq!(
+    ///     module.get_global_values().collect::<Vec<_>>(),
+    ///     vec![global, function.as_global_value()],
+    /// );
+    /// ```
+    pub fn get_global_values(&self) -> GlobalValueIterator<'ctx> {
+        GlobalValueIterator {
+            globals: self.get_globals(),
+            functions: self.get_functions(),
+            indirect_symbols: unsafe { get_indirect_symbols(self.module.get()) }.into_iter(),
         }
-
-        unsafe { Ok(Module::new(module.assume_init())) }
     }
 
-    /// A convenience function for creating a `Module` from a file for a given context.
+    /// An iterator over the named metadata in this `Module`, yielding the name of each
+    /// along with its operands.
     ///
     /// # Example
     ///
     /// ```no_run
     /// use inkwell::context::Context;
-    /// use inkwell::module::Module;
-    /// use std::path::Path;
     ///
-    /// let path = Path::new("foo/bar.bc");
     /// let context = Context::create();
-    /// let module = Module::parse_bitcode_from_path(&path, &context);
+    /// let module = context.create_module("my_mod");
+    /// let md_string = context.metadata_string("my string");
+    /// let md_node = context.metadata_node(&[md_string.into()]);
     ///
-    /// assert_eq!(*module.unwrap().get_context(), context);
+    /// module.add_global_metadata("my_md", &md_node).unwrap();
     ///
+    /// let named_metadata: Vec<_> = module.get_named_metadata().collect();
+    ///
+    /// assert_eq!(named_metadata.len(), 1);
+    /// assert_eq!(named_metadata[0].0.to_str(), Ok("my_md"));
+    /// assert_eq!(named_metadata[0].1, vec![md_node]);
     /// ```
-    // LLVMGetBitcodeModuleInContext was a pain to use, so I seem to be able to achieve the same effect
-    // by reusing create_from_file instead. This is basically just a convenience function.
-    pub fn parse_bitcode_from_path<P: AsRef<Path>>(path: P, context: &'ctx Context) -> Result<Self, LLVMString> {
-        let buffer = MemoryBuffer::create_from_file(path.as_ref())?;
-
-        Self::parse_bitcode_from_buffer(&buffer, &context)
+    #[llvm_versions(7.0..=latest)]
+    pub fn get_named_metadata(&self) -> NamedMetadataIterator<'_, 'ctx> {
+        NamedMetadataIterator {
+            module: self,
+            next: unsafe { LLVMGetFirstNamedMetadata(self.module.get()) },
+        }
     }
 
-    /// Gets the name of this `Module`.
+    /// Removes a function from this `Module` and deletes it, as long as nothing outside of its own
+    /// body uses it anymore. Otherwise, the remaining users are returned in the error.
     ///
     /// # Example
     ///
@@ -1165,65 +1297,151 @@ impl<'ctx> Module<'ctx> {
     /// use inkwell::context::Context;
     ///
     /// let context = Context::create();
-    /// let module = context.create_module("my_module");
+    /// let module = context.create_module("my_mod");
+    /// let fn_type = context.void_type().fn_type(&[], false);
+    /// let function = module.add_function("my_fn", fn_type, None);
     ///
-    /// assert_eq!(module.get_name().to_str(), Ok("my_mdoule"));
+    /// assert!(module.remove_function(function).is_ok());
+    /// assert!(module.get_function("my_fn").is_none());
     /// ```
-    #[llvm_versions(3.9..=latest)]
-    pub fn get_name(&self) -> &CStr {
-        let mut length = 0;
-        let cstr_ptr = unsafe { LLVMGetModuleIdentifier(self.module.get(), &mut length) };
-
-        unsafe { CStr::from_ptr(cstr_ptr) }
+    pub fn remove_function(&self, function: FunctionValue<'ctx>) -> Result<(), RemoveGlobalError<'ctx>> {
+        self.remove_global(function.as_global_value())
     }
 
-    /// Assigns the name of this `Module`.
+    /// Removes a global variable or function from this `Module` and deletes it, as long as nothing
+    /// other than itself uses it anymore. Otherwise, the remaining users are returned in the error.
+    ///
+    /// Users are looked up through constant expressions, such as a bitcast of `global`, so the
+    /// reported users are always instructions or other globals.
     ///
     /// # Example
     ///
     /// ```no_run
     /// use inkwell::context::Context;
+    /// use inkwell::module::RemoveGlobalError;
+    /// use inkwell::values::AnyValueEnum;
     ///
     /// let context = Context::create();
-    /// let module = context.create_module("my_module");
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let global = module.add_global(i32_type, None, "my_global");
+    /// let function = module.add_function("my_fn", i32_type.fn_type(&[], false), None);
     ///
-    /// module.set_name("my_module2");
+    /// builder.position_at_end(context.append_basic_block(function, "entry"));
     ///
-    /// assert_eq!(module.get_name().to_str(), Ok("my_module2"));
+    /// let load = builder.build_load(global.as_pointer_value(), "load");
+    ///
+    /// builder.build_return(Some(&load));
+    ///
+    /// match module.remove_global(global) {
+    ///     Err(RemoveGlobalError::HasUsers(users)) => assert_eq!(users, vec![AnyValueEnum::from(load)]),
+    ///     _ => unreachable!(),
+    /// }
     /// ```
-    #[llvm_versions(3.9..=latest)]
-    pub fn set_name(&self, name: &str) {
-        unsafe { LLVMSetModuleIdentifier(self.module.get(), name.as_ptr() as *const ::libc::c_char, name.len()) }
+    pub fn remove_global(&self, global: GlobalValue<'ctx>) -> Result<(), RemoveGlobalError<'ctx>> {
+        let is_function = self.check_removable(global)?;
+
+        let mut users = Vec::new();
+
+        unsafe { collect_users(global.as_value_ref(), global.as_value_ref(), is_function, &mut users) };
+
+        if !users.is_empty() {
+            return Err(RemoveGlobalError::HasUsers(users));
+        }
+
+        unsafe {
+            // This also takes care of dead constant expressions and uses by the global itself,
+            // which would otherwise be left dangling.
+            LLVMReplaceAllUsesWith(global.as_value_ref(), LLVMGetUndef(LLVMTypeOf(global.as_value_ref())));
+
+            if is_function {
+                LLVMDeleteFunction(global.as_value_ref());
+            } else {
+                LLVMDeleteGlobal(global.as_value_ref());
+            }
+        }
+
+        Ok(())
     }
 
-    /// Gets the source file name. It defaults to the module identifier but is separate from it.
+    /// Replaces all uses of `global` with `replacement`, then removes `global` from this `Module`
+    /// and deletes it, just like `remove_global`. If their types differ, `replacement` is
+    /// cast to the type of `global` first.
+    ///
+    /// Both must belong to this `Module` and be distinct. Nothing is modified if an error is returned.
     ///
     /// # Example
     ///
     /// ```no_run
     /// use inkwell::context::Context;
+    /// use inkwell::values::BasicValue;
     ///
     /// let context = Context::create();
     /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let fn_type = context.void_type().fn_type(&[], false);
+    /// let old = module.add_function("old", fn_type, None);
+    /// let new = module.add_function("new", fn_type, None);
+    /// let caller = module.add_function("caller", fn_type, None);
     ///
-    /// assert_eq!(module.get_source_file_name().to_str(), Ok("my_mod"));
+    /// builder.position_at_end(context.append_basic_block(caller, "entry"));
+    /// builder.build_call(old, &[], "call");
+    /// builder.build_return(None);
     ///
-    /// module.set_source_file_name("my_mod.rs");
+    /// module.replace_and_remove(old.as_global_value(), new.as_global_value()).unwrap();
     ///
-    /// assert_eq!(module.get_name().to_str(), Ok("my_mod"));
-    /// assert_eq!(module.get_source_file_name().to_str(), Ok("my_mod.rs"));
+    /// assert!(module.get_function("old").is_none());
+    /// assert!(new.as_global_value().get_first_use().is_some());
     /// ```
-    #[llvm_versions(7.0..=latest)]
-    pub fn get_source_file_name(&self) -> &CStr {
-        use llvm_sys::core::LLVMGetSourceFileName;
+    pub fn replace_and_remove(
+        &self,
+        global: GlobalValue<'ctx>,
+        replacement: GlobalValue<'ctx>,
+    ) -> Result<(), RemoveGlobalError<'ctx>> {
+        self.check_removable(global)?;
 
-        let mut len = 0;
-        let ptr = unsafe { LLVMGetSourceFileName(self.module.get(), &mut len) };
+        if replacement == global {
+            return Err(RemoveGlobalError::SelfReplacement);
+        }
 
-        unsafe { CStr::from_ptr(ptr) }
+        if unsafe { LLVMGetGlobalParent(replacement.as_value_ref()) } != self.module.get() {
+            return Err(RemoveGlobalError::NotInModule);
+        }
+
+        let global_pointer = global.as_pointer_value();
+        let replacement_pointer = replacement.as_pointer_value();
+
+        if replacement_pointer.get_type() == global_pointer.get_type() {
+            global_pointer.replace_all_uses_with(replacement_pointer);
+        } else {
+            global_pointer.replace_all_uses_with(replacement_pointer.const_cast(global_pointer.get_type()));
+        }
+
+        self.remove_global(global)
     }
 
-    /// Sets the source file name. It defaults to the module identifier but is separate from it.
+    // Returns whether `global` is a function, as opposed to a global variable.
+    fn check_removable(&self, global: GlobalValue<'ctx>) -> Result<bool, RemoveGlobalError<'ctx>> {
+        if unsafe { LLVMGetGlobalParent(global.as_value_ref()) } != self.module.get() {
+            return Err(RemoveGlobalError::NotInModule);
+        }
+
+        let is_function = unsafe { !LLVMIsAFunction(global.as_value_ref()).is_null() };
+        let is_variable = unsafe { !LLVMIsAGlobalVariable(global.as_value_ref()).is_null() };
+
+        if !is_function && !is_variable {
+            return Err(RemoveGlobalError::Unsupported);
+        }
+
+        Ok(is_function)
+    }
+
+    /// Registers `function` to be run when the program starts, by appending it to the
+    /// `llvm.global_ctors` array of this `Module`, which is created if it does not exist yet.
+    ///
+    /// Constructors with a lower `priority` run first. `associated_data`, if any, tells the linker that
+    /// the constructor should be discarded along with that global.
     ///
     /// # Example
     ///
@@ -1231,29 +1449,32 @@ impl<'ctx> Module<'ctx> {
     /// use inkwell::context::Context;
     ///
     /// let context = Context::create();
-    /// let module = context.create_module("my_mod");
+    /// let module = context.create_module("my_module");
+    /// let fn_type = context.void_type().fn_type(&[], false);
+    /// let init = module.add_function("init", fn_type, None);
     ///
-    /// assert_eq!(module.get_source_file_name().to_str(), Ok("my_mod"));
+    /// module.add_global_constructor(init, 65535, None);
     ///
-    /// module.set_source_file_name("my_mod.rs");
+    /// let constructors = module.get_global_constructors();
     ///
-    /// assert_eq!(module.get_name().to_str(), Ok("my_mod"));
-    /// assert_eq!(module.get_source_file_name().to_str(), Ok("my_mod.rs"));
+    /// assert_eq!(constructors.len(), 1);
+    /// assert_eq!(constructors[0].function, init);
+    /// assert_eq!(constructors[0].priority, 65535);
     /// ```
-    #[llvm_versions(7.0..=latest)]
-    pub fn set_source_file_name(&self, file_name: &str) {
-        use llvm_sys::core::LLVMSetSourceFileName;
-
-        unsafe {
-            LLVMSetSourceFileName(
-                self.module.get(),
-                file_name.as_ptr() as *const ::libc::c_char,
-                file_name.len(),
-            )
-        }
+    pub fn add_global_constructor(
+        &self,
+        function: FunctionValue<'ctx>,
+        priority: u32,
+        associated_data: Option<GlobalValue<'ctx>>,
+    ) {
+        self.append_global_ctor_dtor("llvm.global_ctors", function, priority, associated_data)
     }
 
-    /// Links one module into another. This will merge two `Module`s into one.
+    /// Registers `function` to be run when the program exits, by appending it to the
+    /// `llvm.global_dtors` array of this `Module`, which is created if it does not exist yet.
+    ///
+    /// Destructors with a lower `priority` run first. `associated_data`, if any, tells the linker that
+    /// the destructor should be discarded along with that global.
     ///
     /// # Example
     ///
@@ -1261,68 +1482,853 @@ impl<'ctx> Module<'ctx> {
     /// use inkwell::context::Context;
     ///
     /// let context = Context::create();
-    /// let module = context.create_module("mod");
-    /// let module2 = context.create_module("mod2");
+    /// let module = context.create_module("my_module");
+    /// let fn_type = context.void_type().fn_type(&[], false);
+    /// let fini = module.add_function("fini", fn_type, None);
     ///
-    /// assert!(module.link_in_module(module2).is_ok());
+    /// module.add_global_destructor(fini, 65535, None);
+    ///
+    /// let destructors = module.get_global_destructors();
+    ///
+    /// assert_eq!(destructors.len(), 1);
+    /// assert_eq!(destructors[0].function, fini);
     /// ```
-    pub fn link_in_module(&self, other: Self) -> Result<(), LLVMString> {
-        if other.owned_by_ee.borrow().is_some() {
-            let string = "Cannot link a module which is already owned by an ExecutionEngine.\0";
-            return Err(LLVMString::create_from_str(string));
-        }
+    pub fn add_global_destructor(
+        &self,
+        function: FunctionValue<'ctx>,
+        priority: u32,
+        associated_data: Option<GlobalValue<'ctx>>,
+    ) {
+        self.append_global_ctor_dtor("llvm.global_dtors", function, priority, associated_data)
+    }
 
-        #[cfg(any(feature = "llvm3-6", feature = "llvm3-7"))]
-        {
-            use llvm_sys::linker::{LLVMLinkModules, LLVMLinkerMode};
+    /// Gets the entries of the `llvm.global_ctors` array of this `Module`, in order.
+    pub fn get_global_constructors(&self) -> Vec<GlobalCtorDtor<'ctx>> {
+        self.get_global_ctor_dtors("llvm.global_ctors")
+    }
 
-            let mut err_string = ptr::null_mut();
-            // As of 3.7, LLVMLinkerDestroySource is the only option
-            let mode = LLVMLinkerMode::LLVMLinkerDestroySource;
-            let code = unsafe { LLVMLinkModules(self.module.get(), other.module.get(), mode, &mut err_string) };
+    /// Gets the entries of the `llvm.global_dtors` array of this `Module`, in order.
+    pub fn get_global_destructors(&self) -> Vec<GlobalCtorDtor<'ctx>> {
+        self.get_global_ctor_dtors("llvm.global_dtors")
+    }
 
-            forget(other);
+    // The entries of both arrays have the type { i32, void ()*, i8* }. Before LLVM 3.5, the last field
+    // did not exist, so such entries are read with no associated data and upgraded when appending.
+    fn append_global_ctor_dtor(
+        &self,
+        array_name: &str,
+        function: FunctionValue<'ctx>,
+        priority: u32,
+        associated_data: Option<GlobalValue<'ctx>>,
+    ) {
+        let (i32_type, fn_ptr_type, i8_ptr_type, entry_type) = unsafe {
+            let context = LLVMGetModuleContext(self.module.get());
+            let fn_type = LLVMFunctionType(LLVMVoidTypeInContext(context), ptr::null_mut(), 0, 0);
+            let i32_type = IntType::new(LLVMInt32TypeInContext(context));
+            let fn_ptr_type = PointerType::new(LLVMPointerType(fn_type, 0));
+            let i8_ptr_type = PointerType::new(LLVMPointerType(LLVMInt8TypeInContext(context), 0));
+            let mut field_types = [
+                i32_type.as_type_ref(),
+                fn_ptr_type.as_type_ref(),
+                i8_ptr_type.as_type_ref(),
+            ];
+            let entry_type = StructType::new(LLVMStructTypeInContext(context, field_types.as_mut_ptr(), 3, 0));
+
+            (i32_type, fn_ptr_type, i8_ptr_type, entry_type)
+        };
 
-            if code == 1 {
-                unsafe { Err(LLVMString::new(err_string)) }
-            } else {
-                Ok(())
+        let mut entries = Vec::new();
+
+        if let Some(array) = self.get_global(array_name) {
+            if let Some(initializer) = array.get_initializer() {
+                let initializer = initializer.into_array_value();
+
+                for index in 0..initializer.get_type().len() {
+                    let entry = initializer.const_extract_value(&mut [index]).into_struct_value();
+                    let associated_data = if entry.get_type().count_fields() > 2 {
+                        entry.const_extract_value(&mut [2])
+                    } else {
+                        i8_ptr_type.const_null().into()
+                    };
+
+                    let function = entry
+                        .const_extract_value(&mut [1])
+                        .into_pointer_value()
+                        .const_cast(fn_ptr_type);
+
+                    entries.push(entry_type.const_named_struct(&[
+                        entry.const_extract_value(&mut [0]),
+                        function.into(),
+                        associated_data,
+                    ]));
+                }
             }
-        }
-        #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7")))]
-        {
-            use crate::support::error_handling::get_error_str_diagnostic_handler;
-            use libc::c_void;
-            use llvm_sys::linker::LLVMLinkModules2;
 
-            let context = self.get_context();
+            // The array's type changes with its length, so it has to be recreated.
+            unsafe { array.delete() }
+        }
 
-            let mut char_ptr: *mut ::libc::c_char = ptr::null_mut();
-            let char_ptr_ptr = &mut char_ptr as *mut *mut ::libc::c_char as *mut *mut c_void as *mut c_void;
+        let associated_data = match associated_data {
+            Some(global) => global.as_pointer_value().const_cast(i8_ptr_type),
+            None => i8_ptr_type.const_null(),
+        };
 
-            // Newer LLVM versions don't use an out ptr anymore which was really straightforward...
-            // Here we assign an error handler to extract the error message, if any, for us.
-            context.set_diagnostic_handler(get_error_str_diagnostic_handler, char_ptr_ptr);
+        entries.push(
+            entry_type.const_named_struct(&[
+                i32_type.const_int(priority as u64, false).into(),
+                function
+                    .as_global_value()
+                    .as_pointer_value()
+                    .const_cast(fn_ptr_type)
+                    .into(),
+                associated_data.into(),
+            ]),
+        );
 
-            let code = unsafe { LLVMLinkModules2(self.module.get(), other.module.get()) };
+        let initializer = entry_type.const_array(&entries);
+        let array = self.add_global(initializer.get_type(), None, array_name);
 
-            forget(other);
+        array.set_linkage(Linkage::Appending);
+        array.set_initializer(&initializer);
+    }
 
-            if code == 1 {
-                debug_assert!(!char_ptr.is_null());
+    fn get_global_ctor_dtors(&self, array_name: &str) -> Vec<GlobalCtorDtor<'ctx>> {
+        let initializer = match self.get_global(array_name).and_then(|array| array.get_initializer()) {
+            Some(initializer) => initializer.into_array_value(),
+            None => return Vec::new(),
+        };
 
-                unsafe { Err(LLVMString::new(char_ptr)) }
-            } else {
-                Ok(())
-            }
-        }
+        (0..initializer.get_type().len())
+            .filter_map(|index| {
+                let entry = initializer.const_extract_value(&mut [index]).into_struct_value();
+                let priority = entry
+                    .const_extract_value(&mut [0])
+                    .into_int_value()
+                    .get_zero_extended_constant()?;
+                let function = unsafe { strip_constant_pointer_casts(entry.const_extract_value(&mut [1])) };
+
+                // Null entries are allowed, and simply ignored by LLVM.
+                if unsafe { LLVMIsAFunction(function).is_null() } {
+                    return None;
+                }
+
+                let associated_data = if entry.get_type().count_fields() > 2 {
+                    let data = unsafe { strip_constant_pointer_casts(entry.const_extract_value(&mut [2])) };
+
+                    if unsafe { LLVMIsAGlobalValue(data).is_null() } {
+                        None
+                    } else {
+                        unsafe { Some(GlobalValue::new(data)) }
+                    }
+                } else {
+                    None
+                };
+
+                Some(GlobalCtorDtor {
+                    priority: priority as u32,
+                    function: unsafe { FunctionValue::new(function) }?,
+                    associated_data,
+                })
+            })
+            .collect()
     }
 
-    /// Gets the `Comdat` associated with a particular name. If it does not exist, it will be created.
-    /// A new `Comdat` defaults to a kind of `ComdatSelectionKind::Any`.
-    #[llvm_versions(7.0..=latest)]
-    pub fn get_or_insert_comdat(&self, name: &str) -> Comdat {
-        use llvm_sys::comdat::LLVMGetOrInsertComdat;
+    /// Appends `global` to the `llvm.used` array of this `Module`, unless it's already in it.
+    /// This prevents the compiler, assembler and linker from discarding it, even if nothing
+    /// references it.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    /// let i32_type = context.i32_type();
+    /// let global = module.add_global(i32_type, None, "registration");
+    ///
+    /// global.set_initializer(&i32_type.const_zero());
+    /// global.set_section("my_plugins");
+    ///
+    /// module.add_used(global);
+    ///
+    /// assert_eq!(module.get_used(), vec![global]);
+    /// ```
+    pub fn add_used(&self, global: GlobalValue<'ctx>) {
+        self.add_to_used_array("llvm.used", global)
+    }
+
+    /// Appends `global` to the `llvm.compiler.used` array of this `Module`, unless it's already in it.
+    /// This prevents the compiler from discarding it, even if nothing references it, but unlike
+    /// `add_used` still allows the linker to do so.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    /// let i32_type = context.i32_type();
+    /// let global = module.add_global(i32_type, None, "registration");
+    ///
+    /// global.set_initializer(&i32_type.const_zero());
+    ///
+    /// module.add_compiler_used(global);
+    ///
+    /// assert_eq!(module.get_compiler_used(), vec![global]);
+    /// ```
+    pub fn add_compiler_used(&self, global: GlobalValue<'ctx>) {
+        self.add_to_used_array("llvm.compiler.used", global)
+    }
+
+    /// Gets the globals in the `llvm.used` array of this `Module`, in order.
+    pub fn get_used(&self) -> Vec<GlobalValue<'ctx>> {
+        self.get_used_array("llvm.used")
+    }
+
+    /// Gets the globals in the `llvm.compiler.used` array of this `Module`, in order.
+    pub fn get_compiler_used(&self) -> Vec<GlobalValue<'ctx>> {
+        self.get_used_array("llvm.compiler.used")
+    }
+
+    /// Removes `global` from the `llvm.used` array of this `Module`, returning whether it was in it.
+    /// The array is deleted once it's empty.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    /// let i32_type = context.i32_type();
+    /// let global = module.add_global(i32_type, None, "registration");
+    ///
+    /// module.add_used(global);
+    ///
+    /// assert!(module.remove_used(global));
+    /// assert!(!module.remove_used(global));
+    /// assert!(module.get_global("llvm.used").is_none());
+    /// ```
+    pub fn remove_used(&self, global: GlobalValue<'ctx>) -> bool {
+        self.remove_from_used_array("llvm.used", global)
+    }
+
+    /// Removes `global` from the `llvm.compiler.used` array of this `Module`, returning whether it was in it.
+    /// The array is deleted once it's empty.
+    pub fn remove_compiler_used(&self, global: GlobalValue<'ctx>) -> bool {
+        self.remove_from_used_array("llvm.compiler.used", global)
+    }
+
+    fn add_to_used_array(&self, array_name: &str, global: GlobalValue<'ctx>) {
+        let mut globals = self.get_used_array(array_name);
+
+        if !globals.contains(&global) {
+            globals.push(global);
+
+            self.set_used_array(array_name, &globals);
+        }
+    }
+
+    fn remove_from_used_array(&self, array_name: &str, global: GlobalValue<'ctx>) -> bool {
+        let mut globals = self.get_used_array(array_name);
+        let len = globals.len();
+
+        globals.retain(|used| *used != global);
+
+        if globals.len() == len {
+            return false;
+        }
+
+        self.set_used_array(array_name, &globals);
+
+        true
+    }
+
+    fn get_used_array(&self, array_name: &str) -> Vec<GlobalValue<'ctx>> {
+        let initializer = match self.get_global(array_name).and_then(|array| array.get_initializer()) {
+            Some(initializer) => initializer.into_array_value(),
+            None => return Vec::new(),
+        };
+
+        (0..initializer.get_type().len())
+            .filter_map(|index| {
+                let global = unsafe { strip_constant_pointer_casts(initializer.const_extract_value(&mut [index])) };
+
+                if unsafe { LLVMIsAGlobalValue(global).is_null() } {
+                    return None;
+                }
+
+                unsafe { Some(GlobalValue::new(global)) }
+            })
+            .collect()
+    }
+
+    // Both arrays have the type [N x i8*] and must live in the llvm.metadata section.
+    fn set_used_array(&self, array_name: &str, globals: &[GlobalValue<'ctx>]) {
+        // The array's type changes with its length, so it has to be recreated.
+        if let Some(array) = self.get_global(array_name) {
+            unsafe { array.delete() }
+        }
+
+        if globals.is_empty() {
+            return;
+        }
+
+        let i8_ptr_type = unsafe {
+            let context = LLVMGetModuleContext(self.module.get());
+
+            PointerType::new(LLVMPointerType(LLVMInt8TypeInContext(context), 0))
+        };
+        let values: Vec<_> = globals
+            .iter()
+            .map(|global| global.as_pointer_value().const_cast(i8_ptr_type))
+            .collect();
+        let initializer = i8_ptr_type.const_array(&values);
+        let array = self.add_global(initializer.get_type(), None, array_name);
+
+        array.set_linkage(Linkage::Appending);
+        array.set_initializer(&initializer);
+        array.set_section("llvm.metadata");
+    }
+
+    /// Creates a new `Module` from a `MemoryBuffer`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::module::Module;
+    /// use inkwell::memory_buffer::MemoryBuffer;
+    /// use std::path::Path;
+    ///
+    /// let path = Path::new("foo/bar.bc");
+    /// let context = Context::create();
+    /// let buffer = MemoryBuffer::create_from_file(&path).unwrap();
+    /// let module = Module::parse_bitcode_from_buffer(&buffer, &context);
+    ///
+    /// assert_eq!(*module.unwrap().get_context(), context);
+    ///
+    /// ```
+    pub fn parse_bitcode_from_buffer(buffer: &MemoryBuffer, context: &'ctx Context) -> Result<Self, LLVMString> {
+        let mut module = MaybeUninit::uninit();
+        let mut err_string = MaybeUninit::uninit();
+
+        // LLVM has a newer version of this function w/o the error result since 3.8 but this deprecated function
+        // hasen't yet been removed even in LLVM 8. Seems fine to use instead of switching to their
+        // error diagnostics handler for now.
+        #[allow(deprecated)]
+        let success = unsafe {
+            LLVMParseBitcodeInContext(
+                context.context,
+                buffer.memory_buffer,
+                module.as_mut_ptr(),
+                err_string.as_mut_ptr(),
+            )
+        };
+
+        if success != 0 {
+            unsafe {
+                return Err(LLVMString::new(err_string.assume_init()));
+            }
+        }
+
+        unsafe { Ok(Module::new(module.assume_init())) }
+    }
+
+    /// A convenience function for creating a `Module` from a file for a given context.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::module::Module;
+    /// use std::path::Path;
+    ///
+    /// let path = Path::new("foo/bar.bc");
+    /// let context = Context::create();
+    /// let module = Module::parse_bitcode_from_path(&path, &context);
+    ///
+    /// assert_eq!(*module.unwrap().get_context(), context);
+    ///
+    /// ```
+    // LLVMGetBitcodeModuleInContext was a pain to use, so I seem to be able to achieve the same effect
+    // by reusing create_from_file instead. This is basically just a convenience function.
+    pub fn parse_bitcode_from_path<P: AsRef<Path>>(path: P, context: &'ctx Context) -> Result<Self, LLVMString> {
+        let buffer = MemoryBuffer::create_from_file(path.as_ref())?;
+
+        Self::parse_bitcode_from_buffer(&buffer, &context)
+    }
+
+    /// Gets the name of this `Module`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    ///
+    /// assert_eq!(module.get_name().to_str(), Ok("my_mdoule"));
+    /// ```
+    #[llvm_versions(3.9..=latest)]
+    pub fn get_name(&self) -> &CStr {
+        let mut length = 0;
+        let cstr_ptr = unsafe { LLVMGetModuleIdentifier(self.module.get(), &mut length) };
+
+        unsafe { CStr::from_ptr(cstr_ptr) }
+    }
+
+    /// Assigns the name of this `Module`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    ///
+    /// module.set_name("my_module2");
+    ///
+    /// assert_eq!(module.get_name().to_str(), Ok("my_module2"));
+    /// ```
+    #[llvm_versions(3.9..=latest)]
+    pub fn set_name(&self, name: &str) {
+        unsafe { LLVMSetModuleIdentifier(self.module.get(), name.as_ptr() as *const ::libc::c_char, name.len()) }
+    }
+
+    /// Gets the source file name. It defaults to the module identifier but is separate from it.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    ///
+    /// assert_eq!(module.get_source_file_name().to_str(), Ok("my_mod"));
+    ///
+    /// module.set_source_file_name("my_mod.rs");
+    ///
+    /// assert_eq!(module.get_name().to_str(), Ok("my_mod"));
+    /// assert_eq!(module.get_source_file_name().to_str(), Ok("my_mod.rs"));
+    /// ```
+    #[llvm_versions(7.0..=latest)]
+    pub fn get_source_file_name(&self) -> &CStr {
+        use llvm_sys::core::LLVMGetSourceFileName;
+
+        let mut len = 0;
+        let ptr = unsafe { LLVMGetSourceFileName(self.module.get(), &mut len) };
+
+        unsafe { CStr::from_ptr(ptr) }
+    }
+
+    /// Sets the source file name. It defaults to the module identifier but is separate from it.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    ///
+    /// assert_eq!(module.get_source_file_name().to_str(), Ok("my_mod"));
+    ///
+    /// module.set_source_file_name("my_mod.rs");
+    ///
+    /// assert_eq!(module.get_name().to_str(), Ok("my_mod"));
+    /// assert_eq!(module.get_source_file_name().to_str(), Ok("my_mod.rs"));
+    /// ```
+    #[llvm_versions(7.0..=latest)]
+    pub fn set_source_file_name(&self, file_name: &str) {
+        use llvm_sys::core::LLVMSetSourceFileName;
+
+        unsafe {
+            LLVMSetSourceFileName(
+                self.module.get(),
+                file_name.as_ptr() as *const ::libc::c_char,
+                file_name.len(),
+            )
+        }
+    }
+
+    /// Links one module into another. This will merge two `Module`s into one.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("mod");
+    /// let module2 = context.create_module("mod2");
+    ///
+    /// assert!(module.link_in_module(module2).is_ok());
+    /// ```
+    pub fn link_in_module(&self, other: Self) -> Result<(), LLVMString> {
+        if other.owned_by_ee.borrow().is_some() {
+            let string = "Cannot link a module which is already owned by an ExecutionEngine.\0";
+            return Err(LLVMString::create_from_str(string));
+        }
+
+        #[cfg(any(feature = "llvm3-6", feature = "llvm3-7"))]
+        {
+            use llvm_sys::linker::{LLVMLinkModules, LLVMLinkerMode};
+
+            let mut err_string = ptr::null_mut();
+            // As of 3.7, LLVMLinkerDestroySource is the only option
+            let mode = LLVMLinkerMode::LLVMLinkerDestroySource;
+            let code = unsafe { LLVMLinkModules(self.module.get(), other.module.get(), mode, &mut err_string) };
+
+            forget(other);
+
+            if code == 1 {
+                unsafe { Err(LLVMString::new(err_string)) }
+            } else {
+                Ok(())
+            }
+        }
+        #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7")))]
+        {
+            use crate::support::error_handling::get_error_str_diagnostic_handler;
+            use libc::c_void;
+            use llvm_sys::linker::LLVMLinkModules2;
+
+            let context = self.get_context();
+
+            let mut char_ptr: *mut ::libc::c_char = ptr::null_mut();
+            let char_ptr_ptr = &mut char_ptr as *mut *mut ::libc::c_char as *mut *mut c_void as *mut c_void;
+
+            // Newer LLVM versions don't use an out ptr anymore which was really straightforward...
+            // Here we assign an error handler to extract the error message, if any, for us.
+            context.set_diagnostic_handler(get_error_str_diagnostic_handler, char_ptr_ptr);
+
+            let code = unsafe { LLVMLinkModules2(self.module.get(), other.module.get()) };
+
+            forget(other);
+
+            if code == 1 {
+                debug_assert!(!char_ptr.is_null());
+
+                unsafe { Err(LLVMString::new(char_ptr)) }
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// Splits this `Module` into `partitions` new `Module`s, in the style of LLVM's `SplitModule`,
+    /// so that each of them can be code generated in parallel.
+    ///
+    /// Every function and global variable definition is placed into exactly one partition, trying to
+    /// keep the number of instructions in each partition balanced, and is turned into an external
+    /// declaration in all other partitions. Definitions which share a `Comdat` are kept together.
+    /// Aliases and ifuncs are placed along with the function or global variable they point to, and
+    /// each partition's `llvm.used` and `llvm.compiler.used` arrays only keep the symbols it defines.
+    /// Other appending globals, such as `llvm.global_ctors`, are placed into the first partition. Since
+    /// partitions reference each other's symbols, every symbol with `Internal` or `Private` linkage
+    /// is given `External` linkage and `Hidden` visibility (and a name, if it had none), so that the
+    /// resulting object files link back together.
+    ///
+    /// This `Module` is left unchanged. An error is returned if it does not pass verification.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `partitions` is zero.
+    ///
+    /// # Example
+    ///
+    /// A `Module` can't be sent to another thread, but its bitcode can. Each partition can then be
+    /// parsed into a `Context` of its own and emitted independently:
+    ///
+    /// ```no_run
+    /// use inkwell::OptimizationLevel;
+    /// use inkwell::context::Context;
+    /// use inkwell::memory_buffer::MemoryBuffer;
+    /// use inkwell::module::Module;
+    /// use inkwell::targets::{CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetMachine};
+    ///
+    /// use std::thread;
+    ///
+    /// Target::initialize_native(&InitializationConfig::default()).unwrap();
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    ///
+    /// // ... fill in `module` ...
+    ///
+    /// let bitcodes: Vec<Vec<u8>> = module
+    ///     .split(4)
+    ///     .unwrap()
+    ///     .iter()
+    ///     .map(|partition| partition.write_bitcode_to_memory().as_slice().to_vec())
+    ///     .collect();
+    ///
+    /// let workers: Vec<_> = bitcodes
+    ///     .into_iter()
+    ///     .map(|bitcode| {
+    ///         thread::spawn(move || {
+    ///             let context = Context::create();
+    ///             let buffer = MemoryBuffer::create_from_memory_range_copy(&bitcode, "partition");
+    ///             let module = Module::parse_bitcode_from_buffer(&buffer, &context).unwrap();
+    ///             let triple = TargetMachine::get_default_triple();
+    ///             let target = Target::from_triple(&triple).unwrap();
+    ///             let target_machine = target
+    ///                 .create_target_machine(&triple, "generic", "", OptimizationLevel::Default, RelocMode::PIC, CodeModel::Default)
+    ///                 .unwrap();
+    ///
+    ///             target_machine
+    ///                 .write_to_memory_buffer(&module, FileType::Object)
+    ///                 .unwrap()
+    ///                 .as_slice()
+    ///                 .to_vec()
+    ///         })
+    ///     })
+    ///     .collect();
+    ///
+    /// let objects: Vec<Vec<u8>> = workers.into_iter().map(|worker| worker.join().unwrap()).collect();
+    /// ```
+    #[llvm_versions(7.0..=latest)]
+    pub fn split(&self, partitions: u32) -> Result<Vec<Self>, LLVMString> {
+        use llvm_sys::comdat::{LLVMGetComdat, LLVMSetComdat};
+        use llvm_sys::core::LLVMSetPersonalityFn;
+        use llvm_sys::debuginfo::LLVMSetSubprogram;
+        use std::collections::HashMap;
+
+        assert!(partitions > 0, "A Module must be split into at least one partition.");
+
+        self.verify()?;
+
+        // Definitions are identified by their position in the module's function and global lists,
+        // which LLVMCloneModule preserves. `None` means the symbol is left untouched in every partition.
+        let mut function_groups = Vec::new();
+        let mut global_groups = Vec::new();
+        let mut group_costs: Vec<u64> = Vec::new();
+        let mut comdat_groups = HashMap::new();
+
+        let mut group_of = |comdat: LLVMComdatRef, cost: u64| {
+            let group = if comdat.is_null() {
+                group_costs.push(0);
+                group_costs.len() - 1
+            } else {
+                *comdat_groups.entry(comdat).or_insert_with(|| {
+                    group_costs.push(0);
+                    group_costs.len() - 1
+                })
+            };
+
+            group_costs[group] += cost;
+
+            group
+        };
+
+        for function in self.get_functions() {
+            let global = function.as_global_value();
+
+            if global.is_declaration() || global.get_linkage() == Linkage::AvailableExternally {
+                function_groups.push(None);
+                continue;
+            }
+
+            let cost = function
+                .get_basic_blocks()
+                .iter()
+                .map(|block| count_instructions(*block))
+                .sum::<u64>();
+            let comdat = unsafe { LLVMGetComdat(global.as_value_ref()) };
+
+            function_groups.push(Some(group_of(comdat, cost.max(1))));
+        }
+
+        let mut global = self.get_first_global();
+
+        while let Some(current) = global {
+            let is_partitioned = !current.is_declaration()
+                && current.get_linkage() != Linkage::AvailableExternally
+                && current.get_linkage() != Linkage::Appending;
+
+            if is_partitioned {
+                let comdat = unsafe { LLVMGetComdat(current.as_value_ref()) };
+
+                global_groups.push(Some(group_of(comdat, 1)));
+            } else {
+                global_groups.push(None);
+            }
+
+            global = current.get_next_global();
+        }
+
+        // Aliases and ifuncs can't be separated from the definition they point to.
+        let mut definition_groups = HashMap::new();
+
+        for (function, group) in self.get_functions().zip(&function_groups) {
+            definition_groups.insert(function.as_value_ref(), *group);
+        }
+
+        let mut global = self.get_first_global();
+
+        for group in &global_groups {
+            let current = global.expect("global groups should match the globals");
+
+            definition_groups.insert(current.as_value_ref(), *group);
+            global = current.get_next_global();
+        }
+
+        let indirect_symbol_groups: Vec<Option<usize>> = unsafe { get_indirect_symbols(self.module.get()) }
+            .into_iter()
+            .map(|symbol| {
+                let object = unsafe { get_aliased_object(symbol) };
+
+                definition_groups.get(&object).copied().flatten()
+            })
+            .collect();
+
+        // Greedily hand out the most expensive groups first, each to the least loaded partition.
+        let mut order: Vec<usize> = (0..group_costs.len()).collect();
+        order.sort_by(|a, b| group_costs[*b].cmp(&group_costs[*a]));
+
+        let mut loads = vec![0u64; partitions as usize];
+        let mut group_partitions = vec![0u32; group_costs.len()];
+
+        for group in order {
+            let (partition, _) = loads
+                .iter()
+                .enumerate()
+                .min_by_key(|(index, load)| (**load, *index))
+                .expect("partitions is non zero");
+
+            loads[partition] += group_costs[group];
+            group_partitions[group] = partition as u32;
+        }
+
+        let mut modules = Vec::with_capacity(partitions as usize);
+
+        for partition in 0..partitions {
+            let module = unsafe { Module::new(clone_module(self)) };
+
+            for (function, group) in module.get_functions().zip(&function_groups) {
+                let global = function.as_global_value();
+
+                externalize_local(global);
+
+                match group {
+                    Some(group) if group_partitions[*group] != partition => unsafe {
+                        delete_function_body(function);
+
+                        LLVMSetPersonalityFn(function.as_value_ref(), ptr::null_mut());
+                        LLVMSetSubprogram(function.as_value_ref(), ptr::null_mut());
+                        LLVMSetComdat(function.as_value_ref(), ptr::null_mut());
+
+                        global.set_linkage(Linkage::External);
+                    },
+                    _ => {},
+                }
+            }
+
+            let mut groups = global_groups.iter();
+            let mut global = module.get_first_global();
+
+            while let Some(current) = global {
+                let group = groups.next().expect("cloned module should have the same globals");
+
+                global = current.get_next_global();
+
+                if current.get_linkage() == Linkage::Appending {
+                    let name = current.get_name().to_bytes();
+                    let is_used_array = name == b"llvm.used" || name == b"llvm.compiler.used";
+
+                    if partition != 0 && !is_used_array {
+                        unsafe { current.delete() }
+                    }
+
+                    continue;
+                }
+
+                externalize_local(current);
+
+                match group {
+                    Some(group) if group_partitions[*group] != partition => unsafe {
+                        LLVMSetInitializer(current.as_value_ref(), ptr::null_mut());
+                        LLVMSetComdat(current.as_value_ref(), ptr::null_mut());
+
+                        current.set_linkage(Linkage::External);
+                    },
+                    _ => {},
+                }
+            }
+
+            let mut has_dead_symbols = false;
+            let indirect_symbols = unsafe { get_indirect_symbols(module.module.get()) };
+
+            for (symbol, group) in indirect_symbols.into_iter().zip(&indirect_symbol_groups) {
+                let global = unsafe { GlobalValue::new(symbol) };
+
+                externalize_local(global);
+
+                match group {
+                    Some(group) if group_partitions[*group] != partition => {
+                        unsafe { declare_indirect_symbol(&module, global) };
+
+                        has_dead_symbols = true;
+                    },
+                    _ => {},
+                }
+            }
+
+            // Only definitions may be marked as used, which also drops the replaced aliases and ifuncs.
+            let mut used = module.get_used();
+            let mut compiler_used = module.get_compiler_used();
+
+            used.retain(|global| !global.is_declaration());
+            compiler_used.retain(|global| !global.is_declaration());
+
+            // The C API can't erase an alias, so the replaced ones are left for GlobalDCE to delete,
+            // with every other symbol temporarily kept alive through `llvm.compiler.used`.
+            if has_dead_symbols {
+                let mut live_symbols = Vec::new();
+
+                live_symbols.extend(module.get_functions().map(|function| function.as_global_value()));
+                live_symbols.extend(
+                    module
+                        .get_globals()
+                        .filter(|global| global.get_linkage() != Linkage::Appending),
+                );
+
+                for symbol in unsafe { get_indirect_symbols(module.module.get()) } {
+                    let global = unsafe { GlobalValue::new(symbol) };
+
+                    if global.get_linkage() != Linkage::Private {
+                        live_symbols.push(global);
+                    }
+                }
+
+                module.set_used_array("llvm.compiler.used", &live_symbols);
+
+                let pass_manager = PassManager::create(());
+
+                pass_manager.add_global_dce_pass();
+                pass_manager.run_on(&module);
+            }
+
+            module.set_used_array("llvm.used", &used);
+            module.set_used_array("llvm.compiler.used", &compiler_used);
+
+            modules.push(module);
+        }
+
+        Ok(modules)
+    }
+
+    /// Gets the `Comdat` associated with a particular name. If it does not exist, it will be created.
+    /// A new `Comdat` defaults to a kind of `ComdatSelectionKind::Any`.
+    #[llvm_versions(7.0..=latest)]
+    pub fn get_or_insert_comdat(&self, name: &str) -> Comdat {
+        use llvm_sys::comdat::LLVMGetOrInsertComdat;
 
         let c_string = to_c_str(name);
         let comdat_ptr = unsafe { LLVMGetOrInsertComdat(self.module.get(), c_string.as_ptr()) };
@@ -1566,6 +2572,30 @@ pub enum FlagBehavior {
     AppendUnique,
 }
 
+/// An entry of the `llvm.global_ctors` or `llvm.global_dtors` array of a `Module`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct GlobalCtorDtor<'ctx> {
+    /// The priority of the entry. Entries with a lower priority run first.
+    pub priority: u32,
+    /// The function which is run.
+    pub function: FunctionValue<'ctx>,
+    /// The global this entry is discarded along with, if any.
+    pub associated_data: Option<GlobalValue<'ctx>>,
+}
+
+/// The reason a `GlobalValue` could not be removed from a `Module`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RemoveGlobalError<'ctx> {
+    /// The `GlobalValue`, or its replacement, does not belong to this `Module`.
+    NotInModule,
+    /// The `GlobalValue` is neither a function nor a global variable, ie an alias.
+    Unsupported,
+    /// The `GlobalValue` was to be replaced by itself.
+    SelfReplacement,
+    /// The `GlobalValue` is still used by these values.
+    HasUsers(Vec<AnyValueEnum<'ctx>>),
+}
+
 /// Iterate over all `FunctionValue`s in an llvm module
 #[derive(Debug)]
 pub struct FunctionIterator<'ctx>(FunctionIteratorInner<'ctx>);
@@ -1613,3 +2643,377 @@ impl<'ctx> Iterator for FunctionIterator<'ctx> {
         }
     }
 }
+
+/// Iterate over all global variables in an llvm module
+#[derive(Debug)]
+pub struct GlobalIterator<'ctx>(Option<GlobalValue<'ctx>>);
+
+impl<'ctx> GlobalIterator<'ctx> {
+    fn from_module(module: &Module<'ctx>) -> Self {
+        Self(module.get_first_global())
+    }
+}
+
+impl<'ctx> Iterator for GlobalIterator<'ctx> {
+    type Item = GlobalValue<'ctx>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.0?;
+
+        self.0 = current.get_next_global();
+
+        Some(current)
+    }
+}
+
+/// Iterate over all global variables, then all functions, and then all aliases and ifuncs in an llvm module
+#[derive(Debug)]
+pub struct GlobalValueIterator<'ctx> {
+    globals: GlobalIterator<'ctx>,
+    functions: FunctionIterator<'ctx>,
+    indirect_symbols: vec::IntoIter<LLVMValueRef>,
+}
+
+impl<'ctx> Iterator for GlobalValueIterator<'ctx> {
+    type Item = GlobalValue<'ctx>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.globals
+            .next()
+            .or_else(|| self.functions.next().map(|function| function.as_global_value()))
+            .or_else(|| {
+                self.indirect_symbols
+                    .next()
+                    .map(|symbol| unsafe { GlobalValue::new(symbol) })
+            })
+    }
+}
+
+/// Iterate over all named metadata in an llvm module, yielding their names and operands
+#[llvm_versions(7.0..=latest)]
+#[derive(Debug)]
+pub struct NamedMetadataIterator<'a, 'ctx> {
+    module: &'a Module<'ctx>,
+    next: LLVMNamedMDNodeRef,
+}
+
+#[llvm_versions(7.0..=latest)]
+impl<'a, 'ctx> Iterator for NamedMetadataIterator<'a, 'ctx> {
+    type Item = (CString, Vec<MetadataValue<'ctx>>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next.is_null() {
+            return None;
+        }
+
+        let current = self.next;
+
+        self.next = unsafe { LLVMGetNextNamedMetadata(current) };
+
+        // The name isn't guaranteed to be null terminated.
+        let mut len = 0;
+        let name = unsafe {
+            let ptr = LLVMGetNamedMetadataName(current, &mut len);
+
+            slice::from_raw_parts(ptr as *const u8, len)
+        };
+        let name = CString::new(name).expect("Named metadata names should not contain null bytes");
+        let operands = self.module.get_named_metadata_operands(&name);
+
+        Some((name, operands))
+    }
+}
+
+/// Gives a symbol with `Internal` or `Private` linkage `External` linkage and `Hidden`
+/// visibility, so that it can be referenced from another partition of a split `Module`.
+#[llvm_versions(7.0..=latest)]
+fn externalize_local(global: GlobalValue<'_>) {
+    match global.get_linkage() {
+        Linkage::Internal | Linkage::Private => {},
+        _ => return,
+    }
+
+    // Unnamed symbols can't be referenced across modules. LLVM uniques the name for us.
+    if global.get_name().to_bytes().is_empty() {
+        global.set_name("__llvmsplit_unnamed");
+    }
+
+    global.set_linkage(Linkage::External);
+    global.set_visibility(GlobalVisibility::Hidden);
+}
+
+#[llvm_versions(7.0..=latest)]
+fn count_instructions(basic_block: BasicBlock<'_>) -> u64 {
+    let mut count = 0;
+    let mut instruction = basic_block.get_first_instruction();
+
+    while let Some(current) = instruction {
+        count += 1;
+        instruction = current.get_next_instruction();
+    }
+
+    count
+}
+
+/// Deletes every `BasicBlock` of a function, turning it into a declaration.
+///
+/// This is unsafe because any outstanding reference to its blocks or instructions becomes dangling.
+#[llvm_versions(7.0..=latest)]
+unsafe fn delete_function_body(function: FunctionValue<'_>) {
+    let basic_blocks = function.get_basic_blocks();
+
+    // Instructions may be used across blocks, so all uses must go before anything is erased.
+    for basic_block in &basic_blocks {
+        let mut instruction = basic_block.get_first_instruction();
+
+        while let Some(current) = instruction {
+            let value = current.as_value_ref();
+
+            if !LLVMGetFirstUse(value).is_null() {
+                LLVMReplaceAllUsesWith(value, LLVMGetUndef(LLVMTypeOf(value)));
+            }
+
+            instruction = current.get_next_instruction();
+        }
+    }
+
+    for basic_block in &basic_blocks {
+        while let Some(instruction) = basic_block.get_last_instruction() {
+            instruction.erase_from_basic_block();
+        }
+    }
+
+    for basic_block in basic_blocks {
+        LLVMDeleteBasicBlock(basic_block.basic_block);
+    }
+}
+
+/// Clones a module into its own context. Before LLVM 16, `LLVMCloneModule` drops ifuncs and leaves
+/// their users referencing the original module, so modules containing any go through bitcode instead.
+#[llvm_versions(7.0..=8.0)]
+unsafe fn clone_module(module: &Module<'_>) -> LLVMModuleRef {
+    LLVMCloneModule(module.module.get())
+}
+
+/// Clones a module into its own context. Before LLVM 16, `LLVMCloneModule` drops ifuncs and leaves
+/// their users referencing the original module, so modules containing any go through bitcode instead.
+#[llvm_versions(9.0..=latest)]
+unsafe fn clone_module(module: &Module<'_>) -> LLVMModuleRef {
+    use llvm_sys::core::LLVMGetFirstGlobalIFunc;
+
+    if LLVMGetFirstGlobalIFunc(module.module.get()).is_null() {
+        return LLVMCloneModule(module.module.get());
+    }
+
+    let buffer = module.write_bitcode_to_memory();
+    let mut clone = MaybeUninit::uninit();
+    let mut err_string = MaybeUninit::uninit();
+
+    #[allow(deprecated)]
+    let failed = LLVMParseBitcodeInContext(
+        LLVMGetModuleContext(module.module.get()),
+        buffer.memory_buffer,
+        clone.as_mut_ptr(),
+        err_string.as_mut_ptr(),
+    );
+
+    assert_eq!(failed, 0, "A verified module should round trip through bitcode.");
+
+    clone.assume_init()
+}
+
+/// Gets the aliases of a module, followed by its ifuncs.
+#[llvm_versions(3.6..=6.0)]
+unsafe fn get_indirect_symbols(_module: LLVMModuleRef) -> Vec<LLVMValueRef> {
+    Vec::new()
+}
+
+/// Gets the aliases of a module, followed by its ifuncs.
+#[llvm_versions(7.0..=8.0)]
+unsafe fn get_indirect_symbols(module: LLVMModuleRef) -> Vec<LLVMValueRef> {
+    use llvm_sys::core::{LLVMGetFirstGlobalAlias, LLVMGetNextGlobalAlias};
+
+    let mut symbols = Vec::new();
+    let mut alias = LLVMGetFirstGlobalAlias(module);
+
+    while !alias.is_null() {
+        symbols.push(alias);
+        alias = LLVMGetNextGlobalAlias(alias);
+    }
+
+    symbols
+}
+
+/// Gets the aliases of a module, followed by its ifuncs.
+#[llvm_versions(9.0..=latest)]
+unsafe fn get_indirect_symbols(module: LLVMModuleRef) -> Vec<LLVMValueRef> {
+    use llvm_sys::core::{
+        LLVMGetFirstGlobalAlias, LLVMGetFirstGlobalIFunc, LLVMGetNextGlobalAlias, LLVMGetNextGlobalIFunc,
+    };
+
+    let mut symbols = Vec::new();
+    let mut alias = LLVMGetFirstGlobalAlias(module);
+
+    while !alias.is_null() {
+        symbols.push(alias);
+        alias = LLVMGetNextGlobalAlias(alias);
+    }
+
+    let mut ifunc = LLVMGetFirstGlobalIFunc(module);
+
+    while !ifunc.is_null() {
+        symbols.push(ifunc);
+        ifunc = LLVMGetNextGlobalIFunc(ifunc);
+    }
+
+    symbols
+}
+
+/// Gets the aliasee of an alias or the resolver of an ifunc, if `value` is either.
+#[llvm_versions(7.0..=8.0)]
+unsafe fn get_indirect_symbol_target(value: LLVMValueRef) -> Option<LLVMValueRef> {
+    use llvm_sys::core::{LLVMAliasGetAliasee, LLVMIsAGlobalAlias};
+
+    if LLVMIsAGlobalAlias(value).is_null() {
+        return None;
+    }
+
+    Some(LLVMAliasGetAliasee(value))
+}
+
+/// Gets the aliasee of an alias or the resolver of an ifunc, if `value` is either.
+#[llvm_versions(9.0..=latest)]
+unsafe fn get_indirect_symbol_target(value: LLVMValueRef) -> Option<LLVMValueRef> {
+    use llvm_sys::core::{LLVMAliasGetAliasee, LLVMGetGlobalIFuncResolver, LLVMIsAGlobalAlias, LLVMIsAGlobalIFunc};
+
+    if !LLVMIsAGlobalAlias(value).is_null() {
+        return Some(LLVMAliasGetAliasee(value));
+    }
+
+    if !LLVMIsAGlobalIFunc(value).is_null() {
+        return Some(LLVMGetGlobalIFuncResolver(value));
+    }
+
+    None
+}
+
+/// Follows an alias or ifunc through casts, constant GEPs and other aliases down to the function
+/// or global variable defining it.
+#[llvm_versions(7.0..=latest)]
+unsafe fn get_aliased_object(symbol: LLVMValueRef) -> LLVMValueRef {
+    let mut value = symbol;
+
+    loop {
+        if !LLVMIsAConstantExpr(value).is_null() {
+            match LLVMGetConstOpcode(value) {
+                LLVMOpcode::LLVMBitCast | LLVMOpcode::LLVMAddrSpaceCast | LLVMOpcode::LLVMGetElementPtr => {
+                    value = LLVMGetOperand(value, 0)
+                },
+                _ => return value,
+            }
+        } else if let Some(target) = get_indirect_symbol_target(value) {
+            value = target;
+        } else {
+            return value;
+        }
+    }
+}
+
+#[llvm_versions(7.0..8.0)]
+unsafe fn get_global_value_type(global: LLVMValueRef) -> LLVMTypeRef {
+    use llvm_sys::core::LLVMGetElementType;
+
+    LLVMGetElementType(LLVMTypeOf(global))
+}
+
+#[llvm_versions(8.0..=latest)]
+unsafe fn get_global_value_type(global: LLVMValueRef) -> LLVMTypeRef {
+    use llvm_sys::core::LLVMGlobalGetValueType;
+
+    LLVMGlobalGetValueType(global)
+}
+
+/// Replaces every use of an alias or ifunc with an external declaration of the same name and type.
+/// The symbol itself is left unnamed, unused and with `Private` linkage, ready to be deleted.
+///
+/// This is unsafe because the symbol is left pointing to a definition that might not exist anymore.
+#[llvm_versions(7.0..=latest)]
+unsafe fn declare_indirect_symbol(module: &Module<'_>, symbol: GlobalValue<'_>) {
+    use llvm_sys::core::{LLVMConstPointerCast, LLVMGetPointerAddressSpace, LLVMGetTypeKind};
+    use llvm_sys::LLVMTypeKind;
+
+    let name = symbol.get_name().to_owned();
+    let symbol_ref = symbol.as_value_ref();
+    let value_type = get_global_value_type(symbol_ref);
+    let visibility = symbol.get_visibility();
+
+    symbol.set_name("");
+
+    let declaration = if LLVMGetTypeKind(value_type) == LLVMTypeKind::LLVMFunctionTypeKind {
+        LLVMAddFunction(module.module.get(), name.as_ptr(), value_type)
+    } else {
+        let address_space = LLVMGetPointerAddressSpace(LLVMTypeOf(symbol_ref));
+        let declaration = LLVMAddGlobalInAddressSpace(module.module.get(), value_type, name.as_ptr(), address_space);
+
+        GlobalValue::new(declaration).set_thread_local_mode(symbol.get_thread_local_mode());
+
+        declaration
+    };
+
+    GlobalValue::new(declaration).set_visibility(visibility);
+
+    LLVMReplaceAllUsesWith(symbol_ref, LLVMConstPointerCast(declaration, LLVMTypeOf(symbol_ref)));
+
+    symbol.set_linkage(Linkage::Private);
+    symbol.set_visibility(GlobalVisibility::Default);
+}
+
+/// Strips any bitcast or address space cast constant expressions off of a constant pointer.
+unsafe fn strip_constant_pointer_casts<V: AsValueRef>(value: V) -> LLVMValueRef {
+    let mut value = value.as_value_ref();
+
+    while !LLVMIsAConstantExpr(value).is_null() {
+        match LLVMGetConstOpcode(value) {
+            LLVMOpcode::LLVMBitCast | LLVMOpcode::LLVMAddrSpaceCast => value = LLVMGetOperand(value, 0),
+            _ => break,
+        }
+    }
+
+    value
+}
+
+/// Collects the instructions and globals using `value`, looking through constant expressions,
+/// but ignoring uses by `global` itself (or its body, if it's a function).
+unsafe fn collect_users<'ctx>(
+    value: LLVMValueRef,
+    global: LLVMValueRef,
+    is_function: bool,
+    users: &mut Vec<AnyValueEnum<'ctx>>,
+) {
+    let mut use_ = LLVMGetFirstUse(value);
+
+    while !use_.is_null() {
+        let user = LLVMGetUser(use_);
+
+        use_ = LLVMGetNextUse(use_);
+
+        if user == global {
+            continue;
+        }
+
+        if !LLVMIsAInstruction(user).is_null() {
+            if is_function && LLVMGetBasicBlockParent(LLVMGetInstructionParent(user)) == global {
+                continue;
+            }
+        } else if LLVMIsAGlobalValue(user).is_null() && !LLVMIsAConstant(user).is_null() {
+            collect_users(user, global, is_function, users);
+            continue;
+        }
+
+        let user = AnyValueEnum::new(user);
+
+        if !users.contains(&user) {
+            users.push(user);
+        }
+    }
+}
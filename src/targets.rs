@@ -35,6 +35,7 @@ use std::default::Default;
 use std::ffi::CStr;
 use std::fmt;
 use std::mem::MaybeUninit;
+use std::ops::Range;
 use std::path::Path;
 use std::ptr;
 
@@ -1380,6 +1381,122 @@ impl TargetData {
             ))
         }
     }
+
+    /// Computes the layout of a `StructType` in memory, including the offset of every field
+    /// and the padding in between. Returns `None` if the `StructType` is opaque.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::targets::TargetData;
+    ///
+    /// let context = Context::create();
+    /// let target_data = TargetData::create("e-i64:64");
+    /// let struct_type = context.struct_type(&[context.i8_type().into(), context.i64_type().into()], false);
+    /// let layout = target_data.struct_layout(&struct_type).unwrap();
+    ///
+    /// assert_eq!(layout.size(), 16);
+    /// assert_eq!(layout.alignment(), 8);
+    /// assert_eq!(layout.field_offsets(), &[0, 8]);
+    /// assert_eq!(layout.padding(), &[1..8]);
+    /// assert_eq!(layout.field_at_offset(12), Some(1));
+    /// ```
+    pub fn struct_layout(&self, struct_type: &StructType) -> Option<StructLayout> {
+        if struct_type.is_opaque() {
+            return None;
+        }
+
+        let size = self.get_abi_size(struct_type);
+        let mut field_offsets = Vec::new();
+        let mut field_sizes = Vec::new();
+        let mut padding = Vec::new();
+        let mut end_of_previous_field = 0;
+
+        for (index, field_type) in struct_type.get_field_types().iter().enumerate() {
+            let offset = unsafe { LLVMOffsetOfElement(self.target_data, struct_type.as_type_ref(), index as u32) };
+
+            if offset > end_of_previous_field {
+                padding.push(end_of_previous_field..offset);
+            }
+
+            let field_size = self.get_abi_size(field_type);
+
+            field_offsets.push(offset);
+            field_sizes.push(field_size);
+            end_of_previous_field = offset + field_size;
+        }
+
+        if size > end_of_previous_field {
+            padding.push(end_of_previous_field..size);
+        }
+
+        Some(StructLayout {
+            size,
+            alignment: self.get_abi_alignment(struct_type),
+            field_offsets,
+            field_sizes,
+            padding,
+        })
+    }
+}
+
+/// The layout of a `StructType` in memory, as computed by `TargetData::struct_layout`.
+///
+/// Sizes are ABI sizes, which include any tail padding of a field's own type, so they match
+/// what `sizeof` gives in C.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructLayout {
+    size: u64,
+    alignment: u32,
+    field_offsets: Vec<u64>,
+    field_sizes: Vec<u64>,
+    padding: Vec<Range<u64>>,
+}
+
+impl StructLayout {
+    /// Gets the size of the struct in bytes, including tail padding.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Gets the ABI alignment of the struct in bytes.
+    pub fn alignment(&self) -> u32 {
+        self.alignment
+    }
+
+    /// Gets the byte offset of every field, in order.
+    pub fn field_offsets(&self) -> &[u64] {
+        &self.field_offsets
+    }
+
+    /// Gets the byte offset of the field at `index`, if there is one.
+    pub fn field_offset(&self, index: u32) -> Option<u64> {
+        self.field_offsets.get(index as usize).copied()
+    }
+
+    /// Gets the size in bytes of the field at `index`, if there is one.
+    pub fn field_size(&self, index: u32) -> Option<u64> {
+        self.field_sizes.get(index as usize).copied()
+    }
+
+    /// Gets the byte ranges which are not covered by any field, in order.
+    /// The last range may be tail padding which rounds the size up to the alignment.
+    pub fn padding(&self) -> &[Range<u64>] {
+        &self.padding
+    }
+
+    /// Gets the index of the field which covers the byte at `offset`. Returns `None` if that
+    /// byte is padding, or lies outside of the struct.
+    pub fn field_at_offset(&self, offset: u64) -> Option<u32> {
+        // Zero sized fields may share their offset with another, so the last field covering the
+        // byte wins. Structs have few enough fields that a linear search is fine.
+        self.field_offsets
+            .iter()
+            .zip(&self.field_sizes)
+            .rposition(|(&field_offset, &field_size)| field_offset <= offset && offset < field_offset + field_size)
+            .map(|index| index as u32)
+    }
 }
 
 impl Drop for TargetData {
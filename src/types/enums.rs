@@ -4,7 +4,10 @@ use llvm_sys::LLVMTypeKind;
 
 use crate::types::traits::AsTypeRef;
 use crate::types::MetadataType;
-use crate::types::{ArrayType, FloatType, FunctionType, IntType, PointerType, StructType, VectorType, VoidType};
+use crate::types::{
+    ArrayType, FloatType, FunctionType, IntType, LabelType, PointerType, StructType, TokenType, VectorType, VoidType,
+    X86AMXType,
+};
 use crate::values::{BasicValue, BasicValueEnum, IntValue};
 
 use std::convert::TryFrom;
@@ -53,7 +56,7 @@ macro_rules! enum_type_set {
 }
 
 enum_type_set! {
-    /// A wrapper for any `BasicType`, `VoidType`, or `FunctionType`.
+    /// A wrapper for any `BasicType`, `VoidType`, `FunctionType`, or one of the special types.
     AnyTypeEnum: {
         /// A contiguous homogeneous container type.
         ArrayType,
@@ -71,6 +74,12 @@ enum_type_set! {
         VectorType,
         /// A valueless type.
         VoidType,
+        /// An opaque type which can only be passed around.
+        TokenType,
+        /// The type of a basic block.
+        LabelType,
+        /// The type of an x86 AMX tile.
+        X86AMXType,
     }
 }
 enum_type_set! {
@@ -99,6 +108,8 @@ enum_type_set! {
         StructType,
         VectorType,
         MetadataType,
+        TokenType,
+        X86AMXType,
     }
 }
 
@@ -159,6 +170,22 @@ impl<'ctx> BasicMetadataTypeEnum<'ctx> {
         }
     }
 
+    pub fn into_token_type(self) -> TokenType<'ctx> {
+        if let BasicMetadataTypeEnum::TokenType(t) = self {
+            t
+        } else {
+            panic!("Found {:?} but expected another variant", self);
+        }
+    }
+
+    pub fn into_x86_amx_type(self) -> X86AMXType<'ctx> {
+        if let BasicMetadataTypeEnum::X86AMXType(t) = self {
+            t
+        } else {
+            panic!("Found {:?} but expected another variant", self);
+        }
+    }
+
     pub fn is_array_type(self) -> bool {
         matches!(self, BasicMetadataTypeEnum::ArrayType(_))
     }
@@ -186,6 +213,38 @@ impl<'ctx> BasicMetadataTypeEnum<'ctx> {
     pub fn is_vector_type(self) -> bool {
         matches!(self, BasicMetadataTypeEnum::VectorType(_))
     }
+
+    pub fn is_token_type(self) -> bool {
+        matches!(self, BasicMetadataTypeEnum::TokenType(_))
+    }
+
+    pub fn is_x86_amx_type(self) -> bool {
+        matches!(self, BasicMetadataTypeEnum::X86AMXType(_))
+    }
+}
+
+impl<'ctx> BasicMetadataTypeEnum<'ctx> {
+    pub(crate) unsafe fn new(type_: LLVMTypeRef) -> Self {
+        #[cfg(not(any(
+            feature = "llvm3-6",
+            feature = "llvm3-7",
+            feature = "llvm3-8",
+            feature = "llvm3-9",
+            feature = "llvm4-0",
+            feature = "llvm5-0"
+        )))]
+        {
+            if let LLVMTypeKind::LLVMMetadataTypeKind = LLVMGetTypeKind(type_) {
+                return BasicMetadataTypeEnum::MetadataType(MetadataType::new(type_));
+            }
+        }
+
+        match AnyTypeEnum::new(type_) {
+            AnyTypeEnum::TokenType(t) => BasicMetadataTypeEnum::TokenType(t),
+            AnyTypeEnum::X86AMXType(t) => BasicMetadataTypeEnum::X86AMXType(t),
+            _ => BasicTypeEnum::new(type_).into(),
+        }
+    }
 }
 
 impl<'ctx> AnyTypeEnum<'ctx> {
@@ -205,7 +264,7 @@ impl<'ctx> AnyTypeEnum<'ctx> {
                 feature = "llvm14-0"
             ))]
             LLVMTypeKind::LLVMBFloatTypeKind => AnyTypeEnum::FloatType(FloatType::new(type_)),
-            LLVMTypeKind::LLVMLabelTypeKind => panic!("FIXME: Unsupported type: Label"),
+            LLVMTypeKind::LLVMLabelTypeKind => AnyTypeEnum::LabelType(LabelType::new(type_)),
             LLVMTypeKind::LLVMIntegerTypeKind => AnyTypeEnum::IntType(IntType::new(type_)),
             LLVMTypeKind::LLVMFunctionTypeKind => AnyTypeEnum::FunctionType(FunctionType::new(type_)),
             LLVMTypeKind::LLVMStructTypeKind => AnyTypeEnum::StructType(StructType::new(type_)),
@@ -222,9 +281,9 @@ impl<'ctx> AnyTypeEnum<'ctx> {
             LLVMTypeKind::LLVMMetadataTypeKind => unreachable!("Metadata type is not supported as AnyType."),
             LLVMTypeKind::LLVMX86_MMXTypeKind => panic!("FIXME: Unsupported type: MMX"),
             #[cfg(any(feature = "llvm12-0", feature = "llvm13-0", feature = "llvm14-0"))]
-            LLVMTypeKind::LLVMX86_AMXTypeKind => panic!("FIXME: Unsupported type: AMX"),
+            LLVMTypeKind::LLVMX86_AMXTypeKind => AnyTypeEnum::X86AMXType(X86AMXType::new(type_)),
             #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7")))]
-            LLVMTypeKind::LLVMTokenTypeKind => panic!("FIXME: Unsupported type: Token"),
+            LLVMTypeKind::LLVMTokenTypeKind => AnyTypeEnum::TokenType(TokenType::new(type_)),
         }
     }
 
@@ -297,6 +356,30 @@ impl<'ctx> AnyTypeEnum<'ctx> {
         }
     }
 
+    pub fn into_token_type(self) -> TokenType<'ctx> {
+        if let AnyTypeEnum::TokenType(t) = self {
+            t
+        } else {
+            panic!("Found {:?} but expected the TokenType variant", self);
+        }
+    }
+
+    pub fn into_label_type(self) -> LabelType<'ctx> {
+        if let AnyTypeEnum::LabelType(t) = self {
+            t
+        } else {
+            panic!("Found {:?} but expected the LabelType variant", self);
+        }
+    }
+
+    pub fn into_x86_amx_type(self) -> X86AMXType<'ctx> {
+        if let AnyTypeEnum::X86AMXType(t) = self {
+            t
+        } else {
+            panic!("Found {:?} but expected the X86AMXType variant", self);
+        }
+    }
+
     pub fn is_array_type(self) -> bool {
         matches!(self, AnyTypeEnum::ArrayType(_))
     }
@@ -329,6 +412,18 @@ impl<'ctx> AnyTypeEnum<'ctx> {
         matches!(self, AnyTypeEnum::VoidType(_))
     }
 
+    pub fn is_token_type(self) -> bool {
+        matches!(self, AnyTypeEnum::TokenType(_))
+    }
+
+    pub fn is_label_type(self) -> bool {
+        matches!(self, AnyTypeEnum::LabelType(_))
+    }
+
+    pub fn is_x86_amx_type(self) -> bool {
+        matches!(self, AnyTypeEnum::X86AMXType(_))
+    }
+
     pub fn size_of(&self) -> Option<IntValue<'ctx>> {
         match self {
             AnyTypeEnum::ArrayType(t) => t.size_of(),
@@ -339,6 +434,9 @@ impl<'ctx> AnyTypeEnum<'ctx> {
             AnyTypeEnum::VectorType(t) => t.size_of(),
             AnyTypeEnum::VoidType(_) => None,
             AnyTypeEnum::FunctionType(_) => None,
+            AnyTypeEnum::TokenType(_) => None,
+            AnyTypeEnum::LabelType(_) => None,
+            AnyTypeEnum::X86AMXType(_) => None,
         }
     }
 }
@@ -1,8 +1,13 @@
-use llvm_sys::core::{LLVMConstArray, LLVMConstReal, LLVMConstRealOfStringAndSize};
+use llvm_sys::core::{
+    LLVMConstArray, LLVMConstBitCast, LLVMConstIntOfArbitraryPrecision, LLVMConstReal, LLVMConstRealOfStringAndSize,
+    LLVMGetTypeContext, LLVMGetTypeKind, LLVMIntTypeInContext,
+};
 use llvm_sys::execution_engine::LLVMCreateGenericValueOfFloat;
 use llvm_sys::prelude::{LLVMTypeRef, LLVMValueRef};
+use llvm_sys::LLVMTypeKind;
 
 use crate::context::ContextRef;
+use crate::memory_buffer::MemoryBuffer;
 use crate::types::enums::BasicMetadataTypeEnum;
 use crate::types::traits::AsTypeRef;
 use crate::types::{ArrayType, BasicTypeEnum, FunctionType, PointerType, Type, VectorType};
@@ -75,6 +80,28 @@ impl<'ctx> FloatType<'ctx> {
         self.float_type.vec_type(size)
     }
 
+    /// Creates a scalable `VectorType` with this `FloatType` for its element type. Its actual
+    /// number of elements is `size` times a target specific constant called `vscale`,
+    /// which is only known at runtime.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let f32_type = context.f32_type();
+    /// let f32_scalable_vector_type = f32_type.scalable_vec_type(4);
+    ///
+    /// assert!(f32_scalable_vector_type.is_scalable());
+    /// assert_eq!(f32_scalable_vector_type.get_size(), 4);
+    /// assert_eq!(f32_scalable_vector_type.get_element_type().into_float_type(), f32_type);
+    /// ```
+    #[llvm_versions(12.0..=latest)]
+    pub fn scalable_vec_type(self, size: u32) -> VectorType<'ctx> {
+        self.float_type.scalable_vec_type(size)
+    }
+
     /// Creates a `FloatValue` repesenting a constant value of this `FloatType`.
     /// It will be automatically assigned this `FloatType`'s `Context`.
     ///
@@ -132,6 +159,92 @@ impl<'ctx> FloatType<'ctx> {
         }
     }
 
+    /// Creates a `FloatValue` with exactly the given bit pattern, which makes NaN payloads and
+    /// constants that an `f64` can't hold, such as most `f128` values, possible. `words` is read
+    /// least significant word first; missing words are zero and bits past the width of this type
+    /// are ignored.
+    ///
+    /// For a `ppc_f128`, the first word is the bits of the high-order `f64` and the second those of
+    /// the low-order one, as in the `0xM` form of LLVM IR.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let f128_type = context.f128_type();
+    /// // 1 + 2^-112, which rounds to 1 as an f64
+    /// let f128_val = f128_type.const_float_from_bits(&[1, 0x3FFF_0000_0000_0000]);
+    ///
+    /// assert_eq!(f128_val.get_bits(), Some(vec![1, 0x3FFF_0000_0000_0000]));
+    /// assert_eq!(f128_val.get_constant(), Some((1., true)));
+    /// ```
+    pub fn const_float_from_bits(self, words: &[u64]) -> FloatValue<'ctx> {
+        if unsafe { LLVMGetTypeKind(self.as_type_ref()) } == LLVMTypeKind::LLVMPPC_FP128TypeKind {
+            return self.const_ppc_f128_from_bits(words);
+        }
+
+        unsafe {
+            let int_type = LLVMIntTypeInContext(LLVMGetTypeContext(self.as_type_ref()), self.get_bit_width());
+            let int_value = LLVMConstIntOfArbitraryPrecision(int_type, words.len() as u32, words.as_ptr());
+
+            FloatValue::new(LLVMConstBitCast(int_value, self.as_type_ref()))
+        }
+    }
+
+    // LLVM won't fold a bitcast to a `ppc_f128` without knowing the target's endianness, but its IR
+    // parser builds one from exactly the given bits.
+    fn const_ppc_f128_from_bits(self, words: &[u64]) -> FloatValue<'ctx> {
+        let word = |index: usize| words.get(index).copied().unwrap_or(0);
+        let ir = format!("@0 = constant ppc_fp128 0xM{:016X}{:016X}", word(0), word(1));
+        let context = self.get_context();
+        let module = context
+            .create_module_from_ir(MemoryBuffer::create_from_memory_range_copy(ir.as_bytes(), "ppc_f128"))
+            .expect("ppc_f128 constant should always parse");
+        let initializer = module.get_first_global().and_then(|global| global.get_initializer());
+
+        // Constants belong to the context, so they outlive the module
+        unsafe {
+            FloatValue::new(
+                initializer
+                    .expect("ppc_f128 constant should have an initializer")
+                    .as_value_ref(),
+            )
+        }
+    }
+
+    /// Gets the number of bits in this `FloatType`, such as 80 for an `x86_f80`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    ///
+    /// assert_eq!(context.f16_type().get_bit_width(), 16);
+    /// assert_eq!(context.x86_f80_type().get_bit_width(), 80);
+    /// assert_eq!(context.ppc_f128_type().get_bit_width(), 128);
+    /// ```
+    pub fn get_bit_width(self) -> u32 {
+        match unsafe { LLVMGetTypeKind(self.as_type_ref()) } {
+            LLVMTypeKind::LLVMHalfTypeKind => 16,
+            #[cfg(any(
+                feature = "llvm11-0",
+                feature = "llvm12-0",
+                feature = "llvm13-0",
+                feature = "llvm14-0"
+            ))]
+            LLVMTypeKind::LLVMBFloatTypeKind => 16,
+            LLVMTypeKind::LLVMFloatTypeKind => 32,
+            LLVMTypeKind::LLVMDoubleTypeKind => 64,
+            LLVMTypeKind::LLVMX86_FP80TypeKind => 80,
+            LLVMTypeKind::LLVMFP128TypeKind | LLVMTypeKind::LLVMPPC_FP128TypeKind => 128,
+            _ => unreachable!("FloatType should only hold floating point types."),
+        }
+    }
+
     /// Creates a constant zero value of this `FloatType`.
     ///
     /// # Example
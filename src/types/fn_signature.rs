@@ -0,0 +1,283 @@
+use llvm_sys::core::{LLVMCountParamTypes, LLVMGetParamTypes, LLVMGetReturnType};
+
+#[llvm_versions(3.9..=latest)]
+use crate::attributes::{Attribute, AttributeLoc};
+#[llvm_versions(3.9..=latest)]
+use crate::context::Context;
+use crate::types::traits::AsTypeRef;
+use crate::types::{AnyType, AnyTypeEnum, BasicMetadataTypeEnum, BasicTypeEnum, FunctionType, Type};
+use crate::values::{BasicValue, FunctionValue};
+
+/// An attribute of a function parameter or return value, as recorded by a `FunctionSignature`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ParamAttribute<'ctx> {
+    /// The pointer does not alias any other pointer accessible to the function (`noalias`).
+    NoAlias,
+    /// The pointer is never null (`nonnull`).
+    NonNull,
+    /// The argument is a copy of the pointed-to value, made by the caller (`byval`).
+    ByVal(BasicTypeEnum<'ctx>),
+    /// The pointer is where the function writes its structured return value (`sret`).
+    StructRet(BasicTypeEnum<'ctx>),
+    /// The integer is zero extended to the width required by the ABI (`zeroext`).
+    ZeroExt,
+    /// The integer is sign extended to the width required by the ABI (`signext`).
+    SignExt,
+    /// The pointer is aligned to this many bytes (`align`).
+    Align(u32),
+}
+
+impl ParamAttribute<'_> {
+    #[llvm_versions(3.9..=latest)]
+    pub(crate) fn create_attribute(self, context: &Context) -> Attribute {
+        let kind_id = |name| Attribute::get_named_enum_kind_id(name);
+
+        match self {
+            ParamAttribute::NoAlias => context.create_enum_attribute(kind_id("noalias"), 0),
+            ParamAttribute::NonNull => context.create_enum_attribute(kind_id("nonnull"), 0),
+            ParamAttribute::ByVal(ty) => Self::create_pointee_attribute(context, kind_id("byval"), ty),
+            ParamAttribute::StructRet(ty) => Self::create_pointee_attribute(context, kind_id("sret"), ty),
+            ParamAttribute::ZeroExt => context.create_enum_attribute(kind_id("zeroext"), 0),
+            ParamAttribute::SignExt => context.create_enum_attribute(kind_id("signext"), 0),
+            ParamAttribute::Align(bytes) => context.create_enum_attribute(kind_id("align"), u64::from(bytes)),
+        }
+    }
+
+    // Before type attributes were added, the pointee type was implied by the pointer
+    #[llvm_versions(3.9..=11.0)]
+    fn create_pointee_attribute(context: &Context, kind_id: u32, _ty: BasicTypeEnum) -> Attribute {
+        context.create_enum_attribute(kind_id, 0)
+    }
+
+    #[llvm_versions(12.0..=latest)]
+    fn create_pointee_attribute(context: &Context, kind_id: u32, ty: BasicTypeEnum) -> Attribute {
+        context.create_type_attribute(kind_id, ty.as_any_type_enum())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+struct ParamSignature<'ctx> {
+    name: String,
+    attributes: Vec<ParamAttribute<'ctx>>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum SignatureType<'ctx> {
+    // A type converted into a signature is kept as is, so that it isn't rebuilt for every function
+    // added with it and may have parameters which `BasicMetadataTypeEnum` can't represent.
+    Existing(FunctionType<'ctx>),
+    Assembled {
+        return_type: AnyTypeEnum<'ctx>,
+        param_types: Vec<BasicMetadataTypeEnum<'ctx>>,
+        is_var_args: bool,
+    },
+}
+
+/// A description of a function's type, together with the names and attributes of its parameters,
+/// the attributes of its return value and its calling convention. `Module::add_function` applies
+/// all of it to the new function.
+///
+/// # Example
+///
+/// ```no_run
+/// use inkwell::context::Context;
+/// use inkwell::types::{FunctionSignature, ParamAttribute};
+/// use inkwell::AddressSpace;
+///
+/// let context = Context::create();
+/// let module = context.create_module("signature");
+/// let i8_ptr_type = context.i8_type().ptr_type(AddressSpace::Generic);
+/// let signature = FunctionSignature::new(i8_ptr_type)
+///     .param_with_attributes("dst", i8_ptr_type, &[ParamAttribute::NoAlias, ParamAttribute::Align(8)])
+///     .param("src", i8_ptr_type)
+///     .param_with_attributes("len", context.i32_type(), &[ParamAttribute::ZeroExt])
+///     .return_attribute(ParamAttribute::NonNull);
+/// let fn_value = module.add_function("copy", signature, None);
+///
+/// assert_eq!(fn_value.get_nth_param(2).unwrap().into_int_value().get_name().to_str(), Ok("len"));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FunctionSignature<'ctx> {
+    ty: SignatureType<'ctx>,
+    params: Vec<ParamSignature<'ctx>>,
+    return_attributes: Vec<ParamAttribute<'ctx>>,
+    call_conventions: Option<u32>,
+}
+
+impl<'ctx> FunctionSignature<'ctx> {
+    /// Creates a `FunctionSignature` without any parameters. `return_type` may be a `VoidType`,
+    /// a `BasicType` or one of the special return types such as a `TokenType`.
+    pub fn new<T: AnyType<'ctx>>(return_type: T) -> Self {
+        let return_type = return_type.as_any_type_enum();
+
+        assert!(
+            !return_type.is_function_type() && !return_type.is_label_type(),
+            "Function and label types cannot be returned."
+        );
+
+        FunctionSignature {
+            ty: SignatureType::Assembled {
+                return_type,
+                param_types: Vec::new(),
+                is_var_args: false,
+            },
+            params: Vec::new(),
+            return_attributes: Vec::new(),
+            call_conventions: None,
+        }
+    }
+
+    /// Adds a parameter named `name`. An empty name leaves the parameter unnamed.
+    pub fn param<T: Into<BasicMetadataTypeEnum<'ctx>>>(mut self, name: &str, ty: T) -> Self {
+        if let SignatureType::Assembled { param_types, .. } = self.assembled_type() {
+            param_types.push(ty.into());
+        }
+
+        self.params.push(ParamSignature {
+            name: name.to_owned(),
+            attributes: Vec::new(),
+        });
+        self
+    }
+
+    /// Adds a parameter named `name` with the given attributes.
+    #[llvm_versions(3.9..=latest)]
+    pub fn param_with_attributes<T: Into<BasicMetadataTypeEnum<'ctx>>>(
+        mut self,
+        name: &str,
+        ty: T,
+        attributes: &[ParamAttribute<'ctx>],
+    ) -> Self {
+        self = self.param(name, ty);
+        self.params.last_mut().unwrap().attributes.extend_from_slice(attributes);
+        self
+    }
+
+    /// Adds an attribute to the return value.
+    #[llvm_versions(3.9..=latest)]
+    pub fn return_attribute(mut self, attribute: ParamAttribute<'ctx>) -> Self {
+        self.return_attributes.push(attribute);
+        self
+    }
+
+    /// Allows the function to take any number of arguments after its named parameters.
+    pub fn var_args(mut self) -> Self {
+        if let SignatureType::Existing(fn_type) = self.ty {
+            if fn_type.is_var_arg() {
+                return self;
+            }
+        }
+
+        if let SignatureType::Assembled { is_var_args, .. } = self.assembled_type() {
+            *is_var_args = true;
+        }
+
+        self
+    }
+
+    /// Sets the calling convention, as taken by `FunctionValue::set_call_conventions`.
+    pub fn call_conventions(mut self, call_conventions: u32) -> Self {
+        self.call_conventions = Some(call_conventions);
+        self
+    }
+
+    /// Gets the `FunctionType` described by this signature.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::types::FunctionSignature;
+    ///
+    /// let context = Context::create();
+    /// let i32_type = context.i32_type();
+    /// let signature = FunctionSignature::new(context.void_type()).param("x", i32_type).var_args();
+    ///
+    /// assert_eq!(signature.get_fn_type(), context.void_type().fn_type(&[i32_type.into()], true));
+    /// ```
+    pub fn get_fn_type(&self) -> FunctionType<'ctx> {
+        match &self.ty {
+            SignatureType::Existing(fn_type) => *fn_type,
+            SignatureType::Assembled {
+                return_type,
+                param_types,
+                is_var_args,
+            } => unsafe { Type::new(return_type.as_type_ref()).fn_type(param_types, *is_var_args) },
+        }
+    }
+
+    // Splits an existing type into its parts, so that they can be changed.
+    fn assembled_type(&mut self) -> &mut SignatureType<'ctx> {
+        if let SignatureType::Existing(fn_type) = self.ty {
+            let count = unsafe { LLVMCountParamTypes(fn_type.as_type_ref()) };
+            let mut raw_types = vec![std::ptr::null_mut(); count as usize];
+
+            unsafe { LLVMGetParamTypes(fn_type.as_type_ref(), raw_types.as_mut_ptr()) };
+
+            self.ty = SignatureType::Assembled {
+                return_type: unsafe { AnyTypeEnum::new(LLVMGetReturnType(fn_type.as_type_ref())) },
+                param_types: raw_types
+                    .into_iter()
+                    .map(|ty| unsafe { BasicMetadataTypeEnum::new(ty) })
+                    .collect(),
+                is_var_args: fn_type.is_var_arg(),
+            };
+            self.params.resize(count as usize, ParamSignature::default());
+        }
+
+        &mut self.ty
+    }
+
+    /// Names the parameters of `fn_value` and gives them, its return value and the function
+    /// itself the recorded attributes and calling convention.
+    pub(crate) fn apply(&self, fn_value: FunctionValue<'ctx>) {
+        for (index, param) in self.params.iter().enumerate() {
+            if !param.name.is_empty() {
+                if let Some(value) = fn_value.get_nth_param(index as u32) {
+                    value.set_name(&param.name);
+                }
+            }
+        }
+
+        self.apply_attributes(fn_value);
+
+        if let Some(call_conventions) = self.call_conventions {
+            fn_value.set_call_conventions(call_conventions);
+        }
+    }
+
+    #[llvm_versions(3.6..=3.8)]
+    fn apply_attributes(&self, _fn_value: FunctionValue<'ctx>) {}
+
+    #[llvm_versions(3.9..=latest)]
+    fn apply_attributes(&self, fn_value: FunctionValue<'ctx>) {
+        let context = fn_value.get_type().get_context();
+
+        for (index, param) in self.params.iter().enumerate() {
+            for attribute in &param.attributes {
+                fn_value.add_attribute(AttributeLoc::Param(index as u32), attribute.create_attribute(&context));
+            }
+        }
+
+        for attribute in &self.return_attributes {
+            fn_value.add_attribute(AttributeLoc::Return, attribute.create_attribute(&context));
+        }
+    }
+}
+
+impl<'ctx> From<FunctionType<'ctx>> for FunctionSignature<'ctx> {
+    fn from(fn_type: FunctionType<'ctx>) -> Self {
+        FunctionSignature {
+            ty: SignatureType::Existing(fn_type),
+            params: Vec::new(),
+            return_attributes: Vec::new(),
+            call_conventions: None,
+        }
+    }
+}
+
+impl<'ctx> From<&FunctionSignature<'ctx>> for FunctionSignature<'ctx> {
+    fn from(signature: &FunctionSignature<'ctx>) -> Self {
+        signature.clone()
+    }
+}
@@ -159,7 +159,8 @@ impl<'ctx> FunctionType<'ctx> {
         self.fn_type.print_to_stderr()
     }
 
-    /// Gets the return type of this `FunctionType`.
+    /// Gets the return type of this `FunctionType`. This is `None` for functions returning
+    /// void, as well as the special `TokenType` and `X86AMXType`, which are not basic types.
     ///
     /// # Example
     ///
@@ -177,8 +178,13 @@ impl<'ctx> FunctionType<'ctx> {
 
         let kind = unsafe { LLVMGetTypeKind(ty) };
 
-        if let LLVMTypeKind::LLVMVoidTypeKind = kind {
-            return None;
+        match kind {
+            LLVMTypeKind::LLVMVoidTypeKind => return None,
+            #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7")))]
+            LLVMTypeKind::LLVMTokenTypeKind => return None,
+            #[cfg(any(feature = "llvm12-0", feature = "llvm13-0", feature = "llvm14-0"))]
+            LLVMTypeKind::LLVMX86_AMXTypeKind => return None,
+            _ => (),
         }
 
         unsafe { Some(BasicTypeEnum::new(ty)) }
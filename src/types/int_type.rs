@@ -246,6 +246,28 @@ impl<'ctx> IntType<'ctx> {
         self.int_type.vec_type(size)
     }
 
+    /// Creates a scalable `VectorType` with this `IntType` for its element type. Its actual
+    /// number of elements is `size` times a target specific constant called `vscale`,
+    /// which is only known at runtime.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let i8_type = context.i8_type();
+    /// let i8_scalable_vector_type = i8_type.scalable_vec_type(4);
+    ///
+    /// assert!(i8_scalable_vector_type.is_scalable());
+    /// assert_eq!(i8_scalable_vector_type.get_size(), 4);
+    /// assert_eq!(i8_scalable_vector_type.get_element_type().into_int_type(), i8_type);
+    /// ```
+    #[llvm_versions(12.0..=latest)]
+    pub fn scalable_vec_type(self, size: u32) -> VectorType<'ctx> {
+        self.int_type.scalable_vec_type(size)
+    }
+
     /// Gets a reference to the `Context` this `IntType` was created in.
     ///
     /// # Example
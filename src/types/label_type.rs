@@ -0,0 +1,44 @@
+use llvm_sys::prelude::LLVMTypeRef;
+
+use crate::context::ContextRef;
+use crate::types::traits::AsTypeRef;
+use crate::types::Type;
+
+/// A `LabelType` is the type of a `BasicBlock` when it is used as a value,
+/// such as the destination operand of a branch instruction.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LabelType<'ctx> {
+    label_type: Type<'ctx>,
+}
+
+impl<'ctx> LabelType<'ctx> {
+    pub(crate) unsafe fn new(label_type: LLVMTypeRef) -> Self {
+        assert!(!label_type.is_null());
+
+        LabelType {
+            label_type: Type::new(label_type),
+        }
+    }
+
+    /// Gets a reference to the `Context` this `LabelType` was created in.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let label_type = context.label_type();
+    ///
+    /// assert_eq!(*label_type.get_context(), context);
+    /// ```
+    pub fn get_context(self) -> ContextRef<'ctx> {
+        self.label_type.get_context()
+    }
+}
+
+impl AsTypeRef for LabelType<'_> {
+    fn as_type_ref(&self) -> LLVMTypeRef {
+        self.label_type.ty
+    }
+}
@@ -0,0 +1,193 @@
+use llvm_sys::core::LLVMConstArray;
+use llvm_sys::prelude::LLVMValueRef;
+
+use std::marker::PhantomData;
+use std::mem::size_of;
+
+use crate::context::Context;
+use crate::types::{AsTypeRef, BasicType, BasicTypeEnum};
+use crate::values::{ArrayValue, AsValueRef, BasicValueEnum};
+use crate::AddressSpace;
+
+/// A Rust type with a matching LLVM type, so that its values can be shared with generated code.
+///
+/// It is implemented for `bool`, `char`, integers up to 64 bits wide, floats, raw pointers, arrays of up
+/// to 32 elements and `PhantomData`, and may be derived for `#[repr(C)]` structs whose fields all
+/// implement it. Raw pointers are always represented as `i8*` in the generic address space, no matter
+/// what they point to. Fat pointers, such as `*const [T]`, `*const str` or `*const dyn Trait`, are two
+/// words wide and so are not supported.
+///
+/// `u128` and `i128` are deliberately left out, as their alignment differs between Rust and some
+/// versions of LLVM.
+///
+/// # Example
+///
+/// ```no_run
+/// use inkwell::context::Context;
+/// use inkwell::types::LlvmType;
+///
+/// #[derive(LlvmType)]
+/// #[repr(C)]
+/// struct Point {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// let context = Context::create();
+/// let f32_type = context.f32_type();
+/// let point_type = Point::struct_type(&context);
+///
+/// assert_eq!(point_type.get_field_types(), &[f32_type.into(), f32_type.into()]);
+/// assert_eq!(Point::FIELD_Y, 1);
+///
+/// let point = Point { x: 1.0, y: 2.0 }.const_struct(&context);
+///
+/// assert_eq!(point.get_type(), point_type);
+/// ```
+pub trait LlvmType {
+    /// Gets the LLVM type matching the layout of `Self` in the given `Context`.
+    fn llvm_type(context: &Context) -> BasicTypeEnum<'_>;
+
+    /// Creates a constant of the type given by `llvm_type` from `self`.
+    fn llvm_const<'ctx>(&self, context: &'ctx Context) -> BasicValueEnum<'ctx>;
+}
+
+macro_rules! impl_llvm_type_int {
+    ($($ty:ty),*) => {
+        $(
+            impl LlvmType for $ty {
+                fn llvm_type(context: &Context) -> BasicTypeEnum<'_> {
+                    context.custom_width_int_type(size_of::<$ty>() as u32 * 8).into()
+                }
+
+                #[allow(clippy::unnecessary_cast)]
+                fn llvm_const<'ctx>(&self, context: &'ctx Context) -> BasicValueEnum<'ctx> {
+                    Self::llvm_type(context).into_int_type().const_int(*self as u64, false).into()
+                }
+            }
+        )*
+    };
+}
+
+impl_llvm_type_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl LlvmType for bool {
+    // Like in C, a bool takes up a whole byte in memory
+    fn llvm_type(context: &Context) -> BasicTypeEnum<'_> {
+        context.i8_type().into()
+    }
+
+    fn llvm_const<'ctx>(&self, context: &'ctx Context) -> BasicValueEnum<'ctx> {
+        context.i8_type().const_int(*self as u64, false).into()
+    }
+}
+
+impl LlvmType for char {
+    fn llvm_type(context: &Context) -> BasicTypeEnum<'_> {
+        context.i32_type().into()
+    }
+
+    fn llvm_const<'ctx>(&self, context: &'ctx Context) -> BasicValueEnum<'ctx> {
+        context.i32_type().const_int(*self as u64, false).into()
+    }
+}
+
+impl LlvmType for f32 {
+    fn llvm_type(context: &Context) -> BasicTypeEnum<'_> {
+        context.f32_type().into()
+    }
+
+    fn llvm_const<'ctx>(&self, context: &'ctx Context) -> BasicValueEnum<'ctx> {
+        // Going through an `f64` would quiet signaling NaNs
+        context
+            .f32_type()
+            .const_float_from_bits(&[u64::from(self.to_bits())])
+            .into()
+    }
+}
+
+impl LlvmType for f64 {
+    fn llvm_type(context: &Context) -> BasicTypeEnum<'_> {
+        context.f64_type().into()
+    }
+
+    fn llvm_const<'ctx>(&self, context: &'ctx Context) -> BasicValueEnum<'ctx> {
+        context.f64_type().const_float_from_bits(&[self.to_bits()]).into()
+    }
+}
+
+impl<T> LlvmType for *const T {
+    fn llvm_type(context: &Context) -> BasicTypeEnum<'_> {
+        context.i8_type().ptr_type(AddressSpace::Generic).into()
+    }
+
+    fn llvm_const<'ctx>(&self, context: &'ctx Context) -> BasicValueEnum<'ctx> {
+        let ptr_type = context.i8_type().ptr_type(AddressSpace::Generic);
+
+        if self.is_null() {
+            return ptr_type.const_null().into();
+        }
+
+        (*self as *const u8 as usize)
+            .llvm_const(context)
+            .into_int_value()
+            .const_to_pointer(ptr_type)
+            .into()
+    }
+}
+
+impl<T> LlvmType for *mut T {
+    fn llvm_type(context: &Context) -> BasicTypeEnum<'_> {
+        <*const T>::llvm_type(context)
+    }
+
+    fn llvm_const<'ctx>(&self, context: &'ctx Context) -> BasicValueEnum<'ctx> {
+        (*self as *const T).llvm_const(context)
+    }
+}
+
+fn const_array<'ctx, T: LlvmType>(context: &'ctx Context, elements: &[T]) -> BasicValueEnum<'ctx> {
+    let element_type = T::llvm_type(context);
+    let mut values: Vec<LLVMValueRef> = elements
+        .iter()
+        .map(|value| value.llvm_const(context).as_value_ref())
+        .collect();
+
+    unsafe {
+        ArrayValue::new(LLVMConstArray(
+            element_type.as_type_ref(),
+            values.as_mut_ptr(),
+            values.len() as u32,
+        ))
+        .into()
+    }
+}
+
+macro_rules! array_llvm_type {
+    ($($len:literal)*) => {
+        $(
+            impl<T: LlvmType> LlvmType for [T; $len] {
+                fn llvm_type(context: &Context) -> BasicTypeEnum<'_> {
+                    T::llvm_type(context).array_type($len).into()
+                }
+
+                fn llvm_const<'ctx>(&self, context: &'ctx Context) -> BasicValueEnum<'ctx> {
+                    const_array(context, self)
+                }
+            }
+        )*
+    };
+}
+
+// Like the standard library before const generics, arrays are covered up to 32 elements
+array_llvm_type!(0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32);
+
+impl<T: ?Sized> LlvmType for PhantomData<T> {
+    fn llvm_type(context: &Context) -> BasicTypeEnum<'_> {
+        context.struct_type(&[], false).into()
+    }
+
+    fn llvm_const<'ctx>(&self, context: &'ctx Context) -> BasicValueEnum<'ctx> {
+        context.const_struct(&[], false).into()
+    }
+}
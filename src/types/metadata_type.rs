@@ -1,6 +1,7 @@
 use llvm_sys::prelude::LLVMTypeRef;
 
 use crate::context::ContextRef;
+use crate::support::LLVMString;
 use crate::types::enums::BasicMetadataTypeEnum;
 use crate::types::traits::AsTypeRef;
 use crate::types::{ArrayType, BasicTypeEnum, FunctionType, Type, VectorType};
@@ -54,6 +55,23 @@ impl<'ctx> MetadataType<'ctx> {
     pub fn get_context(self) -> ContextRef<'ctx> {
         self.metadata_type.get_context()
     }
+
+    /// Prints the definition of this `MetadataType` to a `LLVMString`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let md_type = context.metadata_type();
+    ///
+    /// assert_eq!(md_type.print_to_string().to_str(), Ok("metadata"));
+    /// ```
+    #[llvm_versions(6.0..=latest)]
+    pub fn print_to_string(self) -> LLVMString {
+        self.metadata_type.print_to_string()
+    }
 }
 
 impl AsTypeRef for MetadataType<'_> {
@@ -6,42 +6,62 @@ mod enums;
 #[deny(missing_docs)]
 mod float_type;
 #[deny(missing_docs)]
+mod fn_signature;
+#[deny(missing_docs)]
 mod fn_type;
 #[deny(missing_docs)]
 mod int_type;
 #[deny(missing_docs)]
+mod label_type;
+#[deny(missing_docs)]
+mod llvm_type;
+#[deny(missing_docs)]
 mod metadata_type;
 #[deny(missing_docs)]
 mod ptr_type;
 #[deny(missing_docs)]
 mod struct_type;
 #[deny(missing_docs)]
+mod token_type;
+#[deny(missing_docs)]
 mod traits;
 #[deny(missing_docs)]
 mod vec_type;
 #[deny(missing_docs)]
 mod void_type;
+#[deny(missing_docs)]
+mod x86_amx_type;
 
 pub use crate::types::array_type::ArrayType;
 pub use crate::types::enums::{AnyTypeEnum, BasicMetadataTypeEnum, BasicTypeEnum};
 pub use crate::types::float_type::FloatType;
+pub use crate::types::fn_signature::{FunctionSignature, ParamAttribute};
 pub use crate::types::fn_type::FunctionType;
 pub use crate::types::int_type::{IntType, StringRadix};
+pub use crate::types::label_type::LabelType;
+pub use crate::types::llvm_type::LlvmType;
 pub use crate::types::metadata_type::MetadataType;
 pub use crate::types::ptr_type::PointerType;
 pub use crate::types::struct_type::StructType;
-pub use crate::types::traits::{AnyType, BasicType, FloatMathType, IntMathType, PointerMathType};
+pub use crate::types::token_type::TokenType;
+pub use crate::types::traits::{AnyType, BasicType, FloatMathType, IntMathType, PointeeType, PointerMathType};
 pub use crate::types::vec_type::VectorType;
 pub use crate::types::void_type::VoidType;
+pub use crate::types::x86_amx_type::X86AMXType;
+pub use inkwell_internals::LlvmType;
 // Export the AsTypeRef to the outside based on features
 pub(crate) use crate::types::traits::AsTypeRef;
 
 #[llvm_versions(3.7..=4.0)]
 use llvm_sys::core::LLVMDumpType;
+#[llvm_versions(12.0..=latest)]
+use llvm_sys::core::LLVMScalableVectorType;
 use llvm_sys::core::{
-    LLVMAlignOf, LLVMArrayType, LLVMConstNull, LLVMConstPointerNull, LLVMFunctionType, LLVMGetElementType,
-    LLVMGetTypeContext, LLVMGetTypeKind, LLVMGetUndef, LLVMPointerType, LLVMPrintTypeToString, LLVMSizeOf,
-    LLVMTypeIsSized, LLVMVectorType,
+    LLVMAlignOf, LLVMArrayType, LLVMConstNull, LLVMConstPointerNull, LLVMCountParamTypes, LLVMCountStructElementTypes,
+    LLVMFunctionType, LLVMGetArrayLength, LLVMGetElementType, LLVMGetIntTypeWidth, LLVMGetParamTypes,
+    LLVMGetPointerAddressSpace, LLVMGetReturnType, LLVMGetStructElementTypes, LLVMGetTypeContext, LLVMGetTypeKind,
+    LLVMGetUndef, LLVMGetVectorSize, LLVMIsFunctionVarArg, LLVMIsOpaqueStruct, LLVMIsPackedStruct, LLVMPointerType,
+    LLVMPrintTypeToString, LLVMSizeOf, LLVMTypeIsSized, LLVMVectorType,
 };
 use llvm_sys::prelude::{LLVMTypeRef, LLVMValueRef};
 use llvm_sys::LLVMTypeKind;
@@ -107,6 +127,14 @@ impl<'ctx> Type<'ctx> {
         unsafe { VectorType::new(LLVMVectorType(self.ty, size)) }
     }
 
+    #[llvm_versions(12.0..=latest)]
+    fn scalable_vec_type(self, size: u32) -> VectorType<'ctx> {
+        assert!(size != 0, "Vectors of size zero are not allowed.");
+        // -- https://llvm.org/docs/LangRef.html#vector-type
+
+        unsafe { VectorType::new(LLVMScalableVectorType(self.ty, size)) }
+    }
+
     #[cfg(not(feature = "experimental"))]
     fn fn_type(self, param_types: &[BasicMetadataTypeEnum<'ctx>], is_var_args: bool) -> FunctionType<'ctx> {
         let mut param_types: Vec<LLVMTypeRef> = param_types.iter().map(|val| val.as_type_ref()).collect();
@@ -181,6 +209,109 @@ impl<'ctx> Type<'ctx> {
     pub fn get_element_type(self) -> AnyTypeEnum<'ctx> {
         unsafe { AnyTypeEnum::new(LLVMGetElementType(self.ty)) }
     }
+
+    /// Encodes the structure of this type, leaving out the names of struct types.
+    fn structural_encoding(self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        unsafe { encode_structure(self.ty, &mut Vec::new(), &mut bytes) };
+
+        bytes
+    }
+}
+
+/// Appends an encoding of the structure of `ty` to `bytes`. Struct types which are already
+/// being encoded further up are referred to by their depth in `structs`, so that recursive
+/// types terminate.
+unsafe fn encode_structure(ty: LLVMTypeRef, structs: &mut Vec<LLVMTypeRef>, bytes: &mut Vec<u8>) {
+    let encode_elements = |tag: u8, len: u64, structs: &mut Vec<LLVMTypeRef>, bytes: &mut Vec<u8>| {
+        bytes.push(tag);
+        bytes.extend_from_slice(&len.to_le_bytes());
+        encode_structure(LLVMGetElementType(ty), structs, bytes);
+    };
+
+    match LLVMGetTypeKind(ty) {
+        LLVMTypeKind::LLVMVoidTypeKind => bytes.push(0),
+        LLVMTypeKind::LLVMHalfTypeKind => bytes.push(1),
+        #[cfg(any(
+            feature = "llvm11-0",
+            feature = "llvm12-0",
+            feature = "llvm13-0",
+            feature = "llvm14-0"
+        ))]
+        LLVMTypeKind::LLVMBFloatTypeKind => bytes.push(2),
+        LLVMTypeKind::LLVMFloatTypeKind => bytes.push(3),
+        LLVMTypeKind::LLVMDoubleTypeKind => bytes.push(4),
+        LLVMTypeKind::LLVMX86_FP80TypeKind => bytes.push(5),
+        LLVMTypeKind::LLVMFP128TypeKind => bytes.push(6),
+        LLVMTypeKind::LLVMPPC_FP128TypeKind => bytes.push(7),
+        LLVMTypeKind::LLVMLabelTypeKind => bytes.push(8),
+        LLVMTypeKind::LLVMIntegerTypeKind => {
+            bytes.push(9);
+            bytes.extend_from_slice(&LLVMGetIntTypeWidth(ty).to_le_bytes());
+        },
+        LLVMTypeKind::LLVMFunctionTypeKind => {
+            let count = LLVMCountParamTypes(ty);
+            let mut param_types = vec![std::ptr::null_mut(); count as usize];
+
+            LLVMGetParamTypes(ty, param_types.as_mut_ptr());
+
+            bytes.push(10);
+            bytes.push(LLVMIsFunctionVarArg(ty) as u8);
+            bytes.extend_from_slice(&count.to_le_bytes());
+            encode_structure(LLVMGetReturnType(ty), structs, bytes);
+
+            for param_type in param_types {
+                encode_structure(param_type, structs, bytes);
+            }
+        },
+        LLVMTypeKind::LLVMStructTypeKind => {
+            if let Some(depth) = structs.iter().position(|&struct_type| struct_type == ty) {
+                bytes.push(11);
+                bytes.extend_from_slice(&(depth as u32).to_le_bytes());
+
+                return;
+            }
+
+            let count = LLVMCountStructElementTypes(ty);
+            let mut field_types = vec![std::ptr::null_mut(); count as usize];
+
+            LLVMGetStructElementTypes(ty, field_types.as_mut_ptr());
+
+            bytes.push(12);
+            bytes.push(LLVMIsPackedStruct(ty) as u8);
+            bytes.push(LLVMIsOpaqueStruct(ty) as u8);
+            bytes.extend_from_slice(&count.to_le_bytes());
+            structs.push(ty);
+
+            for field_type in field_types {
+                encode_structure(field_type, structs, bytes);
+            }
+
+            structs.pop();
+        },
+        LLVMTypeKind::LLVMArrayTypeKind => encode_elements(13, LLVMGetArrayLength(ty).into(), structs, bytes),
+        // Opaque pointers have no element type, so only their address space can be encoded.
+        LLVMTypeKind::LLVMPointerTypeKind if LLVMGetElementType(ty).is_null() => {
+            bytes.push(21);
+            bytes.extend_from_slice(&LLVMGetPointerAddressSpace(ty).to_le_bytes());
+        },
+        LLVMTypeKind::LLVMPointerTypeKind => encode_elements(14, LLVMGetPointerAddressSpace(ty).into(), structs, bytes),
+        LLVMTypeKind::LLVMVectorTypeKind => encode_elements(15, LLVMGetVectorSize(ty).into(), structs, bytes),
+        #[cfg(any(
+            feature = "llvm11-0",
+            feature = "llvm12-0",
+            feature = "llvm13-0",
+            feature = "llvm14-0"
+        ))]
+        LLVMTypeKind::LLVMScalableVectorTypeKind => encode_elements(16, LLVMGetVectorSize(ty).into(), structs, bytes),
+        LLVMTypeKind::LLVMMetadataTypeKind => bytes.push(17),
+        LLVMTypeKind::LLVMX86_MMXTypeKind => bytes.push(18),
+        #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7")))]
+        LLVMTypeKind::LLVMTokenTypeKind => bytes.push(19),
+        #[cfg(any(feature = "llvm12-0", feature = "llvm13-0", feature = "llvm14-0"))]
+        LLVMTypeKind::LLVMX86_AMXTypeKind => bytes.push(20),
+    }
 }
 
 impl fmt::Debug for Type<'_> {
@@ -239,6 +239,29 @@ impl<'ctx> PointerType<'ctx> {
         self.ptr_type.vec_type(size)
     }
 
+    /// Creates a scalable `VectorType` with this `PointerType` for its element type. Its actual
+    /// number of elements is `size` times a target specific constant called `vscale`,
+    /// which is only known at runtime.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::AddressSpace;
+    ///
+    /// let context = Context::create();
+    /// let f32_ptr_type = context.f32_type().ptr_type(AddressSpace::Generic);
+    /// let f32_ptr_scalable_vector_type = f32_ptr_type.scalable_vec_type(4);
+    ///
+    /// assert!(f32_ptr_scalable_vector_type.is_scalable());
+    /// assert_eq!(f32_ptr_scalable_vector_type.get_size(), 4);
+    /// assert_eq!(f32_ptr_scalable_vector_type.get_element_type().into_pointer_type(), f32_ptr_type);
+    /// ```
+    #[llvm_versions(12.0..=latest)]
+    pub fn scalable_vec_type(self, size: u32) -> VectorType<'ctx> {
+        self.ptr_type.scalable_vec_type(size)
+    }
+
     // SubType: PointerrType<BT> -> BT?
     /// Gets the element type of this `PointerType`.
     ///
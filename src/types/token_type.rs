@@ -0,0 +1,81 @@
+use llvm_sys::core::LLVMConstNull;
+use llvm_sys::prelude::LLVMTypeRef;
+
+use crate::context::ContextRef;
+use crate::types::enums::BasicMetadataTypeEnum;
+use crate::types::traits::AsTypeRef;
+use crate::types::{FunctionType, Type};
+use crate::values::TokenValue;
+
+/// A `TokenType` is the type of values which must not be inspected or duplicated,
+/// such as the ones produced by coroutine and exception handling intrinsics.
+/// They may only be passed around as function arguments and return values.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TokenType<'ctx> {
+    token_type: Type<'ctx>,
+}
+
+impl<'ctx> TokenType<'ctx> {
+    pub(crate) unsafe fn new(token_type: LLVMTypeRef) -> Self {
+        assert!(!token_type.is_null());
+
+        TokenType {
+            token_type: Type::new(token_type),
+        }
+    }
+
+    /// Creates the `none` constant of this `TokenType`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::values::AnyValue;
+    ///
+    /// let context = Context::create();
+    /// let token_type = context.token_type();
+    /// let token_none = token_type.const_none();
+    ///
+    /// assert_eq!(token_none.print_to_string().to_string(), "token none");
+    /// ```
+    pub fn const_none(self) -> TokenValue<'ctx> {
+        unsafe { TokenValue::new(LLVMConstNull(self.as_type_ref())) }
+    }
+
+    /// Creates a `FunctionType` with this `TokenType` for its return type.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let token_type = context.token_type();
+    /// let fn_type = token_type.fn_type(&[], false);
+    /// ```
+    pub fn fn_type(self, param_types: &[BasicMetadataTypeEnum<'ctx>], is_var_args: bool) -> FunctionType<'ctx> {
+        self.token_type.fn_type(param_types, is_var_args)
+    }
+
+    /// Gets a reference to the `Context` this `TokenType` was created in.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let token_type = context.token_type();
+    ///
+    /// assert_eq!(*token_type.get_context(), context);
+    /// ```
+    pub fn get_context(self) -> ContextRef<'ctx> {
+        self.token_type.get_context()
+    }
+}
+
+impl AsTypeRef for TokenType<'_> {
+    fn as_type_ref(&self) -> LLVMTypeRef {
+        self.token_type.ty
+    }
+}
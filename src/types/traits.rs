@@ -1,11 +1,19 @@
 use llvm_sys::prelude::LLVMTypeRef;
 
-use std::fmt::Debug;
+use std::convert::TryFrom;
+use std::fmt::{self, Debug};
 
 use crate::support::LLVMString;
 use crate::types::enums::{AnyTypeEnum, BasicMetadataTypeEnum, BasicTypeEnum};
-use crate::types::{ArrayType, FloatType, FunctionType, IntType, PointerType, StructType, Type, VectorType, VoidType};
-use crate::values::{FloatMathValue, FloatValue, IntMathValue, IntValue, PointerMathValue, PointerValue, VectorValue};
+use crate::types::MetadataType;
+use crate::types::{
+    ArrayType, FloatType, FunctionType, IntType, LabelType, PointerType, StructType, TokenType, Type, VectorType,
+    VoidType, X86AMXType,
+};
+use crate::values::{
+    ArrayValue, BasicValue, BasicValueEnum, FloatMathValue, FloatValue, IntMathValue, IntValue, PointerMathValue,
+    PointerValue, StructValue, VectorValue,
+};
 use crate::AddressSpace;
 
 // This is an ugly privacy hack so that Type can stay private to this module
@@ -25,6 +33,20 @@ macro_rules! trait_type_set {
     );
 }
 
+macro_rules! display_type_set {
+    ($($args:ident),*) => (
+        $(
+            impl fmt::Display for $args<'_> {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    let llvm_type = unsafe { Type::new(self.as_type_ref()).print_to_string() };
+
+                    write!(f, "{}", llvm_type.to_string_lossy())
+                }
+            }
+        )*
+    );
+}
+
 /// Represents any LLVM type.
 pub trait AnyType<'ctx>: AsTypeRef + Debug {
     /// Returns an `AnyTypeEnum` that represents the current type.
@@ -36,6 +58,64 @@ pub trait AnyType<'ctx>: AsTypeRef + Debug {
     fn print_to_string(&self) -> LLVMString {
         unsafe { Type::new(self.as_type_ref()).print_to_string() }
     }
+
+    /// Determines whether or not this type has the same structure as `other`, ignoring the names
+    /// of any struct types within them. Recursive struct types are only considered equal if they
+    /// refer back to their enclosing structs in the same places.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::types::AnyType;
+    ///
+    /// let context = Context::create();
+    /// let i32_type = context.i32_type();
+    /// let named_struct_type = context.opaque_struct_type("point");
+    ///
+    /// named_struct_type.set_body(&[i32_type.into(), i32_type.into()], false);
+    ///
+    /// let literal_struct_type = context.struct_type(&[i32_type.into(), i32_type.into()], false);
+    ///
+    /// assert_ne!(named_struct_type, literal_struct_type);
+    /// assert!(named_struct_type.is_structurally_equal(&literal_struct_type));
+    /// ```
+    fn is_structurally_equal(&self, other: &dyn AnyType<'ctx>) -> bool {
+        if self.as_type_ref() == other.as_type_ref() {
+            return true;
+        }
+
+        unsafe {
+            Type::new(self.as_type_ref()).structural_encoding() == Type::new(other.as_type_ref()).structural_encoding()
+        }
+    }
+
+    /// Gets a hash of the structure of this type, which agrees with `is_structurally_equal`.
+    /// It does not depend on the `Context`, the process or the platform, so it may be persisted.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::types::AnyType;
+    ///
+    /// let context = Context::create();
+    /// let other_context = Context::create();
+    /// let struct_type = context.struct_type(&[context.i8_type().into()], false);
+    /// let other_struct_type = other_context.opaque_struct_type("my_struct");
+    ///
+    /// other_struct_type.set_body(&[other_context.i8_type().into()], false);
+    ///
+    /// assert_eq!(struct_type.structural_hash(), other_struct_type.structural_hash());
+    /// ```
+    fn structural_hash(&self) -> u64 {
+        // 64 bit FNV-1a, which unlike std's hashers is guaranteed never to change
+        let bytes = unsafe { Type::new(self.as_type_ref()).structural_encoding() };
+
+        bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+    }
 }
 
 /// Represents a basic LLVM type, that may be used in functions and struct definitions.
@@ -160,8 +240,45 @@ pub trait PointerMathType<'ctx>: BasicType<'ctx> {
     type PtrConvType: IntMathType<'ctx>;
 }
 
-trait_type_set! {AnyType: AnyTypeEnum, BasicTypeEnum, IntType, FunctionType, FloatType, PointerType, StructType, ArrayType, VoidType, VectorType}
+/// Represents a `BasicType` which may be pointed to by a `TypedPointerValue`, and the
+/// value which is produced when loading through such a pointer.
+///
+/// [`TypedPointerValue`]: crate::values::TypedPointerValue
+pub trait PointeeType<'ctx>: BasicType<'ctx> + Copy {
+    /// The value instance of this type.
+    type ValueType: BasicValue<'ctx> + TryFrom<BasicValueEnum<'ctx>>;
+}
+
+trait_type_set! {AnyType: AnyTypeEnum, BasicTypeEnum, IntType, FunctionType, FloatType, PointerType, StructType, ArrayType, VoidType, VectorType, TokenType, LabelType, X86AMXType}
 trait_type_set! {BasicType: BasicTypeEnum, IntType, FloatType, PointerType, StructType, ArrayType, VectorType}
+display_type_set! {AnyTypeEnum, BasicTypeEnum, BasicMetadataTypeEnum, IntType, FunctionType, FloatType, PointerType, StructType, ArrayType, VoidType, VectorType, MetadataType, TokenType, LabelType, X86AMXType}
+
+impl BasicMetadataTypeEnum<'_> {
+    /// Prints the definition of this type to a `LLVMString`.
+    pub fn print_to_string(self) -> LLVMString {
+        unsafe { Type::new(self.as_type_ref()).print_to_string() }
+    }
+}
+
+macro_rules! pointee_type_set {
+    ($(($type_:ident => $value_type:ident)),*) => (
+        $(
+            impl<'ctx> PointeeType<'ctx> for $type_<'ctx> {
+                type ValueType = $value_type<'ctx>;
+            }
+        )*
+    );
+}
+
+pointee_type_set! {
+    (BasicTypeEnum => BasicValueEnum),
+    (IntType => IntValue),
+    (FloatType => FloatValue),
+    (PointerType => PointerValue),
+    (StructType => StructValue),
+    (ArrayType => ArrayValue),
+    (VectorType => VectorValue)
+}
 
 impl<'ctx> IntMathType<'ctx> for IntType<'ctx> {
     type ValueType = IntValue<'ctx>;
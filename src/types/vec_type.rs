@@ -1,5 +1,9 @@
+#[llvm_versions(11.0..=latest)]
+use llvm_sys::core::LLVMGetTypeKind;
 use llvm_sys::core::{LLVMConstArray, LLVMConstVector, LLVMGetVectorSize};
 use llvm_sys::prelude::{LLVMTypeRef, LLVMValueRef};
+#[llvm_versions(11.0..=latest)]
+use llvm_sys::LLVMTypeKind;
 
 use crate::context::ContextRef;
 use crate::types::enums::BasicMetadataTypeEnum;
@@ -27,6 +31,9 @@ impl<'ctx> VectorType<'ctx> {
     // like VectorType<ArrayType<StructType<Opaque>>>?
     /// Gets the size of this `VectorType`. Value may vary depending on the target architecture.
     ///
+    /// The size of a scalable `VectorType` is a multiple of `vscale`, so it will not fold
+    /// to a plain integer constant, even once a target's data layout is known.
+    ///
     /// # Example
     ///
     /// ```no_run
@@ -57,7 +64,8 @@ impl<'ctx> VectorType<'ctx> {
         self.vec_type.get_alignment()
     }
 
-    /// Gets the size of this `VectorType`.
+    /// Gets the size of this `VectorType`. For a scalable `VectorType`, this is
+    /// the minimum number of elements, which gets multiplied by `vscale`.
     ///
     /// # Example
     ///
@@ -75,6 +83,25 @@ impl<'ctx> VectorType<'ctx> {
         unsafe { LLVMGetVectorSize(self.as_type_ref()) }
     }
 
+    /// Determines whether or not this `VectorType` is scalable, meaning its number of
+    /// elements is a multiple of the runtime constant `vscale`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let f32_type = context.f32_type();
+    ///
+    /// assert!(!f32_type.vec_type(4).is_scalable());
+    /// assert!(f32_type.scalable_vec_type(4).is_scalable());
+    /// ```
+    #[llvm_versions(11.0..=latest)]
+    pub fn is_scalable(self) -> bool {
+        unsafe { LLVMGetTypeKind(self.as_type_ref()) == LLVMTypeKind::LLVMScalableVectorTypeKind }
+    }
+
     // REVIEW:
     // TypeSafety v2 (GH Issue #8) could help here by constraining
     // sub-types to be the same across the board. For now, we could
@@ -0,0 +1,60 @@
+use llvm_sys::prelude::LLVMTypeRef;
+
+use crate::context::ContextRef;
+use crate::types::enums::BasicMetadataTypeEnum;
+use crate::types::traits::AsTypeRef;
+use crate::types::{FunctionType, Type};
+
+/// An `X86AMXType` is the type of a tile held in the AMX registers of x86 processors.
+/// Its values are only produced and consumed by AMX intrinsics.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct X86AMXType<'ctx> {
+    x86_amx_type: Type<'ctx>,
+}
+
+impl<'ctx> X86AMXType<'ctx> {
+    pub(crate) unsafe fn new(x86_amx_type: LLVMTypeRef) -> Self {
+        assert!(!x86_amx_type.is_null());
+
+        X86AMXType {
+            x86_amx_type: Type::new(x86_amx_type),
+        }
+    }
+
+    /// Creates a `FunctionType` with this `X86AMXType` for its return type.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let x86_amx_type = context.x86_amx_type();
+    /// let fn_type = x86_amx_type.fn_type(&[], false);
+    /// ```
+    pub fn fn_type(self, param_types: &[BasicMetadataTypeEnum<'ctx>], is_var_args: bool) -> FunctionType<'ctx> {
+        self.x86_amx_type.fn_type(param_types, is_var_args)
+    }
+
+    /// Gets a reference to the `Context` this `X86AMXType` was created in.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let x86_amx_type = context.x86_amx_type();
+    ///
+    /// assert_eq!(*x86_amx_type.get_context(), context);
+    /// ```
+    pub fn get_context(self) -> ContextRef<'ctx> {
+        self.x86_amx_type.get_context()
+    }
+}
+
+impl AsTypeRef for X86AMXType<'_> {
+    fn as_type_ref(&self) -> LLVMTypeRef {
+        self.x86_amx_type.ty
+    }
+}
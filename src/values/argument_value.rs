@@ -0,0 +1,231 @@
+use llvm_sys::core::{LLVMGetParamParent, LLVMIsAArgument, LLVMSetParamAlignment};
+use llvm_sys::prelude::LLVMValueRef;
+
+use std::convert::TryFrom;
+use std::ffi::CStr;
+
+#[llvm_versions(3.9..=latest)]
+use crate::attributes::{Attribute, AttributeLoc};
+use crate::types::BasicTypeEnum;
+use crate::values::traits::AsValueRef;
+use crate::values::{AnyValue, AnyValueEnum, BasicValue, BasicValueEnum, FunctionValue, Value};
+
+/// A parameter of a `FunctionValue`. Unlike the `BasicValueEnum` given by `FunctionValue::get_params`,
+/// an `ArgumentValue` knows which function it belongs to and at which position, which gives access
+/// to its attributes and alignment.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::convert::TryFrom;
+///
+/// use inkwell::context::Context;
+/// use inkwell::values::{ArgumentValue, BasicValueEnum};
+///
+/// let context = Context::create();
+/// let module = context.create_module("args");
+/// let i32_type = context.i32_type();
+/// let fn_type = i32_type.fn_type(&[i32_type.into(), i32_type.into()], false);
+/// let function = module.add_function("second", fn_type, None);
+/// let param = function.get_nth_param(1).unwrap();
+/// let argument = ArgumentValue::try_from(param).unwrap();
+///
+/// assert_eq!(argument.get_parent_function(), function);
+/// assert_eq!(argument.get_index(), 1);
+/// assert!(ArgumentValue::try_from(BasicValueEnum::from(i32_type.const_zero())).is_err());
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct ArgumentValue<'ctx> {
+    argument_value: Value<'ctx>,
+}
+
+impl<'ctx> ArgumentValue<'ctx> {
+    pub(crate) unsafe fn new(value: LLVMValueRef) -> Self {
+        assert!(!value.is_null());
+
+        ArgumentValue {
+            argument_value: Value::new(value),
+        }
+    }
+
+    /// Gets the name of an `ArgumentValue`.
+    pub fn get_name(&self) -> &CStr {
+        self.argument_value.get_name()
+    }
+
+    /// Sets the name of an `ArgumentValue`.
+    pub fn set_name(self, name: &str) {
+        self.argument_value.set_name(name)
+    }
+
+    /// Gets the type of this `ArgumentValue`.
+    pub fn get_type(self) -> BasicTypeEnum<'ctx> {
+        unsafe { BasicTypeEnum::new(self.argument_value.get_type()) }
+    }
+
+    /// Gets the `FunctionValue` this argument is a parameter of.
+    pub fn get_parent_function(self) -> FunctionValue<'ctx> {
+        unsafe { FunctionValue::new(LLVMGetParamParent(self.as_value_ref())).expect("Argument should have a parent") }
+    }
+
+    /// Gets the position of this argument in its function's parameter list, starting at zero.
+    pub fn get_index(self) -> u32 {
+        // The C API has no way to ask an argument for its number directly
+        self.get_parent_function()
+            .get_param_iter()
+            .position(|param| param.as_value_ref() == self.as_value_ref())
+            .expect("Argument should be a parameter of its parent") as u32
+    }
+
+    /// Gets this argument as a `BasicValueEnum`, as used by the rest of the API.
+    pub fn as_basic_value_enum(self) -> BasicValueEnum<'ctx> {
+        unsafe { BasicValueEnum::new(self.as_value_ref()) }
+    }
+
+    /// Adds an `Attribute` to this argument.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::attributes::{Attribute, AttributeLoc};
+    /// use inkwell::context::Context;
+    /// use inkwell::AddressSpace;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("args");
+    /// let i8_ptr_type = context.i8_type().ptr_type(AddressSpace::Generic);
+    /// let fn_type = context.void_type().fn_type(&[i8_ptr_type.into()], false);
+    /// let function = module.add_function("take_ptr", fn_type, None);
+    /// let argument = function.get_nth_argument(0).unwrap();
+    /// let nonnull = context.create_enum_attribute(Attribute::get_named_enum_kind_id("nonnull"), 0);
+    ///
+    /// argument.add_attribute(nonnull);
+    ///
+    /// assert_eq!(argument.attributes(), vec![nonnull]);
+    /// assert_eq!(function.attributes(AttributeLoc::Param(0)), vec![nonnull]);
+    /// ```
+    #[llvm_versions(3.9..=latest)]
+    pub fn add_attribute(self, attribute: Attribute) {
+        self.get_parent_function()
+            .add_attribute(self.get_attribute_loc(), attribute)
+    }
+
+    /// Counts the attributes of this argument.
+    #[llvm_versions(3.9..=latest)]
+    pub fn count_attributes(self) -> u32 {
+        self.get_parent_function().count_attributes(self.get_attribute_loc())
+    }
+
+    /// Gets all the attributes of this argument.
+    #[llvm_versions(3.9..=latest)]
+    pub fn attributes(self) -> Vec<Attribute> {
+        self.get_parent_function().attributes(self.get_attribute_loc())
+    }
+
+    /// Gets the enum `Attribute` of this argument with the given kind id, if any.
+    #[llvm_versions(3.9..=latest)]
+    pub fn get_enum_attribute(self, kind_id: u32) -> Option<Attribute> {
+        self.get_parent_function()
+            .get_enum_attribute(self.get_attribute_loc(), kind_id)
+    }
+
+    /// Gets the string `Attribute` of this argument with the given key, if any.
+    #[llvm_versions(3.9..=latest)]
+    pub fn get_string_attribute(self, key: &str) -> Option<Attribute> {
+        self.get_parent_function()
+            .get_string_attribute(self.get_attribute_loc(), key)
+    }
+
+    /// Removes the enum `Attribute` of this argument with the given kind id.
+    #[llvm_versions(3.9..=latest)]
+    pub fn remove_enum_attribute(self, kind_id: u32) {
+        self.get_parent_function()
+            .remove_enum_attribute(self.get_attribute_loc(), kind_id)
+    }
+
+    /// Removes the string `Attribute` of this argument with the given key.
+    #[llvm_versions(3.9..=latest)]
+    pub fn remove_string_attribute(self, key: &str) {
+        self.get_parent_function()
+            .remove_string_attribute(self.get_attribute_loc(), key)
+    }
+
+    /// Gets the alignment in bytes given to this argument by an `align` attribute, if any.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::AddressSpace;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("args");
+    /// let i64_ptr_type = context.i64_type().ptr_type(AddressSpace::Generic);
+    /// let fn_type = context.void_type().fn_type(&[i64_ptr_type.into()], false);
+    /// let function = module.add_function("take_ptr", fn_type, None);
+    /// let argument = function.get_nth_argument(0).unwrap();
+    ///
+    /// assert_eq!(argument.get_alignment(), None);
+    ///
+    /// argument.set_alignment(8);
+    ///
+    /// assert_eq!(argument.get_alignment(), Some(8));
+    /// ```
+    #[llvm_versions(3.9..=latest)]
+    pub fn get_alignment(self) -> Option<u32> {
+        let align = self.get_enum_attribute(Attribute::get_named_enum_kind_id("align"))?;
+
+        Some(align.get_enum_value() as u32)
+    }
+
+    /// Sets the alignment in bytes of this argument, which must be a pointer.
+    pub fn set_alignment(self, alignment: u32) {
+        unsafe { LLVMSetParamAlignment(self.as_value_ref(), alignment) }
+    }
+
+    #[llvm_versions(3.9..=latest)]
+    fn get_attribute_loc(self) -> AttributeLoc {
+        AttributeLoc::Param(self.get_index())
+    }
+}
+
+impl AsValueRef for ArgumentValue<'_> {
+    fn as_value_ref(&self) -> LLVMValueRef {
+        self.argument_value.value
+    }
+}
+
+impl<'ctx> AnyValue<'ctx> for ArgumentValue<'ctx> {}
+impl<'ctx> BasicValue<'ctx> for ArgumentValue<'ctx> {}
+
+impl<'ctx> From<ArgumentValue<'ctx>> for BasicValueEnum<'ctx> {
+    fn from(value: ArgumentValue<'ctx>) -> Self {
+        value.as_basic_value_enum()
+    }
+}
+
+impl<'ctx> From<ArgumentValue<'ctx>> for AnyValueEnum<'ctx> {
+    fn from(value: ArgumentValue<'ctx>) -> Self {
+        value.as_any_value_enum()
+    }
+}
+
+impl<'ctx> TryFrom<BasicValueEnum<'ctx>> for ArgumentValue<'ctx> {
+    type Error = ();
+
+    fn try_from(value: BasicValueEnum<'ctx>) -> Result<Self, Self::Error> {
+        if unsafe { LLVMIsAArgument(value.as_value_ref()) }.is_null() {
+            return Err(());
+        }
+
+        unsafe { Ok(ArgumentValue::new(value.as_value_ref())) }
+    }
+}
+
+impl<'ctx> TryFrom<AnyValueEnum<'ctx>> for ArgumentValue<'ctx> {
+    type Error = ();
+
+    fn try_from(value: AnyValueEnum<'ctx>) -> Result<Self, Self::Error> {
+        ArgumentValue::try_from(BasicValueEnum::try_from(value)?)
+    }
+}
@@ -1,12 +1,20 @@
-use llvm_sys::core::{LLVMIsAConstantArray, LLVMIsAConstantDataArray};
-use llvm_sys::prelude::LLVMValueRef;
+use llvm_sys::core::{
+    LLVMConstBitCast, LLVMConstInt, LLVMConstStringInContext, LLVMDoubleTypeInContext, LLVMFloatTypeInContext,
+    LLVMGetAsString, LLVMGetElementAsConstant, LLVMGetElementType, LLVMGetIntTypeWidth, LLVMGetTypeContext,
+    LLVMGetTypeKind, LLVMIntTypeInContext, LLVMIsAConstantArray, LLVMIsAConstantDataArray, LLVMTypeOf,
+};
+use llvm_sys::prelude::{LLVMContextRef, LLVMTypeRef, LLVMValueRef};
+use llvm_sys::LLVMTypeKind;
 
 use std::ffi::CStr;
 use std::fmt;
+use std::mem::{align_of, size_of};
+use std::slice;
 
+use crate::context::Context;
 use crate::types::ArrayType;
 use crate::values::traits::{AnyValue, AsValueRef};
-use crate::values::{InstructionValue, Value};
+use crate::values::{BasicValueEnum, InstructionValue, Value};
 
 /// An `ArrayValue` is a block of contiguous constants or variables.
 #[derive(PartialEq, Eq, Clone, Copy, Hash)]
@@ -29,6 +37,11 @@ impl<'ctx> ArrayValue<'ctx> {
         self.array_value.get_name()
     }
 
+    /// Sets the name of an `ArrayValue`. If the value is a constant, this is a noop.
+    pub fn set_name(self, name: &str) {
+        self.array_value.set_name(name)
+    }
+
     /// Gets the type of this `ArrayValue`.
     pub fn get_type(self) -> ArrayType<'ctx> {
         unsafe { ArrayType::new(self.array_value.get_type()) }
@@ -60,6 +73,99 @@ impl<'ctx> ArrayValue<'ctx> {
         self.array_value.replace_all_uses_with(other.as_value_ref())
     }
 
+    /// Creates a constant array holding a copy of `data`, which LLVM stores compactly as a
+    /// `ConstantDataArray`. Only byte slices are handed to LLVM in one piece: LLVM's C API has no
+    /// way to do the same for wider elements, so they are built one constant at a time and every
+    /// distinct element stays interned in the context. Use `Module::add_global_data_table` to
+    /// embed large tables of other element types.
+    ///
+    /// An array whose elements are all zero becomes a `zeroinitializer` rather than a
+    /// `ConstantDataArray`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::values::ArrayValue;
+    ///
+    /// let context = Context::create();
+    /// let table = ArrayValue::from_slice(&context, &[1u32, 2, 3, 5, 8]);
+    ///
+    /// assert_eq!(table.get_type(), context.i32_type().array_type(5));
+    /// assert_eq!(table.as_slice::<u32>(), Some(&[1, 2, 3, 5, 8][..]));
+    /// ```
+    pub fn from_slice<T: ConstantDataElement>(context: &'ctx Context, data: &[T]) -> Self {
+        unsafe { ArrayValue::new(T::const_data_array(context.context, data)) }
+    }
+
+    /// Gets the elements of a constant data array as a slice, without copying them. Returns `None`
+    /// if this is not a `ConstantDataArray` or if its elements are not of type `T`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::values::ArrayValue;
+    ///
+    /// let context = Context::create();
+    /// let weights = ArrayValue::from_slice(&context, &[0.5f64, 0.25]);
+    ///
+    /// assert_eq!(weights.as_slice::<f64>(), Some(&[0.5, 0.25][..]));
+    /// assert_eq!(weights.as_slice::<u64>(), None);
+    /// ```
+    pub fn as_slice<T: ConstantDataElement>(&self) -> Option<&[T]> {
+        if !self.is_const_data_array() {
+            return None;
+        }
+
+        if !T::is_element_type(unsafe { LLVMGetElementType(LLVMTypeOf(self.as_value_ref())) }) {
+            return None;
+        }
+
+        let mut len = 0;
+        let ptr = unsafe { LLVMGetAsString(self.as_value_ref(), &mut len) };
+
+        // LLVM keeps the data suitably aligned, but nothing promises it
+        if ptr.align_offset(align_of::<T>()) != 0 {
+            return None;
+        }
+
+        unsafe { Some(slice::from_raw_parts(ptr as *const T, len / size_of::<T>())) }
+    }
+
+    /// Gets the element at `index` of a constant data array as a constant. Returns `None` if
+    /// this is not a `ConstantDataArray` or if `index` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::values::ArrayValue;
+    ///
+    /// let context = Context::create();
+    /// let table = ArrayValue::from_slice(&context, &[7u16, 9]);
+    /// let nine = table.get_element_as_constant(1).unwrap().into_int_value();
+    ///
+    /// assert_eq!(nine.get_zero_extended_constant(), Some(9));
+    /// assert!(table.get_element_as_constant(2).is_none());
+    /// ```
+    pub fn get_element_as_constant(self, index: u32) -> Option<BasicValueEnum<'ctx>> {
+        if !self.is_const_data_array() || index >= self.get_type().len() {
+            return None;
+        }
+
+        unsafe {
+            Some(BasicValueEnum::new(LLVMGetElementAsConstant(
+                self.as_value_ref(),
+                index,
+            )))
+        }
+    }
+
+    fn is_const_data_array(self) -> bool {
+        unsafe { !LLVMIsAConstantDataArray(self.as_value_ref()).is_null() }
+    }
+
     /// Determines whether or not an `ArrayValue` is a constant.
     ///
     /// # Example
@@ -93,7 +199,7 @@ impl fmt::Debug for ArrayValue<'_> {
         let is_const = self.is_const();
         let is_null = self.is_null();
         let is_const_array = unsafe { !LLVMIsAConstantArray(self.as_value_ref()).is_null() };
-        let is_const_data_array = unsafe { !LLVMIsAConstantDataArray(self.as_value_ref()).is_null() };
+        let is_const_data_array = self.is_const_data_array();
 
         f.debug_struct("ArrayValue")
             .field("name", &name)
@@ -107,3 +213,111 @@ impl fmt::Debug for ArrayValue<'_> {
             .finish()
     }
 }
+
+/// A Rust scalar which can be the element of a `ConstantDataArray`, as created by
+/// `ArrayValue::from_slice` and read back by `ArrayValue::as_slice`.
+pub trait ConstantDataElement: private::SealedConstantDataElement {}
+
+mod private {
+    use llvm_sys::core::LLVMConstArray;
+    use llvm_sys::prelude::{LLVMContextRef, LLVMTypeRef, LLVMValueRef};
+
+    /// A sealed trait which ensures nobody outside this crate can implement
+    /// `ConstantDataElement`.
+    ///
+    /// See https://rust-lang-nursery.github.io/api-guidelines/future-proofing.html
+    pub trait SealedConstantDataElement: Copy + Sized {
+        fn element_type(context: LLVMContextRef) -> LLVMTypeRef;
+
+        fn is_element_type(ty: LLVMTypeRef) -> bool;
+
+        fn const_element(self, ty: LLVMTypeRef) -> LLVMValueRef;
+
+        unsafe fn const_data_array(context: LLVMContextRef, data: &[Self]) -> LLVMValueRef {
+            let ty = Self::element_type(context);
+            let mut values: Vec<LLVMValueRef> = data.iter().map(|value| value.const_element(ty)).collect();
+
+            // LLVM turns arrays of simple constants into a ConstantDataArray itself. This is the
+            // slow path, see `Module::add_global_data_table` for large tables
+            LLVMConstArray(ty, values.as_mut_ptr(), values.len() as u32)
+        }
+    }
+}
+
+impl<T: private::SealedConstantDataElement> ConstantDataElement for T {}
+
+macro_rules! int_data_element {
+    ($($ty:ty => $bits:literal $({ $($const_data_array:tt)* })?),*) => {
+        $(
+            impl private::SealedConstantDataElement for $ty {
+                fn element_type(context: LLVMContextRef) -> LLVMTypeRef {
+                    unsafe { LLVMIntTypeInContext(context, $bits) }
+                }
+
+                fn is_element_type(ty: LLVMTypeRef) -> bool {
+                    unsafe { LLVMGetTypeKind(ty) == LLVMTypeKind::LLVMIntegerTypeKind && LLVMGetIntTypeWidth(ty) == $bits }
+                }
+
+                fn const_element(self, ty: LLVMTypeRef) -> LLVMValueRef {
+                    unsafe { LLVMConstInt(ty, u64::from(self), 0) }
+                }
+
+                $($($const_data_array)*)?
+            }
+        )*
+    };
+}
+
+int_data_element!(
+    // Bytes can be handed over all at once, without creating a constant per element
+    u8 => 8 {
+        unsafe fn const_data_array(context: LLVMContextRef, data: &[Self]) -> LLVMValueRef {
+            LLVMConstStringInContext(context, data.as_ptr() as *const ::libc::c_char, data.len() as u32, 1)
+        }
+    },
+    u16 => 16,
+    u32 => 32,
+    u64 => 64
+);
+
+impl private::SealedConstantDataElement for f32 {
+    fn element_type(context: LLVMContextRef) -> LLVMTypeRef {
+        unsafe { LLVMFloatTypeInContext(context) }
+    }
+
+    fn is_element_type(ty: LLVMTypeRef) -> bool {
+        unsafe { LLVMGetTypeKind(ty) == LLVMTypeKind::LLVMFloatTypeKind }
+    }
+
+    // Going through an `f64` would quiet signaling NaNs, so the bits are cast instead
+    fn const_element(self, ty: LLVMTypeRef) -> LLVMValueRef {
+        unsafe {
+            let bits = LLVMConstInt(
+                LLVMIntTypeInContext(LLVMGetTypeContext(ty), 32),
+                u64::from(self.to_bits()),
+                0,
+            );
+
+            LLVMConstBitCast(bits, ty)
+        }
+    }
+}
+
+impl private::SealedConstantDataElement for f64 {
+    fn element_type(context: LLVMContextRef) -> LLVMTypeRef {
+        unsafe { LLVMDoubleTypeInContext(context) }
+    }
+
+    fn is_element_type(ty: LLVMTypeRef) -> bool {
+        unsafe { LLVMGetTypeKind(ty) == LLVMTypeKind::LLVMDoubleTypeKind }
+    }
+
+    // Like `f32`, the bits are cast so that NaN payloads are kept exactly
+    fn const_element(self, ty: LLVMTypeRef) -> LLVMValueRef {
+        unsafe {
+            let bits = LLVMConstInt(LLVMIntTypeInContext(LLVMGetTypeContext(ty), 64), self.to_bits(), 0);
+
+            LLVMConstBitCast(bits, ty)
+        }
+    }
+}
@@ -171,3 +171,49 @@ impl<'ctx> BasicValueUse<'ctx> {
         }
     }
 }
+
+/// An iterator over the uses of a value, created by `AnyValue::uses` or `BasicBlock::uses`.
+///
+/// The next use is looked up before the current one is yielded, so the yielded use may be
+/// rewritten (ie by `InstructionValue::set_operand`) without ending the iteration early.
+#[derive(Debug)]
+pub struct BasicValueUseIter<'ctx>(Option<BasicValueUse<'ctx>>);
+
+impl<'ctx> BasicValueUseIter<'ctx> {
+    pub(crate) fn new(first_use: Option<BasicValueUse<'ctx>>) -> Self {
+        BasicValueUseIter(first_use)
+    }
+}
+
+impl<'ctx> Iterator for BasicValueUseIter<'ctx> {
+    type Item = BasicValueUse<'ctx>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let use_ = self.0?;
+
+        self.0 = use_.get_next_use();
+
+        Some(use_)
+    }
+}
+
+/// An iterator over the users of a value, created by `AnyValue::users` or `BasicBlock::users`.
+///
+/// A user appears once for every time it uses the value, so an instruction such as `add %x, %x`
+/// is yielded twice.
+#[derive(Debug)]
+pub struct UserIter<'ctx>(BasicValueUseIter<'ctx>);
+
+impl<'ctx> UserIter<'ctx> {
+    pub(crate) fn new(first_use: Option<BasicValueUse<'ctx>>) -> Self {
+        UserIter(BasicValueUseIter::new(first_use))
+    }
+}
+
+impl<'ctx> Iterator for UserIter<'ctx> {
+    type Item = AnyValueEnum<'ctx>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(BasicValueUse::get_user)
+    }
+}
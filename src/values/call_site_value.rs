@@ -78,6 +78,8 @@ impl<'ctx> CallSiteValue<'ctx> {
     }
 
     /// Try to convert this `CallSiteValue` to a `BasicValueEnum` if not a void return type.
+    /// Calls returning a `TokenType` or `X86AMXType` are also given as an `InstructionValue`,
+    /// whose `as_any_value_enum` yields the returned `TokenValue` or `X86AMXValue`.
     ///
     /// # Example
     ///
@@ -102,6 +104,10 @@ impl<'ctx> CallSiteValue<'ctx> {
         unsafe {
             match LLVMGetTypeKind(LLVMTypeOf(self.as_value_ref())) {
                 LLVMTypeKind::LLVMVoidTypeKind => Either::Right(InstructionValue::new(self.as_value_ref())),
+                #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7")))]
+                LLVMTypeKind::LLVMTokenTypeKind => Either::Right(InstructionValue::new(self.as_value_ref())),
+                #[cfg(any(feature = "llvm12-0", feature = "llvm13-0", feature = "llvm14-0"))]
+                LLVMTypeKind::LLVMX86_AMXTypeKind => Either::Right(InstructionValue::new(self.as_value_ref())),
                 _ => Either::Left(BasicValueEnum::new(self.as_value_ref())),
             }
         }
@@ -6,7 +6,7 @@ use crate::types::{AnyTypeEnum, BasicTypeEnum};
 use crate::values::traits::AsValueRef;
 use crate::values::{
     ArrayValue, FloatValue, FunctionValue, InstructionValue, IntValue, MetadataValue, PhiValue, PointerValue,
-    StructValue, VectorValue,
+    StructValue, TokenValue, VectorValue, X86AMXValue,
 };
 
 use std::convert::TryFrom;
@@ -64,9 +64,9 @@ macro_rules! enum_value_set {
 }
 
 enum_value_set! {AggregateValueEnum: ArrayValue, StructValue}
-enum_value_set! {AnyValueEnum: ArrayValue, IntValue, FloatValue, PhiValue, FunctionValue, PointerValue, StructValue, VectorValue, InstructionValue}
+enum_value_set! {AnyValueEnum: ArrayValue, IntValue, FloatValue, PhiValue, FunctionValue, PointerValue, StructValue, VectorValue, InstructionValue, TokenValue, X86AMXValue}
 enum_value_set! {BasicValueEnum: ArrayValue, IntValue, FloatValue, PointerValue, StructValue, VectorValue}
-enum_value_set! {BasicMetadataValueEnum: ArrayValue, IntValue, FloatValue, PointerValue, StructValue, VectorValue, MetadataValue}
+enum_value_set! {BasicMetadataValueEnum: ArrayValue, IntValue, FloatValue, PointerValue, StructValue, VectorValue, MetadataValue, TokenValue, X86AMXValue}
 
 impl<'ctx> AnyValueEnum<'ctx> {
     pub(crate) unsafe fn new(value: LLVMValueRef) -> Self {
@@ -77,11 +77,25 @@ impl<'ctx> AnyValueEnum<'ctx> {
             | LLVMTypeKind::LLVMHalfTypeKind
             | LLVMTypeKind::LLVMX86_FP80TypeKind
             | LLVMTypeKind::LLVMPPC_FP128TypeKind => AnyValueEnum::FloatValue(FloatValue::new(value)),
+            #[cfg(any(
+                feature = "llvm11-0",
+                feature = "llvm12-0",
+                feature = "llvm13-0",
+                feature = "llvm14-0"
+            ))]
+            LLVMTypeKind::LLVMBFloatTypeKind => AnyValueEnum::FloatValue(FloatValue::new(value)),
             LLVMTypeKind::LLVMIntegerTypeKind => AnyValueEnum::IntValue(IntValue::new(value)),
             LLVMTypeKind::LLVMStructTypeKind => AnyValueEnum::StructValue(StructValue::new(value)),
             LLVMTypeKind::LLVMPointerTypeKind => AnyValueEnum::PointerValue(PointerValue::new(value)),
             LLVMTypeKind::LLVMArrayTypeKind => AnyValueEnum::ArrayValue(ArrayValue::new(value)),
             LLVMTypeKind::LLVMVectorTypeKind => AnyValueEnum::VectorValue(VectorValue::new(value)),
+            #[cfg(any(
+                feature = "llvm11-0",
+                feature = "llvm12-0",
+                feature = "llvm13-0",
+                feature = "llvm14-0"
+            ))]
+            LLVMTypeKind::LLVMScalableVectorTypeKind => AnyValueEnum::VectorValue(VectorValue::new(value)),
             LLVMTypeKind::LLVMFunctionTypeKind => AnyValueEnum::FunctionValue(FunctionValue::new(value).unwrap()),
             LLVMTypeKind::LLVMVoidTypeKind => {
                 if LLVMIsAInstruction(value).is_null() {
@@ -89,6 +103,10 @@ impl<'ctx> AnyValueEnum<'ctx> {
                 }
                 AnyValueEnum::InstructionValue(InstructionValue::new(value))
             },
+            #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7")))]
+            LLVMTypeKind::LLVMTokenTypeKind => AnyValueEnum::TokenValue(TokenValue::new(value)),
+            #[cfg(any(feature = "llvm12-0", feature = "llvm13-0", feature = "llvm14-0"))]
+            LLVMTypeKind::LLVMX86_AMXTypeKind => AnyValueEnum::X86AMXValue(X86AMXValue::new(value)),
             LLVMTypeKind::LLVMMetadataTypeKind => panic!("Metadata values are not supported as AnyValue's."),
             _ => panic!("The given type is not supported."),
         }
@@ -98,6 +116,18 @@ impl<'ctx> AnyValueEnum<'ctx> {
         unsafe { AnyTypeEnum::new(LLVMTypeOf(self.as_value_ref())) }
     }
 
+    /// Gets the `InstructionValue` behind this value, if it is an instruction. Unlike the
+    /// `InstructionValue` variant, this also covers instructions producing a basic value.
+    pub fn as_instruction_value(&self) -> Option<InstructionValue<'ctx>> {
+        let value = self.as_value_ref();
+
+        if unsafe { LLVMIsAInstruction(value).is_null() } {
+            return None;
+        }
+
+        unsafe { Some(InstructionValue::new(value)) }
+    }
+
     pub fn is_array_value(self) -> bool {
         matches!(self, AnyValueEnum::ArrayValue(_))
     }
@@ -134,6 +164,14 @@ impl<'ctx> AnyValueEnum<'ctx> {
         matches!(self, AnyValueEnum::InstructionValue(_))
     }
 
+    pub fn is_token_value(self) -> bool {
+        matches!(self, AnyValueEnum::TokenValue(_))
+    }
+
+    pub fn is_x86_amx_value(self) -> bool {
+        matches!(self, AnyValueEnum::X86AMXValue(_))
+    }
+
     pub fn into_array_value(self) -> ArrayValue<'ctx> {
         if let AnyValueEnum::ArrayValue(v) = self {
             v
@@ -205,6 +243,22 @@ impl<'ctx> AnyValueEnum<'ctx> {
             panic!("Found {:?} but expected the InstructionValue variant", self)
         }
     }
+
+    pub fn into_token_value(self) -> TokenValue<'ctx> {
+        if let AnyValueEnum::TokenValue(v) = self {
+            v
+        } else {
+            panic!("Found {:?} but expected the TokenValue variant", self)
+        }
+    }
+
+    pub fn into_x86_amx_value(self) -> X86AMXValue<'ctx> {
+        if let AnyValueEnum::X86AMXValue(v) = self {
+            v
+        } else {
+            panic!("Found {:?} but expected the X86AMXValue variant", self)
+        }
+    }
 }
 
 impl<'ctx> BasicValueEnum<'ctx> {
@@ -216,11 +270,25 @@ impl<'ctx> BasicValueEnum<'ctx> {
             | LLVMTypeKind::LLVMHalfTypeKind
             | LLVMTypeKind::LLVMX86_FP80TypeKind
             | LLVMTypeKind::LLVMPPC_FP128TypeKind => BasicValueEnum::FloatValue(FloatValue::new(value)),
+            #[cfg(any(
+                feature = "llvm11-0",
+                feature = "llvm12-0",
+                feature = "llvm13-0",
+                feature = "llvm14-0"
+            ))]
+            LLVMTypeKind::LLVMBFloatTypeKind => BasicValueEnum::FloatValue(FloatValue::new(value)),
             LLVMTypeKind::LLVMIntegerTypeKind => BasicValueEnum::IntValue(IntValue::new(value)),
             LLVMTypeKind::LLVMStructTypeKind => BasicValueEnum::StructValue(StructValue::new(value)),
             LLVMTypeKind::LLVMPointerTypeKind => BasicValueEnum::PointerValue(PointerValue::new(value)),
             LLVMTypeKind::LLVMArrayTypeKind => BasicValueEnum::ArrayValue(ArrayValue::new(value)),
             LLVMTypeKind::LLVMVectorTypeKind => BasicValueEnum::VectorValue(VectorValue::new(value)),
+            #[cfg(any(
+                feature = "llvm11-0",
+                feature = "llvm12-0",
+                feature = "llvm13-0",
+                feature = "llvm14-0"
+            ))]
+            LLVMTypeKind::LLVMScalableVectorTypeKind => BasicValueEnum::VectorValue(VectorValue::new(value)),
             _ => unreachable!("The given type is not a basic type."),
         }
     }
@@ -345,12 +413,30 @@ impl<'ctx> BasicMetadataValueEnum<'ctx> {
             | LLVMTypeKind::LLVMHalfTypeKind
             | LLVMTypeKind::LLVMX86_FP80TypeKind
             | LLVMTypeKind::LLVMPPC_FP128TypeKind => BasicMetadataValueEnum::FloatValue(FloatValue::new(value)),
+            #[cfg(any(
+                feature = "llvm11-0",
+                feature = "llvm12-0",
+                feature = "llvm13-0",
+                feature = "llvm14-0"
+            ))]
+            LLVMTypeKind::LLVMBFloatTypeKind => BasicMetadataValueEnum::FloatValue(FloatValue::new(value)),
             LLVMTypeKind::LLVMIntegerTypeKind => BasicMetadataValueEnum::IntValue(IntValue::new(value)),
             LLVMTypeKind::LLVMStructTypeKind => BasicMetadataValueEnum::StructValue(StructValue::new(value)),
             LLVMTypeKind::LLVMPointerTypeKind => BasicMetadataValueEnum::PointerValue(PointerValue::new(value)),
             LLVMTypeKind::LLVMArrayTypeKind => BasicMetadataValueEnum::ArrayValue(ArrayValue::new(value)),
             LLVMTypeKind::LLVMVectorTypeKind => BasicMetadataValueEnum::VectorValue(VectorValue::new(value)),
+            #[cfg(any(
+                feature = "llvm11-0",
+                feature = "llvm12-0",
+                feature = "llvm13-0",
+                feature = "llvm14-0"
+            ))]
+            LLVMTypeKind::LLVMScalableVectorTypeKind => BasicMetadataValueEnum::VectorValue(VectorValue::new(value)),
             LLVMTypeKind::LLVMMetadataTypeKind => BasicMetadataValueEnum::MetadataValue(MetadataValue::new(value)),
+            #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7")))]
+            LLVMTypeKind::LLVMTokenTypeKind => BasicMetadataValueEnum::TokenValue(TokenValue::new(value)),
+            #[cfg(any(feature = "llvm12-0", feature = "llvm13-0", feature = "llvm14-0"))]
+            LLVMTypeKind::LLVMX86_AMXTypeKind => BasicMetadataValueEnum::X86AMXValue(X86AMXValue::new(value)),
             _ => unreachable!("Unsupported type"),
         }
     }
@@ -383,6 +469,14 @@ impl<'ctx> BasicMetadataValueEnum<'ctx> {
         matches!(self, BasicMetadataValueEnum::MetadataValue(_))
     }
 
+    pub fn is_token_value(self) -> bool {
+        matches!(self, BasicMetadataValueEnum::TokenValue(_))
+    }
+
+    pub fn is_x86_amx_value(self) -> bool {
+        matches!(self, BasicMetadataValueEnum::X86AMXValue(_))
+    }
+
     pub fn into_array_value(self) -> ArrayValue<'ctx> {
         if let BasicMetadataValueEnum::ArrayValue(v) = self {
             v
@@ -438,6 +532,22 @@ impl<'ctx> BasicMetadataValueEnum<'ctx> {
             panic!("Found {:?} but expected MetaData variant", self)
         }
     }
+
+    pub fn into_token_value(self) -> TokenValue<'ctx> {
+        if let BasicMetadataValueEnum::TokenValue(v) = self {
+            v
+        } else {
+            panic!("Found {:?} but expected the TokenValue variant", self)
+        }
+    }
+
+    pub fn into_x86_amx_value(self) -> X86AMXValue<'ctx> {
+        if let BasicMetadataValueEnum::X86AMXValue(v) = self {
+            v
+        } else {
+            panic!("Found {:?} but expected the X86AMXValue variant", self)
+        }
+    }
 }
 
 impl<'ctx> From<BasicValueEnum<'ctx>> for AnyValueEnum<'ctx> {
@@ -1,8 +1,10 @@
 use llvm_sys::core::{
-    LLVMConstFAdd, LLVMConstFCmp, LLVMConstFDiv, LLVMConstFMul, LLVMConstFNeg, LLVMConstFPCast, LLVMConstFPExt,
-    LLVMConstFPToSI, LLVMConstFPToUI, LLVMConstFPTrunc, LLVMConstFRem, LLVMConstFSub, LLVMConstRealGetDouble,
+    LLVMConstBitCast, LLVMConstFAdd, LLVMConstFCmp, LLVMConstFDiv, LLVMConstFMul, LLVMConstFNeg, LLVMConstFPCast,
+    LLVMConstFPExt, LLVMConstFPToSI, LLVMConstFPToUI, LLVMConstFPTrunc, LLVMConstFRem, LLVMConstFSub,
+    LLVMConstRealGetDouble, LLVMGetTypeContext, LLVMGetTypeKind, LLVMIntTypeInContext, LLVMIsAConstantFP,
 };
 use llvm_sys::prelude::LLVMValueRef;
+use llvm_sys::LLVMTypeKind;
 
 use std::ffi::CStr;
 
@@ -31,6 +33,11 @@ impl<'ctx> FloatValue<'ctx> {
         self.float_value.get_name()
     }
 
+    /// Sets the name of a `FloatValue`. If the value is a constant, this is a noop.
+    pub fn set_name(self, name: &str) {
+        self.float_value.set_name(name)
+    }
+
     pub fn get_type(self) -> FloatType<'ctx> {
         unsafe { FloatType::new(self.float_value.get_type()) }
     }
@@ -131,9 +138,8 @@ impl<'ctx> FloatValue<'ctx> {
     /// assert_eq!(f64_1_2.get_constant(), Some((1.2, false)));
     /// ```
     pub fn get_constant(self) -> Option<(f64, bool)> {
-        // Nothing bad happens as far as I can tell if we don't check if const
-        // unlike the int versions, but just doing this just in case and for consistency
-        if !self.is_const() {
+        // Constant expressions, such as unfolded casts, aren't ConstantFPs and would crash LLVM
+        if !self.is_constant_fp() {
             return None;
         }
 
@@ -143,6 +149,57 @@ impl<'ctx> FloatValue<'ctx> {
         Some((constant, lossy == 1))
     }
 
+    /// Obtains the exact bit pattern of a constant `FloatValue`, least significant word first, as
+    /// taken by `FloatType::const_float_from_bits`.
+    ///
+    /// For a `ppc_f128`, the first word is the bits of the high-order `f64`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let f32_type = context.f32_type();
+    /// let nan = f32_type.const_float_from_bits(&[0x7FC0_1234]);
+    ///
+    /// assert_eq!(nan.get_bits(), Some(vec![0x7FC0_1234]));
+    /// assert_eq!(f32_type.const_float(-2.).get_bits(), Some(vec![0xC000_0000]));
+    /// ```
+    pub fn get_bits(self) -> Option<Vec<u64>> {
+        if !self.is_constant_fp() {
+            return None;
+        }
+
+        let float_type = self.get_type();
+
+        // LLVM won't fold a `ppc_f128` into its bits without knowing the target's endianness, but
+        // prints them exactly in the `0xM` form
+        if unsafe { LLVMGetTypeKind(float_type.as_type_ref()) } == LLVMTypeKind::LLVMPPC_FP128TypeKind {
+            let ir = self.float_value.print_to_string().to_string();
+            let hex = &ir[ir.find("0xM")? + 3..];
+
+            return Some(vec![
+                u64::from_str_radix(hex.get(..16)?, 16).ok()?,
+                u64::from_str_radix(hex.get(16..32)?, 16).ok()?,
+            ]);
+        }
+
+        let bits = unsafe {
+            let int_type =
+                LLVMIntTypeInContext(LLVMGetTypeContext(float_type.as_type_ref()), float_type.get_bit_width());
+
+            IntValue::new(LLVMConstBitCast(self.as_value_ref(), int_type))
+        };
+
+        // Unfolded casts are constant expressions rather than constant ints
+        bits.get_arbitrary_precision_words()
+    }
+
+    fn is_constant_fp(self) -> bool {
+        !unsafe { LLVMIsAConstantFP(self.as_value_ref()) }.is_null()
+    }
+
     pub fn replace_all_uses_with(self, other: FloatValue<'ctx>) {
         self.float_value.replace_all_uses_with(other.as_value_ref())
     }
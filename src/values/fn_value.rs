@@ -17,21 +17,23 @@ use llvm_sys::core::{LLVMGetPersonalityFn, LLVMSetPersonalityFn};
 use llvm_sys::debuginfo::{LLVMGetSubprogram, LLVMSetSubprogram};
 use llvm_sys::prelude::{LLVMBasicBlockRef, LLVMValueRef};
 
+use std::collections::{HashMap, HashSet};
 use std::ffi::CStr;
 use std::fmt;
 use std::marker::PhantomData;
 use std::mem::forget;
+use std::vec::IntoIter;
 
 #[llvm_versions(3.9..=latest)]
 use crate::attributes::{Attribute, AttributeLoc};
-use crate::basic_block::BasicBlock;
+use crate::basic_block::{BasicBlock, InstructionIter};
 #[llvm_versions(7.0..=latest)]
 use crate::debug_info::DISubprogram;
 use crate::module::Linkage;
 use crate::support::to_c_str;
 use crate::types::{AnyType, FunctionType, PointerType};
 use crate::values::traits::{AnyValue, AsValueRef};
-use crate::values::{BasicValueEnum, GlobalValue, Value};
+use crate::values::{ArgumentValue, BasicValueEnum, GlobalValue, InstructionOpcode, InstructionValue, Value};
 
 #[derive(PartialEq, Eq, Clone, Copy, Hash)]
 pub struct FunctionValue<'ctx> {
@@ -154,6 +156,40 @@ impl<'ctx> FunctionValue<'ctx> {
             .collect()
     }
 
+    /// Iterates over the instructions of every `BasicBlock` in this function, in layout order.
+    ///
+    /// As with `BasicBlock::get_instructions`, the instruction just yielded may be erased or moved
+    /// without disturbing the iteration.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let builder = context.create_builder();
+    /// let module = context.create_module("my_mod");
+    /// let fn_type = context.void_type().fn_type(&[], false);
+    /// let fn_value = module.add_function("my_fn", fn_type, None);
+    /// let entry = context.append_basic_block(fn_value, "entry");
+    /// let exit = context.append_basic_block(fn_value, "exit");
+    ///
+    /// builder.position_at_end(entry);
+    /// builder.build_unconditional_branch(exit);
+    /// builder.position_at_end(exit);
+    /// builder.build_return(None);
+    ///
+    /// assert_eq!(fn_value.get_instructions().count(), 2);
+    /// ```
+    pub fn get_instructions(self) -> FunctionInstructionIter<'ctx> {
+        let block = self.get_first_basic_block();
+
+        FunctionInstructionIter {
+            instructions: block.map(BasicBlock::get_instructions),
+            block,
+        }
+    }
+
     pub fn get_param_iter(self) -> ParamValueIter<'ctx> {
         ParamValueIter {
             param_iter_value: self.fn_value.value,
@@ -178,6 +214,37 @@ impl<'ctx> FunctionValue<'ctx> {
         raw_vec.iter().map(|val| unsafe { BasicValueEnum::new(*val) }).collect()
     }
 
+    /// Gets the parameter at position `nth` as an `ArgumentValue`.
+    pub fn get_nth_argument(self, nth: u32) -> Option<ArgumentValue<'ctx>> {
+        if nth >= self.count_params() {
+            return None;
+        }
+
+        unsafe { Some(ArgumentValue::new(LLVMGetParam(self.as_value_ref(), nth))) }
+    }
+
+    /// Gets every parameter as an `ArgumentValue`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let i32_type = context.i32_type();
+    /// let fn_type = i32_type.fn_type(&[i32_type.into(), i32_type.into()], false);
+    /// let fn_value = module.add_function("my_fn", fn_type, None);
+    /// let indices: Vec<u32> = fn_value.get_arguments().iter().map(|arg| arg.get_index()).collect();
+    ///
+    /// assert_eq!(indices, vec![0, 1]);
+    /// ```
+    pub fn get_arguments(self) -> Vec<ArgumentValue<'ctx>> {
+        self.get_param_iter()
+            .map(|param| unsafe { ArgumentValue::new(param.as_value_ref()) })
+            .collect()
+    }
+
     pub fn get_last_basic_block(self) -> Option<BasicBlock<'ctx>> {
         unsafe { BasicBlock::new(LLVMGetLastBasicBlock(self.fn_value.value)) }
     }
@@ -187,6 +254,11 @@ impl<'ctx> FunctionValue<'ctx> {
         self.fn_value.get_name()
     }
 
+    /// Sets the name of a `FunctionValue`.
+    pub fn set_name(self, name: &str) {
+        self.fn_value.set_name(name)
+    }
+
     /// View the control flow graph and produce a .dot file
     pub fn view_function_cfg(self) {
         unsafe { LLVMViewFunctionCFG(self.as_value_ref()) }
@@ -197,6 +269,191 @@ impl<'ctx> FunctionValue<'ctx> {
         unsafe { LLVMViewFunctionCFGOnly(self.as_value_ref()) }
     }
 
+    /// Prints the control flow graph, including each block's instructions, as a Graphviz DOT
+    /// graph. Unlike `view_function_cfg`, this doesn't write any file or launch a viewer.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let builder = context.create_builder();
+    /// let module = context.create_module("my_mod");
+    /// let fn_type = context.void_type().fn_type(&[], false);
+    /// let function = module.add_function("my_fn", fn_type, None);
+    /// let entry = context.append_basic_block(function, "entry");
+    /// let exit = context.append_basic_block(function, "exit");
+    ///
+    /// builder.position_at_end(entry);
+    /// builder.build_unconditional_branch(exit);
+    /// builder.position_at_end(exit);
+    /// builder.build_return(None);
+    ///
+    /// let dot = function.print_cfg_to_string();
+    ///
+    /// assert!(dot.starts_with("digraph \"CFG for 'my_fn' function\" {"));
+    /// assert!(dot.contains("bb0 -> bb1;"));
+    /// assert!(dot.contains("ret void"));
+    /// ```
+    pub fn print_cfg_to_string(self) -> String {
+        self.print_cfg(false)
+    }
+
+    /// Prints the control flow graph as a Graphviz DOT graph, labelling blocks by name only.
+    pub fn print_cfg_only_to_string(self) -> String {
+        self.print_cfg(true)
+    }
+
+    fn print_cfg(self, cfg_only: bool) -> String {
+        fn escape(text: &str) -> String {
+            text.replace('\\', "\\\\").replace('"', "\\\"")
+        }
+
+        let blocks = self.get_basic_blocks();
+        let node_ids: HashMap<_, _> = blocks
+            .iter()
+            .enumerate()
+            .map(|(index, block)| (*block, format!("bb{}", index)))
+            .collect();
+        let title = escape(&format!("CFG for '{}' function", self.get_name().to_string_lossy()));
+        let mut dot = format!("digraph \"{}\" {{\n    label=\"{}\";\n", title, title);
+
+        for block in &blocks {
+            let node_id = &node_ids[block];
+            let name = block.get_name().to_string_lossy();
+            let mut label = if name.is_empty() {
+                format!("{}:", node_id)
+            } else {
+                format!("{}:", escape(&name))
+            };
+
+            if !cfg_only {
+                label.push_str("\\l");
+
+                for instruction in block.get_instructions() {
+                    label.push_str(&escape(instruction.print_to_string().to_string().trim()));
+                    label.push_str("\\l");
+                }
+            }
+
+            dot.push_str(&format!("\n    {} [shape=box,label=\"{}\"];\n", node_id, label));
+
+            let successors = block.get_successors();
+            let is_conditional_branch = successors.len() == 2
+                && block.get_terminator().map(|terminator| terminator.get_opcode()) == Some(InstructionOpcode::Br);
+
+            for (index, successor) in successors.iter().enumerate() {
+                let edge = format!("    {} -> {}", node_id, node_ids[successor]);
+
+                if is_conditional_branch {
+                    let label = if index == 0 { "T" } else { "F" };
+
+                    dot.push_str(&format!("{} [label=\"{}\"];\n", edge, label));
+                } else if successors.len() > 1 {
+                    dot.push_str(&format!("{} [label=\"{}\"];\n", edge, index));
+                } else {
+                    dot.push_str(&format!("{};\n", edge));
+                }
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Iterates over the `BasicBlock`s reachable from the entry block in depth first preorder,
+    /// visiting successors in operand order. Unreachable blocks aren't visited.
+    ///
+    /// The order is computed up front, so the control flow graph may be modified while iterating.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let builder = context.create_builder();
+    /// let module = context.create_module("my_mod");
+    /// let bool_type = context.bool_type();
+    /// let fn_type = context.void_type().fn_type(&[bool_type.into()], false);
+    /// let function = module.add_function("my_fn", fn_type, None);
+    /// let entry = context.append_basic_block(function, "entry");
+    /// let then_block = context.append_basic_block(function, "then");
+    /// let else_block = context.append_basic_block(function, "else");
+    /// let exit = context.append_basic_block(function, "exit");
+    /// let condition = function.get_first_param().unwrap().into_int_value();
+    ///
+    /// builder.position_at_end(entry);
+    /// builder.build_conditional_branch(condition, then_block, else_block);
+    /// builder.position_at_end(then_block);
+    /// builder.build_unconditional_branch(exit);
+    /// builder.position_at_end(else_block);
+    /// builder.build_unconditional_branch(exit);
+    /// builder.position_at_end(exit);
+    /// builder.build_return(None);
+    ///
+    /// let preorder: Vec<_> = function.get_preorder_blocks().collect();
+    /// let postorder: Vec<_> = function.get_postorder_blocks().collect();
+    /// let reverse_postorder: Vec<_> = function.get_reverse_postorder_blocks().collect();
+    ///
+    /// assert_eq!(preorder, vec![entry, then_block, exit, else_block]);
+    /// assert_eq!(postorder, vec![exit, then_block, else_block, entry]);
+    /// assert_eq!(reverse_postorder, vec![entry, else_block, then_block, exit]);
+    /// ```
+    pub fn get_preorder_blocks(self) -> CfgIter<'ctx> {
+        CfgIter::new(self.depth_first_search().0)
+    }
+
+    /// Iterates over the `BasicBlock`s reachable from the entry block in depth first postorder,
+    /// so that every block comes after its successors, back edges aside.
+    pub fn get_postorder_blocks(self) -> CfgIter<'ctx> {
+        CfgIter::new(self.depth_first_search().1)
+    }
+
+    /// Iterates over the `BasicBlock`s reachable from the entry block in reverse postorder,
+    /// so that every block comes before its successors, back edges aside. This is the usual
+    /// order for forward data flow analyses.
+    pub fn get_reverse_postorder_blocks(self) -> CfgIter<'ctx> {
+        let mut blocks = self.depth_first_search().1;
+
+        blocks.reverse();
+
+        CfgIter::new(blocks)
+    }
+
+    // Returns the reachable blocks in (preorder, postorder)
+    fn depth_first_search(self) -> (Vec<BasicBlock<'ctx>>, Vec<BasicBlock<'ctx>>) {
+        let mut preorder = Vec::new();
+        let mut postorder = Vec::new();
+        let entry = match self.get_first_basic_block() {
+            Some(entry) => entry,
+            None => return (preorder, postorder),
+        };
+        let mut visited = HashSet::new();
+        // Iterative, as deep graphs would overflow the stack otherwise
+        let mut stack = vec![(entry, entry.get_successors().into_iter())];
+
+        visited.insert(entry);
+        preorder.push(entry);
+
+        while let Some((block, successors)) = stack.last_mut() {
+            match successors.find(|successor| !visited.contains(successor)) {
+                Some(successor) => {
+                    visited.insert(successor);
+                    preorder.push(successor);
+                    stack.push((successor, successor.get_successors().into_iter()));
+                },
+                None => {
+                    postorder.push(*block);
+                    stack.pop();
+                },
+            }
+        }
+
+        (preorder, postorder)
+    }
+
     // TODO: Look for ways to prevent use after delete but maybe not possible
     pub unsafe fn delete(self) {
         LLVMDeleteFunction(self.as_value_ref())
@@ -565,6 +822,63 @@ impl fmt::Debug for FunctionValue<'_> {
     }
 }
 
+/// An iterator over the `BasicBlock`s of a `FunctionValue` in a control flow graph order, created by
+/// `FunctionValue::get_preorder_blocks`, `get_postorder_blocks` or `get_reverse_postorder_blocks`.
+#[derive(Debug)]
+pub struct CfgIter<'ctx> {
+    blocks: IntoIter<BasicBlock<'ctx>>,
+}
+
+impl<'ctx> CfgIter<'ctx> {
+    fn new(blocks: Vec<BasicBlock<'ctx>>) -> Self {
+        CfgIter {
+            blocks: blocks.into_iter(),
+        }
+    }
+}
+
+impl<'ctx> Iterator for CfgIter<'ctx> {
+    type Item = BasicBlock<'ctx>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.blocks.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.blocks.size_hint()
+    }
+}
+
+impl<'ctx> DoubleEndedIterator for CfgIter<'ctx> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.blocks.next_back()
+    }
+}
+
+impl ExactSizeIterator for CfgIter<'_> {}
+
+/// An iterator over the instructions of a `FunctionValue`, created by `FunctionValue::get_instructions`.
+#[derive(Debug)]
+pub struct FunctionInstructionIter<'ctx> {
+    block: Option<BasicBlock<'ctx>>,
+    instructions: Option<InstructionIter<'ctx>>,
+}
+
+impl<'ctx> Iterator for FunctionInstructionIter<'ctx> {
+    type Item = InstructionValue<'ctx>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(instruction) = self.instructions.as_mut()?.next() {
+                return Some(instruction);
+            }
+
+            self.block = self.block?.get_next_basic_block();
+            self.instructions = self.block.map(BasicBlock::get_instructions);
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ParamValueIter<'ctx> {
     param_iter_value: LLVMValueRef,
@@ -58,6 +58,11 @@ impl<'ctx> GlobalValue<'ctx> {
         self.global_value.get_name()
     }
 
+    /// Sets the name of a `GlobalValue`.
+    pub fn set_name(self, name: &str) {
+        self.global_value.set_name(name)
+    }
+
     pub fn get_previous_global(self) -> Option<GlobalValue<'ctx>> {
         let value = unsafe { LLVMGetPreviousGlobal(self.as_value_ref()) };
 
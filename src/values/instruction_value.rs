@@ -2,21 +2,29 @@ use either::{
     Either,
     Either::{Left, Right},
 };
+use llvm_sys::core::LLVMGetTypeKind;
 #[llvm_versions(3.9..=latest)]
-use llvm_sys::core::LLVMInstructionRemoveFromParent;
+use llvm_sys::core::{
+    LLVMCreateBuilderInContext, LLVMDisposeBuilder, LLVMGetTypeContext, LLVMInsertIntoBuilderWithName,
+    LLVMInstructionRemoveFromParent, LLVMPositionBuilderAtEnd, LLVMPositionBuilderBefore,
+};
 use llvm_sys::core::{
     LLVMGetAlignment, LLVMGetFCmpPredicate, LLVMGetICmpPredicate, LLVMGetInstructionOpcode, LLVMGetInstructionParent,
     LLVMGetMetadata, LLVMGetNextInstruction, LLVMGetNumOperands, LLVMGetOperand, LLVMGetOperandUse,
     LLVMGetPreviousInstruction, LLVMGetVolatile, LLVMHasMetadata, LLVMInstructionClone, LLVMInstructionEraseFromParent,
     LLVMIsAAllocaInst, LLVMIsABasicBlock, LLVMIsALoadInst, LLVMIsAStoreInst, LLVMIsTailCall, LLVMSetAlignment,
-    LLVMSetMetadata, LLVMSetOperand, LLVMSetVolatile, LLVMValueAsBasicBlock,
+    LLVMSetMetadata, LLVMSetOperand, LLVMSetVolatile, LLVMTypeOf, LLVMValueAsBasicBlock,
 };
 #[llvm_versions(3.8..=latest)]
 use llvm_sys::core::{LLVMGetOrdering, LLVMSetOrdering};
 #[llvm_versions(10.0..=latest)]
 use llvm_sys::core::{LLVMIsAAtomicCmpXchgInst, LLVMIsAAtomicRMWInst};
+#[llvm_versions(3.9..=latest)]
+use llvm_sys::prelude::LLVMBuilderRef;
 use llvm_sys::prelude::LLVMValueRef;
-use llvm_sys::LLVMOpcode;
+use llvm_sys::{LLVMOpcode, LLVMTypeKind};
+
+use std::ffi::CStr;
 
 use crate::values::traits::AsValueRef;
 use crate::values::{BasicValue, BasicValueEnum, BasicValueUse, MetadataValue, Value};
@@ -145,6 +153,17 @@ impl<'ctx> InstructionValue<'ctx> {
         }
     }
 
+    /// Gets the name of an `InstructionValue`. Instructions which produce no value, such as
+    /// stores and branches, have an empty name.
+    pub fn get_name(&self) -> &CStr {
+        self.instruction_value.get_name()
+    }
+
+    /// Sets the name of an `InstructionValue`. This is a noop for instructions producing no value.
+    pub fn set_name(self, name: &str) {
+        self.instruction_value.set_name(name)
+    }
+
     /// Get type of the current InstructionValue
     pub fn get_type(self) -> AnyTypeEnum<'ctx> {
         unsafe { AnyTypeEnum::new(self.instruction_value.get_type()) }
@@ -187,6 +206,108 @@ impl<'ctx> InstructionValue<'ctx> {
         unsafe { LLVMInstructionRemoveFromParent(self.as_value_ref()) }
     }
 
+    /// Creates a copy of this `InstructionValue` which has the same operands, but no name
+    /// and no parent. It can be placed with `Builder::insert_instruction`.
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("ivs");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let fn_type = i32_type.fn_type(&[i32_type.into()], false);
+    /// let function = module.add_function("double", fn_type, None);
+    /// let entry = context.append_basic_block(function, "entry");
+    /// let exit = context.append_basic_block(function, "exit");
+    ///
+    /// builder.position_at_end(entry);
+    ///
+    /// let x = function.get_first_param().unwrap().into_int_value();
+    /// let sum = builder.build_int_add(x, x, "sum");
+    /// let add = sum.as_instruction().unwrap();
+    /// let add_copy = add.clone_instruction();
+    ///
+    /// assert!(add_copy.get_parent().is_none());
+    ///
+    /// builder.position_at_end(exit);
+    /// builder.insert_instruction(&add_copy, Some("sum_copy"));
+    ///
+    /// assert_eq!(add_copy.get_parent(), Some(exit));
+    /// assert_eq!(add_copy.get_operand(0), add.get_operand(0));
+    /// ```
+    pub fn clone_instruction(self) -> Self {
+        unsafe { InstructionValue::new(LLVMInstructionClone(self.as_value_ref())) }
+    }
+
+    /// Moves this `InstructionValue` so that it comes right before `other`, which may be in a
+    /// different `BasicBlock`. The instruction keeps its name and operands. An instruction without
+    /// a parent, such as one made by `clone_instruction`, is inserted.
+    ///
+    /// Returns an error if `other` is not in a `BasicBlock`.
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("ivs");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let fn_type = i32_type.fn_type(&[i32_type.into()], false);
+    /// let function = module.add_function("f", fn_type, None);
+    /// let entry = context.append_basic_block(function, "entry");
+    ///
+    /// builder.position_at_end(entry);
+    ///
+    /// let x = function.get_first_param().unwrap().into_int_value();
+    /// let sum = builder.build_int_add(x, x, "sum").as_instruction().unwrap();
+    /// let product = builder.build_int_mul(x, x, "product").as_instruction().unwrap();
+    ///
+    /// product.move_before(sum).unwrap();
+    ///
+    /// assert_eq!(entry.get_first_instruction(), Some(product));
+    /// assert_eq!(product.get_next_instruction(), Some(sum));
+    /// ```
+    #[llvm_versions(3.9..=latest)]
+    pub fn move_before(self, other: InstructionValue<'ctx>) -> Result<(), &'static str> {
+        if other.get_parent().is_none() {
+            return Err("Cannot move an instruction before one which is not in a basic block.");
+        }
+
+        if self != other {
+            self.reinsert(|builder| unsafe { LLVMPositionBuilderBefore(builder, other.as_value_ref()) });
+        }
+
+        Ok(())
+    }
+
+    /// Moves this `InstructionValue` to the end of `block`, keeping its name and operands. An
+    /// instruction without a parent, such as one made by `clone_instruction`, is inserted.
+    ///
+    /// Moving an instruction after the terminator of `block` will produce invalid IR.
+    #[llvm_versions(3.9..=latest)]
+    pub fn move_to_end_of(self, block: BasicBlock<'ctx>) {
+        self.reinsert(|builder| unsafe { LLVMPositionBuilderAtEnd(builder, block.basic_block) });
+    }
+
+    #[llvm_versions(3.9..=latest)]
+    fn reinsert<F: FnOnce(LLVMBuilderRef)>(self, position: F) {
+        // Reinserting through a builder would otherwise clear the name
+        let name = self.get_name().to_owned();
+
+        if self.get_parent().is_some() {
+            self.remove_from_basic_block();
+        }
+
+        unsafe {
+            let builder = LLVMCreateBuilderInContext(LLVMGetTypeContext(LLVMTypeOf(self.as_value_ref())));
+
+            position(builder);
+            LLVMInsertIntoBuilderWithName(builder, self.as_value_ref(), name.as_ptr());
+            LLVMDisposeBuilder(builder);
+        }
+    }
+
     // REVIEW: Potentially unsafe is parent BB or grandparent fn was deleted
     // REVIEW: Should this *not* be an option? Parent should always exist,
     // but I doubt LLVM returns null if the parent BB (or grandparent FN)
@@ -550,6 +671,90 @@ impl<'ctx> InstructionValue<'ctx> {
         unsafe { Some(BasicValueUse::new(use_)) }
     }
 
+    /// Iterates over the operands of an `InstructionValue`, in the same order as `get_operand`.
+    /// Operands which have not been set are skipped, as are operands which are neither basic values
+    /// nor basic blocks, such as the metadata arguments of an `llvm.dbg.value` call or token operands.
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("ivs");
+    /// let builder = context.create_builder();
+    /// let bool_type = context.bool_type();
+    /// let fn_type = context.void_type().fn_type(&[bool_type.into()], false);
+    /// let function = module.add_function("branch", fn_type, None);
+    /// let entry = context.append_basic_block(function, "entry");
+    /// let then_block = context.append_basic_block(function, "then");
+    /// let else_block = context.append_basic_block(function, "else");
+    ///
+    /// builder.position_at_end(entry);
+    ///
+    /// let condition = function.get_first_param().unwrap().into_int_value();
+    /// let branch = builder.build_conditional_branch(condition, then_block, else_block);
+    /// let blocks: Vec<_> = branch.operands().filter_map(|operand| operand.right()).collect();
+    ///
+    /// assert_eq!(branch.operands().count(), 3);
+    /// assert!(blocks.contains(&then_block) && blocks.contains(&else_block));
+    /// ```
+    pub fn operands(self) -> OperandIter<'ctx> {
+        OperandIter {
+            instruction: self,
+            index: 0,
+            end: self.get_num_operands(),
+        }
+    }
+
+    /// Replaces every operand of this `InstructionValue` which is `old` with `new`. Unlike
+    /// `replace_all_uses_with`, uses of `old` in other instructions are left alone.
+    ///
+    /// Returns false, changing nothing, if `old` and `new` have different types. Otherwise returns
+    /// whether any operand was replaced.
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("ivs");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let fn_type = i32_type.fn_type(&[i32_type.into(), i32_type.into()], false);
+    /// let function = module.add_function("sub", fn_type, None);
+    /// let entry = context.append_basic_block(function, "entry");
+    ///
+    /// builder.position_at_end(entry);
+    ///
+    /// let x = function.get_nth_param(0).unwrap().into_int_value();
+    /// let y = function.get_nth_param(1).unwrap().into_int_value();
+    /// let x_minus_y = builder.build_int_sub(x, y, "x_minus_y");
+    /// let x_plus_y = builder.build_int_add(x, y, "x_plus_y");
+    /// let sub = x_minus_y.as_instruction().unwrap();
+    ///
+    /// assert!(sub.replace_uses_of_with(x, y));
+    /// assert!(!sub.replace_uses_of_with(x, y));
+    /// assert_eq!(sub.get_operand(0).unwrap().left(), Some(y.into()));
+    /// assert_eq!(x_plus_y.as_instruction().unwrap().get_operand(0).unwrap().left(), Some(x.into()));
+    /// ```
+    pub fn replace_uses_of_with<O: BasicValue<'ctx>, N: BasicValue<'ctx>>(self, old: O, new: N) -> bool {
+        let (old, new) = (old.as_value_ref(), new.as_value_ref());
+
+        if unsafe { LLVMTypeOf(old) != LLVMTypeOf(new) } {
+            return false;
+        }
+
+        let mut replaced = false;
+
+        for index in 0..self.get_num_operands() {
+            if unsafe { LLVMGetOperand(self.as_value_ref(), index) } == old {
+                unsafe { LLVMSetOperand(self.as_value_ref(), index, new) }
+
+                replaced = true;
+            }
+        }
+
+        replaced
+    }
+
     /// Gets the first use of an `InstructionValue` if any.
     ///
     /// The following example,
@@ -665,3 +870,78 @@ impl AsValueRef for InstructionValue<'_> {
         self.instruction_value.value
     }
 }
+
+/// An iterator over the operands of an `InstructionValue`, created by `InstructionValue::operands`.
+#[derive(Debug)]
+pub struct OperandIter<'ctx> {
+    instruction: InstructionValue<'ctx>,
+    index: u32,
+    end: u32,
+}
+
+impl<'ctx> OperandIter<'ctx> {
+    fn get_operand(&self, index: u32) -> Option<Either<BasicValueEnum<'ctx>, BasicBlock<'ctx>>> {
+        let operand = unsafe { LLVMGetOperand(self.instruction.as_value_ref(), index) };
+
+        if operand.is_null() {
+            return None;
+        }
+
+        let is_basic_block = unsafe { !LLVMIsABasicBlock(operand).is_null() };
+
+        // Labels are only yielded as basic blocks; anything else which can't be a `BasicValueEnum` is skipped.
+        let is_supported = is_basic_block
+            || match unsafe { LLVMGetTypeKind(LLVMTypeOf(operand)) } {
+                LLVMTypeKind::LLVMMetadataTypeKind
+                | LLVMTypeKind::LLVMVoidTypeKind
+                | LLVMTypeKind::LLVMLabelTypeKind
+                | LLVMTypeKind::LLVMFunctionTypeKind
+                | LLVMTypeKind::LLVMX86_MMXTypeKind => false,
+                #[cfg(any(feature = "llvm12-0", feature = "llvm13-0", feature = "llvm14-0"))]
+                LLVMTypeKind::LLVMX86_AMXTypeKind => false,
+                #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7")))]
+                LLVMTypeKind::LLVMTokenTypeKind => false,
+                _ => true,
+            };
+
+        if is_supported {
+            self.instruction.get_operand(index)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'ctx> Iterator for OperandIter<'ctx> {
+    type Item = Either<BasicValueEnum<'ctx>, BasicBlock<'ctx>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.end {
+            let operand = self.get_operand(self.index);
+
+            self.index += 1;
+
+            if operand.is_some() {
+                return operand;
+            }
+        }
+
+        None
+    }
+}
+
+impl<'ctx> DoubleEndedIterator for OperandIter<'ctx> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while self.index < self.end {
+            self.end -= 1;
+
+            let operand = self.get_operand(self.end);
+
+            if operand.is_some() {
+                return operand;
+            }
+        }
+
+        None
+    }
+}
@@ -1,13 +1,14 @@
 #[llvm_versions(4.0..=latest)]
 use llvm_sys::core::LLVMConstExactUDiv;
 use llvm_sys::core::{
-    LLVMConstAShr, LLVMConstAdd, LLVMConstAnd, LLVMConstBitCast, LLVMConstExactSDiv, LLVMConstICmp, LLVMConstIntCast,
-    LLVMConstIntGetSExtValue, LLVMConstIntGetZExtValue, LLVMConstIntToPtr, LLVMConstLShr, LLVMConstMul,
-    LLVMConstNSWAdd, LLVMConstNSWMul, LLVMConstNSWNeg, LLVMConstNSWSub, LLVMConstNUWAdd, LLVMConstNUWMul,
+    LLVMConstAShr, LLVMConstAdd, LLVMConstAnd, LLVMConstBitCast, LLVMConstExactSDiv, LLVMConstICmp, LLVMConstInt,
+    LLVMConstIntCast, LLVMConstIntGetSExtValue, LLVMConstIntGetZExtValue, LLVMConstIntToPtr, LLVMConstLShr,
+    LLVMConstMul, LLVMConstNSWAdd, LLVMConstNSWMul, LLVMConstNSWNeg, LLVMConstNSWSub, LLVMConstNUWAdd, LLVMConstNUWMul,
     LLVMConstNUWNeg, LLVMConstNUWSub, LLVMConstNeg, LLVMConstNot, LLVMConstOr, LLVMConstSDiv, LLVMConstSExt,
     LLVMConstSExtOrBitCast, LLVMConstSIToFP, LLVMConstSRem, LLVMConstSelect, LLVMConstShl, LLVMConstSub,
     LLVMConstTrunc, LLVMConstTruncOrBitCast, LLVMConstUDiv, LLVMConstUIToFP, LLVMConstURem, LLVMConstXor,
-    LLVMConstZExt, LLVMConstZExtOrBitCast, LLVMIsAConstantInt,
+    LLVMConstZExt, LLVMConstZExtOrBitCast, LLVMGetTypeContext, LLVMInt64TypeInContext, LLVMIntTypeInContext,
+    LLVMIsAConstantInt,
 };
 use llvm_sys::prelude::LLVMValueRef;
 
@@ -38,6 +39,11 @@ impl<'ctx> IntValue<'ctx> {
         self.int_value.get_name()
     }
 
+    /// Sets the name of an `IntValue`. If the value is a constant, this is a noop.
+    pub fn set_name(self, name: &str) {
+        self.int_value.set_name(name)
+    }
+
     pub fn get_type(self) -> IntType<'ctx> {
         unsafe { IntType::new(self.int_value.get_type()) }
     }
@@ -323,6 +329,115 @@ impl<'ctx> IntValue<'ctx> {
         unsafe { Some(LLVMConstIntGetSExtValue(self.as_value_ref())) }
     }
 
+    /// Obtains the words of a constant `IntValue` of any width, least significant word first, as
+    /// taken by `IntType::const_int_arbitrary_precision`. Bits past the width of the type are zero.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let i256_type = context.custom_width_int_type(256);
+    /// let i256_val = i256_type.const_int_arbitrary_precision(&[1, 2, 3, 4]);
+    ///
+    /// assert_eq!(i256_val.get_arbitrary_precision_words(), Some(vec![1, 2, 3, 4]));
+    /// ```
+    pub fn get_arbitrary_precision_words(self) -> Option<Vec<u64>> {
+        // Garbage values are produced on non constant values
+        if !self.is_constant_int() {
+            return None;
+        }
+
+        // Integer types are always at least one bit wide
+        let word_count = (self.get_type().get_bit_width() - 1) / 64 + 1;
+
+        Some(self.get_constant_words(word_count, false))
+    }
+
+    /// Obtains a constant `IntValue`'s zero extended value, for types up to 128 bits wide.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let i128_type = context.i128_type();
+    /// let i128_all_ones = i128_type.const_all_ones();
+    ///
+    /// assert_eq!(i128_all_ones.get_zero_extended_constant_u128(), Some(u128::MAX));
+    /// ```
+    pub fn get_zero_extended_constant_u128(self) -> Option<u128> {
+        // Garbage values are produced on non constant values
+        if !self.is_constant_int() {
+            return None;
+        }
+        if self.get_type().get_bit_width() > 128 {
+            return None;
+        }
+
+        let words = self.get_constant_words(2, false);
+
+        Some(u128::from(words[0]) | u128::from(words[1]) << 64)
+    }
+
+    /// Obtains a constant `IntValue`'s sign extended value, for types up to 128 bits wide.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let i128_type = context.i128_type();
+    /// let i128_all_ones = i128_type.const_all_ones();
+    ///
+    /// assert_eq!(i128_all_ones.get_sign_extended_constant_i128(), Some(-1));
+    /// ```
+    pub fn get_sign_extended_constant_i128(self) -> Option<i128> {
+        // Garbage values are produced on non constant values
+        if !self.is_constant_int() {
+            return None;
+        }
+        if self.get_type().get_bit_width() > 128 {
+            return None;
+        }
+
+        let words = self.get_constant_words(2, true);
+
+        Some((u128::from(words[0]) | u128::from(words[1]) << 64) as i128)
+    }
+
+    // LLVM's C API can only read out 64 bits at a time, so wider constants are extended to a
+    // whole number of words and folded down one word at a time.
+    //
+    // This is a deliberate trade-off: the C API offers no other way to get at the words, and every
+    // constant folded here stays interned in the context. The types and shift amounts are shared
+    // between calls, but each distinct constant read leaves its extended value, shifted values
+    // and words behind, so reading many different wide constants grows the context accordingly.
+    fn get_constant_words(self, word_count: u32, sign_extend: bool) -> Vec<u64> {
+        unsafe {
+            let context = LLVMGetTypeContext(self.get_type().as_type_ref());
+            let wide_type = LLVMIntTypeInContext(context, word_count * 64);
+            let word_type = LLVMInt64TypeInContext(context);
+            let wide_value = if sign_extend {
+                LLVMConstSExtOrBitCast(self.as_value_ref(), wide_type)
+            } else {
+                LLVMConstZExtOrBitCast(self.as_value_ref(), wide_type)
+            };
+
+            (0..word_count)
+                .map(|index| {
+                    let shift = LLVMConstInt(wide_type, u64::from(index) * 64, 0);
+                    let word = LLVMConstTruncOrBitCast(LLVMConstLShr(wide_value, shift), word_type);
+
+                    LLVMConstIntGetZExtValue(word)
+                })
+                .collect()
+        }
+    }
+
     pub fn replace_all_uses_with(self, other: IntValue<'ctx>) {
         self.int_value.replace_all_uses_with(other.as_value_ref())
     }
@@ -1,5 +1,7 @@
 //! A value is an instance of a type.
 
+#[deny(missing_docs)]
+mod argument_value;
 #[deny(missing_docs)]
 mod array_value;
 #[deny(missing_docs)]
@@ -18,38 +20,46 @@ mod metadata_value;
 mod phi_value;
 mod ptr_value;
 mod struct_value;
+mod token_value;
 mod traits;
+mod typed_ptr_value;
 mod vec_value;
+mod x86_amx_value;
 
 use crate::support::LLVMString;
-pub use crate::values::array_value::ArrayValue;
-pub use crate::values::basic_value_use::BasicValueUse;
+pub use crate::values::argument_value::ArgumentValue;
+pub use crate::values::array_value::{ArrayValue, ConstantDataElement};
+pub use crate::values::basic_value_use::{BasicValueUse, BasicValueUseIter, UserIter};
 pub use crate::values::call_site_value::CallSiteValue;
 pub use crate::values::callable_value::CallableValue;
 pub use crate::values::enums::{AggregateValueEnum, AnyValueEnum, BasicMetadataValueEnum, BasicValueEnum};
 pub use crate::values::float_value::FloatValue;
-pub use crate::values::fn_value::FunctionValue;
+pub use crate::values::fn_value::{CfgIter, FunctionInstructionIter, FunctionValue};
 pub use crate::values::generic_value::GenericValue;
 pub use crate::values::global_value::GlobalValue;
 #[llvm_versions(7.0..=latest)]
 pub use crate::values::global_value::UnnamedAddress;
-pub use crate::values::instruction_value::{InstructionOpcode, InstructionValue};
+pub use crate::values::instruction_value::{InstructionOpcode, InstructionValue, OperandIter};
 pub use crate::values::int_value::IntValue;
 pub use crate::values::metadata_value::{MetadataValue, FIRST_CUSTOM_METADATA_KIND_ID};
 pub use crate::values::phi_value::PhiValue;
 pub use crate::values::ptr_value::PointerValue;
 pub use crate::values::struct_value::StructValue;
+pub use crate::values::token_value::TokenValue;
 pub(crate) use crate::values::traits::AsValueRef;
 pub use crate::values::traits::{AggregateValue, AnyValue, BasicValue, FloatMathValue, IntMathValue, PointerMathValue};
+pub use crate::values::typed_ptr_value::TypedPointerValue;
 pub use crate::values::vec_value::VectorValue;
+pub use crate::values::x86_amx_value::X86AMXValue;
 #[cfg(feature = "internal-getters")]
 use crate::LLVMReference;
 
 use llvm_sys::core::{
-    LLVMDumpValue, LLVMGetFirstUse, LLVMIsAInstruction, LLVMIsConstant, LLVMIsNull, LLVMIsUndef, LLVMPrintTypeToString,
-    LLVMPrintValueToString, LLVMReplaceAllUsesWith, LLVMTypeOf,
+    LLVMDumpValue, LLVMGetFirstUse, LLVMGetTypeKind, LLVMIsAInstruction, LLVMIsConstant, LLVMIsNull, LLVMIsUndef,
+    LLVMPrintTypeToString, LLVMPrintValueToString, LLVMReplaceAllUsesWith, LLVMTypeOf,
 };
 use llvm_sys::prelude::{LLVMTypeRef, LLVMValueRef};
+use llvm_sys::LLVMTypeKind;
 
 use std::ffi::CStr;
 use std::fmt;
@@ -101,6 +111,11 @@ impl<'ctx> Value<'ctx> {
     // add a ParamValue wrapper type that always have it but conditional types (IntValue<Variable>)
     // that also have it. This isn't a huge deal though, since it hasn't proven to be UB so far
     fn set_name(self, name: &str) {
+        // LLVM asserts when naming values without a result, such as stores and void calls
+        if unsafe { LLVMGetTypeKind(self.get_type()) } == LLVMTypeKind::LLVMVoidTypeKind {
+            return;
+        }
+
         #[cfg(any(
             feature = "llvm3-6",
             feature = "llvm3-7",
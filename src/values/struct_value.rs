@@ -26,6 +26,11 @@ impl<'ctx> StructValue<'ctx> {
         self.struct_value.get_name()
     }
 
+    /// Sets the name of a `StructValue`. If the value is a constant, this is a noop.
+    pub fn set_name(self, name: &str) {
+        self.struct_value.set_name(name)
+    }
+
     pub fn get_type(self) -> StructType<'ctx> {
         unsafe { StructType::new(self.struct_value.get_type()) }
     }
@@ -0,0 +1,62 @@
+use llvm_sys::prelude::LLVMValueRef;
+
+use std::ffi::CStr;
+
+use crate::types::TokenType;
+use crate::values::traits::AsValueRef;
+use crate::values::{InstructionValue, Value};
+
+/// A `TokenValue` is an opaque value of a `TokenType`, such as the ones returned by
+/// `llvm.coro.id` or `catchswitch`. It may only be passed on to other instructions.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct TokenValue<'ctx> {
+    token_value: Value<'ctx>,
+}
+
+impl<'ctx> TokenValue<'ctx> {
+    pub(crate) unsafe fn new(value: LLVMValueRef) -> Self {
+        assert!(!value.is_null());
+
+        TokenValue {
+            token_value: Value::new(value),
+        }
+    }
+
+    /// Gets the name of a `TokenValue`. If the value is a constant, this will
+    /// return an empty string.
+    pub fn get_name(&self) -> &CStr {
+        self.token_value.get_name()
+    }
+
+    /// Sets the name of a `TokenValue`. This is a no-op for the `none` constant.
+    pub fn set_name(self, name: &str) {
+        self.token_value.set_name(name)
+    }
+
+    pub fn get_type(self) -> TokenType<'ctx> {
+        unsafe { TokenType::new(self.token_value.get_type()) }
+    }
+
+    /// Determines whether or not this is the `none` constant.
+    pub fn is_none(self) -> bool {
+        self.token_value.is_null()
+    }
+
+    pub fn is_undef(self) -> bool {
+        self.token_value.is_undef()
+    }
+
+    pub fn print_to_stderr(self) {
+        self.token_value.print_to_stderr()
+    }
+
+    pub fn as_instruction(self) -> Option<InstructionValue<'ctx>> {
+        self.token_value.as_instruction()
+    }
+}
+
+impl AsValueRef for TokenValue<'_> {
+    fn as_value_ref(&self) -> LLVMValueRef {
+        self.token_value.value
+    }
+}
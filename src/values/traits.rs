@@ -1,13 +1,15 @@
 use llvm_sys::core::{LLVMConstExtractValue, LLVMConstInsertValue};
 use llvm_sys::prelude::LLVMValueRef;
 
+use std::ffi::CStr;
 use std::fmt::Debug;
 
 use crate::support::LLVMString;
 use crate::types::{FloatMathType, FloatType, IntMathType, IntType, PointerMathType, PointerType, VectorType};
 use crate::values::{
-    AggregateValueEnum, AnyValueEnum, ArrayValue, BasicValueEnum, BasicValueUse, CallSiteValue, FloatValue,
-    FunctionValue, GlobalValue, InstructionValue, IntValue, PhiValue, PointerValue, StructValue, Value, VectorValue,
+    AggregateValueEnum, AnyValueEnum, ArrayValue, BasicValueEnum, BasicValueUse, BasicValueUseIter, CallSiteValue,
+    FloatValue, FunctionValue, GlobalValue, InstructionValue, IntValue, PhiValue, PointerValue, StructValue,
+    TokenValue, UserIter, Value, VectorValue, X86AMXValue,
 };
 
 // This is an ugly privacy hack so that Type can stay private to this module
@@ -77,6 +79,8 @@ pub trait AggregateValue<'ctx>: BasicValue<'ctx> {
 }
 
 /// Represents a basic value, which can be used both by itself, or in an `AggregateValue`.
+///
+/// Its name is read through `AnyValue::get_name` and changed through `BasicValue::set_name`.
 pub trait BasicValue<'ctx>: AnyValue<'ctx> {
     /// Returns an enum containing a typed version of the `BasicValue`.
     fn as_basic_value_enum(&self) -> BasicValueEnum<'ctx> {
@@ -100,6 +104,9 @@ pub trait BasicValue<'ctx>: AnyValue<'ctx> {
     }
 
     /// Sets the name of a `BasicValue`. If the value is a constant, this is a noop.
+    ///
+    /// Values which aren't basic, such as `InstructionValue` and `FunctionValue`, are named
+    /// through their own `set_name` methods.
     fn set_name(&self, name: &str) {
         unsafe { Value::new(self.as_value_ref()).set_name(name) }
     }
@@ -137,10 +144,56 @@ pub trait AnyValue<'ctx>: AsValueRef + Debug {
     fn print_to_string(&self) -> LLVMString {
         unsafe { Value::new(self.as_value_ref()).print_to_string() }
     }
+
+    /// Gets the name of a value. Constants and unnamed values have an empty name.
+    ///
+    /// As every `BasicValue` is also an `AnyValue`, this works the same way on instructions,
+    /// function parameters, globals and the value enums.
+    fn get_name(&self) -> &CStr {
+        // The name is owned by the LLVM value rather than the temporary `Value` wrapper
+        unsafe { CStr::from_ptr(Value::new(self.as_value_ref()).get_name().as_ptr()) }
+    }
+
+    /// Iterates over every use of this value, following `BasicValueUse::get_next_use`.
+    fn uses(&self) -> BasicValueUseIter<'ctx> {
+        BasicValueUseIter::new(unsafe { Value::new(self.as_value_ref()).get_first_use() })
+    }
+
+    /// Iterates over the users of this value, such as the instructions which take it as an operand.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::values::AnyValue;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("users");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let fn_type = i32_type.fn_type(&[i32_type.into()], false);
+    /// let function = module.add_function("double", fn_type, None);
+    /// let entry = context.append_basic_block(function, "entry");
+    ///
+    /// builder.position_at_end(entry);
+    ///
+    /// let x = function.get_first_param().unwrap();
+    /// let sum = builder.build_int_add(x.into_int_value(), x.into_int_value(), "sum");
+    ///
+    /// builder.build_return(Some(&sum));
+    ///
+    /// let add = sum.as_instruction().unwrap();
+    ///
+    /// assert_eq!(x.uses().count(), 2);
+    /// assert!(x.users().all(|user| user.as_instruction_value() == Some(add)));
+    /// ```
+    fn users(&self) -> UserIter<'ctx> {
+        UserIter::new(unsafe { Value::new(self.as_value_ref()).get_first_use() })
+    }
 }
 
 trait_value_set! {AggregateValue: ArrayValue, AggregateValueEnum, StructValue}
-trait_value_set! {AnyValue: AnyValueEnum, BasicValueEnum, AggregateValueEnum, ArrayValue, IntValue, FloatValue, GlobalValue, PhiValue, PointerValue, FunctionValue, StructValue, VectorValue, InstructionValue, CallSiteValue}
+trait_value_set! {AnyValue: AnyValueEnum, BasicValueEnum, AggregateValueEnum, ArrayValue, IntValue, FloatValue, GlobalValue, PhiValue, PointerValue, FunctionValue, StructValue, VectorValue, InstructionValue, CallSiteValue, TokenValue, X86AMXValue}
 trait_value_set! {BasicValue: ArrayValue, BasicValueEnum, AggregateValueEnum, IntValue, FloatValue, GlobalValue, StructValue, PointerValue, VectorValue}
 math_trait_value_set! {IntMathValue: (IntValue => IntType), (VectorValue => VectorType)}
 math_trait_value_set! {FloatMathValue: (FloatValue => FloatType), (VectorValue => VectorType)}
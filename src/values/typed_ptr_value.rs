@@ -0,0 +1,165 @@
+use llvm_sys::prelude::LLVMValueRef;
+
+use std::convert::TryFrom;
+use std::ffi::CStr;
+
+use crate::types::{BasicType, BasicTypeEnum, PointerType};
+use crate::values::{AnyValue, AsValueRef, BasicValue, BasicValueEnum, PointerValue};
+
+/// A `PointerValue` which keeps track of the type it points to, so that loads, stores and
+/// GEPs through it can be checked on the Rust side rather than by LLVM.
+///
+/// # Example
+///
+/// ```no_run
+/// use inkwell::context::Context;
+/// use inkwell::values::TypedPointerValue;
+/// use inkwell::AddressSpace;
+///
+/// let context = Context::create();
+/// let module = context.create_module("typed_ptr");
+/// let builder = context.create_builder();
+/// let i32_type = context.i32_type();
+/// let fn_type = i32_type.fn_type(&[i32_type.ptr_type(AddressSpace::Generic).into()], false);
+/// let fn_value = module.add_function("deref", fn_type, None);
+/// let entry = context.append_basic_block(fn_value, "entry");
+///
+/// builder.position_at_end(entry);
+///
+/// let param = fn_value.get_first_param().unwrap().into_pointer_value();
+/// let ptr = TypedPointerValue::new(param, i32_type).unwrap();
+/// let value = builder.build_typed_load(ptr, "value").unwrap();
+///
+/// builder.build_return(Some(&value));
+///
+/// assert!(TypedPointerValue::new(param, context.i64_type()).is_err());
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct TypedPointerValue<'ctx, T: BasicType<'ctx>> {
+    ptr_value: PointerValue<'ctx>,
+    pointee_type: T,
+}
+
+impl<'ctx, T: BasicType<'ctx> + Copy> TypedPointerValue<'ctx, T> {
+    /// Creates a `TypedPointerValue` from a `PointerValue`, failing if the pointer's element type is
+    /// not `pointee_type`.
+    pub fn new(ptr_value: PointerValue<'ctx>, pointee_type: T) -> Result<Self, &'static str> {
+        if ptr_value.get_type().get_element_type() != pointee_type.as_any_type_enum() {
+            return Err("Pointer does not point to the given pointee type.");
+        }
+
+        Ok(TypedPointerValue {
+            ptr_value,
+            pointee_type,
+        })
+    }
+
+    /// Creates a `TypedPointerValue` without checking that `ptr_value` points to `pointee_type`.
+    ///
+    /// # Safety
+    ///
+    /// Loads and stores through the resulting pointer will use `pointee_type`, so it must really be
+    /// the type of the memory being pointed to.
+    pub unsafe fn new_unchecked(ptr_value: PointerValue<'ctx>, pointee_type: T) -> Self {
+        TypedPointerValue {
+            ptr_value,
+            pointee_type,
+        }
+    }
+
+    /// Gets the type this pointer points to.
+    pub fn get_pointee_type(self) -> T {
+        self.pointee_type
+    }
+
+    /// Gets the untyped `PointerValue` behind this pointer.
+    pub fn as_pointer_value(self) -> PointerValue<'ctx> {
+        self.ptr_value
+    }
+
+    /// Gets the name of the pointer. If the value is a constant, this will return an empty string.
+    pub fn get_name(&self) -> &CStr {
+        self.ptr_value.get_name()
+    }
+
+    /// Sets the name of the pointer. If the value is a constant, this is a noop.
+    pub fn set_name(self, name: &str) {
+        self.ptr_value.set_name(name)
+    }
+
+    /// Gets the `PointerType` of this pointer.
+    pub fn get_type(self) -> PointerType<'ctx> {
+        self.ptr_value.get_type()
+    }
+
+    /// Forgets the specific Rust type of the pointee, keeping only its `BasicTypeEnum`.
+    pub fn into_basic(self) -> TypedPointerValue<'ctx, BasicTypeEnum<'ctx>> {
+        TypedPointerValue {
+            ptr_value: self.ptr_value,
+            pointee_type: self.pointee_type.as_basic_type_enum(),
+        }
+    }
+
+    /// Attempts to give the pointee a more specific Rust type, such as turning a pointer to a
+    /// `BasicTypeEnum` into a pointer to an `IntType`. Returns `self` back on failure.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::types::{FloatType, IntType};
+    /// use inkwell::values::TypedPointerValue;
+    /// use inkwell::AddressSpace;
+    ///
+    /// let context = Context::create();
+    /// let i32_type = context.i32_type();
+    /// let null = i32_type.ptr_type(AddressSpace::Generic).const_null();
+    /// let ptr = TypedPointerValue::new(null, i32_type).unwrap().into_basic();
+    ///
+    /// assert!(ptr.downcast::<FloatType>().is_err());
+    /// assert_eq!(ptr.downcast::<IntType>().unwrap().get_pointee_type(), i32_type);
+    /// ```
+    pub fn downcast<U: BasicType<'ctx> + Copy + TryFrom<T>>(self) -> Result<TypedPointerValue<'ctx, U>, Self> {
+        match U::try_from(self.pointee_type) {
+            Ok(pointee_type) => Ok(TypedPointerValue {
+                ptr_value: self.ptr_value,
+                pointee_type,
+            }),
+            Err(_) => Err(self),
+        }
+    }
+}
+
+impl<'ctx, T: BasicType<'ctx>> AsValueRef for TypedPointerValue<'ctx, T> {
+    fn as_value_ref(&self) -> LLVMValueRef {
+        self.ptr_value.as_value_ref()
+    }
+}
+
+impl<'ctx, T: BasicType<'ctx>> AnyValue<'ctx> for TypedPointerValue<'ctx, T> {}
+impl<'ctx, T: BasicType<'ctx>> BasicValue<'ctx> for TypedPointerValue<'ctx, T> {}
+
+impl<'ctx, T: BasicType<'ctx>> From<TypedPointerValue<'ctx, T>> for PointerValue<'ctx> {
+    fn from(value: TypedPointerValue<'ctx, T>) -> Self {
+        value.ptr_value
+    }
+}
+
+impl<'ctx, T: BasicType<'ctx>> From<TypedPointerValue<'ctx, T>> for BasicValueEnum<'ctx> {
+    fn from(value: TypedPointerValue<'ctx, T>) -> Self {
+        value.ptr_value.into()
+    }
+}
+
+impl<'ctx> TryFrom<PointerValue<'ctx>> for TypedPointerValue<'ctx, BasicTypeEnum<'ctx>> {
+    type Error = ();
+
+    fn try_from(value: PointerValue<'ctx>) -> Result<Self, Self::Error> {
+        let pointee_type = BasicTypeEnum::try_from(value.get_type().get_element_type())?;
+
+        Ok(TypedPointerValue {
+            ptr_value: value,
+            pointee_type,
+        })
+    }
+}
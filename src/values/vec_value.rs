@@ -60,6 +60,11 @@ impl<'ctx> VectorValue<'ctx> {
         self.vec_value.get_name()
     }
 
+    /// Sets the name of a `VectorValue`. If the value is a constant, this is a noop.
+    pub fn set_name(self, name: &str) {
+        self.vec_value.set_name(name)
+    }
+
     pub fn get_type(self) -> VectorType<'ctx> {
         unsafe { VectorType::new(self.vec_value.get_type()) }
     }
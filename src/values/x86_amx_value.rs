@@ -0,0 +1,56 @@
+use llvm_sys::prelude::LLVMValueRef;
+
+use std::ffi::CStr;
+
+use crate::types::X86AMXType;
+use crate::values::traits::AsValueRef;
+use crate::values::{InstructionValue, Value};
+
+/// An `X86AMXValue` is a tile of an `X86AMXType`, as produced and consumed by AMX intrinsics.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct X86AMXValue<'ctx> {
+    x86_amx_value: Value<'ctx>,
+}
+
+impl<'ctx> X86AMXValue<'ctx> {
+    pub(crate) unsafe fn new(value: LLVMValueRef) -> Self {
+        assert!(!value.is_null());
+
+        X86AMXValue {
+            x86_amx_value: Value::new(value),
+        }
+    }
+
+    /// Gets the name of an `X86AMXValue`. If the value is a constant, this will
+    /// return an empty string.
+    pub fn get_name(&self) -> &CStr {
+        self.x86_amx_value.get_name()
+    }
+
+    /// Sets the name of an `X86AMXValue`.
+    pub fn set_name(self, name: &str) {
+        self.x86_amx_value.set_name(name)
+    }
+
+    pub fn get_type(self) -> X86AMXType<'ctx> {
+        unsafe { X86AMXType::new(self.x86_amx_value.get_type()) }
+    }
+
+    pub fn is_undef(self) -> bool {
+        self.x86_amx_value.is_undef()
+    }
+
+    pub fn print_to_stderr(self) {
+        self.x86_amx_value.print_to_stderr()
+    }
+
+    pub fn as_instruction(self) -> Option<InstructionValue<'ctx>> {
+        self.x86_amx_value.as_instruction()
+    }
+}
+
+impl AsValueRef for X86AMXValue<'_> {
+    fn as_value_ref(&self) -> LLVMValueRef {
+        self.x86_amx_value.value
+    }
+}
@@ -4,6 +4,9 @@ extern crate either;
 #[macro_use]
 extern crate inkwell_internals;
 
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+mod test_abi;
+mod test_analysis;
 #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
 mod test_attributes;
 mod test_basic_block;
@@ -0,0 +1,325 @@
+use inkwell::abi::{AbiTarget, CType, FunctionAbi, PassMode};
+use inkwell::context::Context;
+use inkwell::targets::{InitializationConfig, Target, TargetData, TargetMachine, TargetTriple};
+use inkwell::values::BasicValue;
+use inkwell::OptimizationLevel;
+
+#[test]
+fn test_abi_target_from_triple() {
+    let target = |triple| AbiTarget::from_triple(&TargetTriple::create(triple));
+
+    assert_eq!(target("x86_64-unknown-linux-gnu"), Some(AbiTarget::X86_64SysV));
+    assert_eq!(target("x86_64-apple-darwin"), Some(AbiTarget::X86_64SysV));
+    assert_eq!(target("aarch64-unknown-linux-gnu"), Some(AbiTarget::AArch64Aapcs));
+    assert_eq!(target("aarch64-apple-ios"), Some(AbiTarget::AArch64Darwin));
+    assert_eq!(target("x86_64-pc-windows-gnu"), None);
+    assert_eq!(target("riscv64gc-unknown-linux-gnu"), None);
+}
+
+#[test]
+fn test_x86_64_sysv_lowering() {
+    let context = Context::create();
+    let target_data = TargetData::create("e-m:e-i64:64-f80:128-n8:16:32:64-S128");
+    let i8_type = context.i8_type();
+    let i32_type = context.i32_type();
+    let i64_type = context.i64_type();
+    let f32_type = context.f32_type();
+    let f64_type = context.f64_type();
+    let two_ints = context.struct_type(&[i32_type.into(), i32_type.into()], false);
+    let mixed = context.struct_type(&[f32_type.into(), i32_type.into(), f64_type.into()], false);
+    let floats = context.struct_type(&[f32_type.array_type(3).into()], false);
+    let big = context.struct_type(&[i64_type.array_type(3).into()], false);
+    let tiny = context.struct_type(&[i8_type.into(), i8_type.into(), i8_type.into()], false);
+    let long_double = context.struct_type(&[context.x86_f80_type().into()], false);
+    let empty = context.struct_type(&[], false);
+    let params = [
+        CType::Struct(two_ints),
+        CType::Struct(mixed),
+        CType::Struct(floats),
+        CType::Struct(big),
+        CType::Struct(tiny),
+        CType::Struct(long_double),
+        CType::Struct(empty),
+        CType::SignedInt(i8_type),
+        CType::UnsignedInt(context.bool_type()),
+        CType::Float(f64_type),
+    ];
+    let abi = FunctionAbi::new(
+        &context,
+        AbiTarget::X86_64SysV,
+        &target_data,
+        Some(CType::Struct(big)),
+        &params,
+    );
+    let modes: Vec<PassMode> = abi.get_params().iter().map(|param| param.get_mode()).collect();
+
+    assert_eq!(
+        abi.get_return().unwrap().get_mode(),
+        PassMode::Indirect { byval: false, align: 8 }
+    );
+    assert_eq!(
+        modes,
+        [
+            PassMode::Coerce(i64_type.into()),
+            PassMode::Coerce(context.struct_type(&[i64_type.into(), f64_type.into()], false).into()),
+            PassMode::Coerce(
+                context
+                    .struct_type(&[f32_type.vec_type(2).into(), f32_type.into()], false)
+                    .into()
+            ),
+            PassMode::Indirect { byval: true, align: 8 },
+            PassMode::Coerce(context.custom_width_int_type(24).into()),
+            PassMode::Indirect { byval: true, align: 16 },
+            PassMode::Ignore,
+            PassMode::Extend,
+            PassMode::Extend,
+            PassMode::Direct,
+        ]
+    );
+
+    let fn_type = abi.get_signature().get_fn_type();
+
+    assert_eq!(
+        fn_type.to_string(),
+        "void ({ [3 x i64] }*, i64, i64, double, <2 x float>, float, { [3 x i64] }*, i24, { x86_fp80 }*, i8, i1, double)"
+    );
+
+    // Once the integer registers run out, structs which need them go on the stack
+    let pairs = [CType::Struct(context.struct_type(&[i64_type.into(), i64_type.into()], false)); 4];
+    let abi = FunctionAbi::new(&context, AbiTarget::X86_64SysV, &target_data, None, &pairs);
+    let modes: Vec<PassMode> = abi.get_params().iter().map(|param| param.get_mode()).collect();
+
+    assert!(matches!(modes[2], PassMode::Coerce(_)));
+    assert_eq!(modes[3], PassMode::Indirect { byval: true, align: 8 });
+
+    // Likewise for SSE registers, which fp128 uses up but x86_fp80 doesn't
+    let two_doubles = CType::Struct(context.struct_type(&[f64_type.into(), f64_type.into()], false));
+    let mut f128_params = vec![CType::Float(context.f128_type()); 7];
+    let mut f80_params = vec![CType::Float(context.x86_f80_type()); 7];
+
+    f128_params.push(two_doubles);
+    f80_params.push(two_doubles);
+
+    let abi = FunctionAbi::new(&context, AbiTarget::X86_64SysV, &target_data, None, &f128_params);
+
+    assert_eq!(
+        abi.get_params()[7].get_mode(),
+        PassMode::Indirect { byval: true, align: 8 }
+    );
+
+    let abi = FunctionAbi::new(&context, AbiTarget::X86_64SysV, &target_data, None, &f80_params);
+
+    assert!(matches!(abi.get_params()[7].get_mode(), PassMode::Coerce(_)));
+}
+
+#[test]
+fn test_aarch64_lowering() {
+    let context = Context::create();
+    let target_data = TargetData::create("e-m:e-i8:8:32-i16:16:32-i64:64-i128:128-n32:64-S128");
+    let i8_type = context.i8_type();
+    let i64_type = context.i64_type();
+    let f32_type = context.f32_type();
+    let f64_type = context.f64_type();
+    let hfa = context.struct_type(&[f32_type.into(), f32_type.array_type(3).into()], false);
+    let not_hfa = context.struct_type(&[f32_type.into(), f64_type.into()], false);
+    let tiny = context.struct_type(&[i8_type.into(), i8_type.into(), i8_type.into()], false);
+    let big = context.struct_type(&[i64_type.array_type(3).into()], false);
+    let aligned = context.struct_type(&[context.i128_type().into()], false);
+    let params = [
+        CType::Struct(hfa),
+        CType::Struct(not_hfa),
+        CType::Struct(tiny),
+        CType::Struct(big),
+        CType::Struct(aligned),
+        CType::UnsignedInt(i8_type),
+    ];
+
+    let abi = FunctionAbi::new(
+        &context,
+        AbiTarget::AArch64Aapcs,
+        &target_data,
+        Some(CType::Struct(tiny)),
+        &params,
+    );
+    let modes: Vec<PassMode> = abi.get_params().iter().map(|param| param.get_mode()).collect();
+
+    assert_eq!(
+        abi.get_return().unwrap().get_mode(),
+        PassMode::Coerce(context.custom_width_int_type(24).into())
+    );
+    assert_eq!(
+        modes,
+        [
+            PassMode::Coerce(f32_type.array_type(4).into()),
+            PassMode::Coerce(i64_type.array_type(2).into()),
+            PassMode::Coerce(i64_type.into()),
+            PassMode::Indirect { byval: false, align: 8 },
+            PassMode::Coerce(context.i128_type().into()),
+            PassMode::Direct,
+        ]
+    );
+    assert_eq!(
+        abi.get_signature().get_fn_type().to_string(),
+        "i24 ([4 x float], [2 x i64], i64, { [3 x i64] }*, i128, i8)"
+    );
+
+    let abi = FunctionAbi::new(
+        &context,
+        AbiTarget::AArch64Darwin,
+        &target_data,
+        Some(CType::Struct(big)),
+        &params[5..],
+    );
+
+    assert_eq!(abi.get_params()[0].get_mode(), PassMode::Extend);
+    assert_eq!(
+        abi.get_return().unwrap().get_mode(),
+        PassMode::Indirect { byval: false, align: 8 }
+    );
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Floats {
+    a: f32,
+    b: f32,
+    c: f32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Pair {
+    x: i32,
+    y: i64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Big {
+    values: [i64; 3],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Tiny {
+    bytes: [u8; 3],
+}
+
+extern "C" fn combine(floats: Floats, pair: Pair, big: Big, tiny: Tiny, small: i8) -> Big {
+    let tiny_sum: i64 = tiny.bytes.iter().map(|&byte| i64::from(byte)).sum();
+
+    Big {
+        values: [
+            (floats.a + floats.b + floats.c) as i64,
+            i64::from(pair.x) * pair.y,
+            big.values.iter().sum::<i64>() + tiny_sum + i64::from(small),
+        ],
+    }
+}
+
+// Calls a Rust function through a JIT compiled wrapper, so that both sides of the lowering are
+// checked against the host's C ABI
+#[test]
+fn test_abi_calls_on_host() {
+    let triple = TargetMachine::get_default_triple();
+    let target = match AbiTarget::from_triple(&triple) {
+        Some(target) => target,
+        None => return,
+    };
+
+    Target::initialize_native(&InitializationConfig::default()).unwrap();
+
+    let context = Context::create();
+    let module = context.create_module("abi");
+    let builder = context.create_builder();
+    let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    let target_data = execution_engine.get_target_data();
+    let i8_type = context.i8_type();
+    let i32_type = context.i32_type();
+    let i64_type = context.i64_type();
+    let f32_type = context.f32_type();
+    let floats = CType::Struct(context.struct_type(&[f32_type.array_type(3).into()], false));
+    let pair = CType::Struct(context.struct_type(&[i32_type.into(), i64_type.into()], false));
+    let big = CType::Struct(context.struct_type(&[i64_type.array_type(3).into()], false));
+    let tiny = CType::Struct(context.struct_type(&[i8_type.array_type(3).into()], false));
+    let params = [floats, pair, big, tiny, CType::SignedInt(i8_type)];
+    let abi = FunctionAbi::new(&context, target, target_data, Some(big), &params);
+    let combine_fn = module.add_function("combine", abi.get_signature(), None);
+    let wrapper_fn = module.add_function("combine_wrapper", abi.get_signature(), None);
+    let entry = context.append_basic_block(wrapper_fn, "entry");
+
+    builder.position_at_end(entry);
+
+    let args = abi.get_param_values(&builder, wrapper_fn);
+
+    assert_eq!(args.len(), 5);
+    assert_eq!(args[3].get_type(), tiny.get_llvm_type());
+
+    let result = abi.build_call(&builder, combine_fn, &args, "result").unwrap();
+
+    abi.build_return(&builder, wrapper_fn, Some(result));
+
+    // A function which only uses the lowering on its own side, swapping fields of a coerced struct
+    let swap_abi = FunctionAbi::new(&context, target, target_data, Some(floats), &[floats, tiny]);
+    let swap_fn = module.add_function("swap", swap_abi.get_signature(), None);
+    let entry = context.append_basic_block(swap_fn, "entry");
+
+    builder.position_at_end(entry);
+
+    let args = swap_abi.get_param_values(&builder, swap_fn);
+    let array = builder
+        .build_extract_value(args[0].into_struct_value(), 0, "array")
+        .unwrap()
+        .into_array_value();
+    let a = builder.build_extract_value(array, 0, "a").unwrap();
+    let c = builder.build_extract_value(array, 2, "c").unwrap();
+    let bytes = builder
+        .build_extract_value(args[1].into_struct_value(), 0, "bytes")
+        .unwrap()
+        .into_array_value();
+    let byte = builder.build_extract_value(bytes, 1, "byte").unwrap().into_int_value();
+    let byte = builder.build_unsigned_int_to_float(byte, f32_type, "byte_float");
+    let array = builder.build_insert_value(array, c, 0, "array").unwrap();
+    let array = builder.build_insert_value(array, a, 2, "array").unwrap();
+    let array = builder.build_insert_value(array, byte, 1, "array").unwrap();
+    let result = builder
+        .build_insert_value(args[0].into_struct_value(), array.as_basic_value_enum(), 0, "result")
+        .unwrap();
+
+    swap_abi.build_return(&builder, swap_fn, Some(result.as_basic_value_enum()));
+
+    assert!(module.verify().is_ok(), "{}", module.print_to_string().to_string());
+
+    execution_engine.add_global_mapping(&combine_fn, combine as usize);
+
+    let floats = Floats { a: 1.5, b: 2.5, c: 3.0 };
+    let pair = Pair { x: -3, y: 1 << 40 };
+    let big = Big { values: [1, 2, 3] };
+    let tiny = Tiny { bytes: [10, 20, 30] };
+
+    unsafe {
+        let wrapper = execution_engine
+            .get_function::<unsafe extern "C" fn(Floats, Pair, Big, Tiny, i8) -> Big>("combine_wrapper")
+            .unwrap();
+
+        assert_eq!(
+            wrapper.call(floats, pair, big, tiny, -7),
+            Big {
+                values: [7, -3 << 40, 59]
+            }
+        );
+
+        let swap = execution_engine
+            .get_function::<unsafe extern "C" fn(Floats, Tiny) -> Floats>("swap")
+            .unwrap();
+
+        assert_eq!(
+            swap.call(floats, tiny),
+            Floats {
+                a: 3.0,
+                b: 20.0,
+                c: 1.5
+            }
+        );
+    }
+}
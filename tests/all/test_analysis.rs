@@ -0,0 +1,403 @@
+use inkwell::analysis::{DominatorTree, LoopInfo, PostDominatorTree};
+use inkwell::context::Context;
+use inkwell::IntPredicate;
+
+#[test]
+fn test_dominator_trees() {
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[i32_type.into()], false);
+    let function = module.add_function("count", fn_type, None);
+
+    let dominator_tree = DominatorTree::compute(function);
+
+    assert_eq!(dominator_tree.get_root(), None);
+
+    let entry = context.append_basic_block(function, "entry");
+    let header = context.append_basic_block(function, "header");
+    let body = context.append_basic_block(function, "body");
+    let exit = context.append_basic_block(function, "exit");
+    let dead = context.append_basic_block(function, "dead");
+    let param = function.get_first_param().unwrap().into_int_value();
+    let one = i32_type.const_int(1, false);
+
+    builder.position_at_end(entry);
+
+    let start = builder.build_int_add(param, one, "start");
+
+    builder.build_unconditional_branch(header);
+    builder.position_at_end(header);
+
+    let i = builder.build_phi(i32_type, "i");
+    let i_value = i.as_basic_value().into_int_value();
+    let condition = builder.build_int_compare(IntPredicate::SLT, i_value, param, "condition");
+
+    builder.build_conditional_branch(condition, body, exit);
+    builder.position_at_end(body);
+
+    let next = builder.build_int_add(i_value, one, "next");
+
+    builder.build_unconditional_branch(header);
+    i.add_incoming(&[(&start, entry), (&next, body)]);
+    builder.position_at_end(exit);
+
+    let ret = builder.build_return(Some(&i_value));
+
+    builder.position_at_end(dead);
+
+    let dead_add = builder.build_int_add(param, one, "dead_add");
+
+    builder.build_unconditional_branch(exit);
+
+    let start = start.as_instruction().unwrap();
+    let i = i.as_instruction();
+    let condition = condition.as_instruction().unwrap();
+    let next = next.as_instruction().unwrap();
+    let dead_add = dead_add.as_instruction().unwrap();
+
+    let dominator_tree = DominatorTree::compute(function);
+
+    assert_eq!(dominator_tree.get_root(), Some(entry));
+    assert!(dominator_tree.is_reachable(exit));
+    assert!(!dominator_tree.is_reachable(dead));
+
+    assert_eq!(dominator_tree.idom(entry), None);
+    assert_eq!(dominator_tree.idom(header), Some(entry));
+    assert_eq!(dominator_tree.idom(body), Some(header));
+    assert_eq!(dominator_tree.idom(exit), Some(header));
+    assert_eq!(dominator_tree.idom(dead), None);
+    assert_eq!(dominator_tree.get_children(entry), vec![header]);
+    assert_eq!(dominator_tree.get_children(header), vec![body, exit]);
+    assert!(dominator_tree.get_children(dead).is_empty());
+
+    assert!(dominator_tree.dominates(entry, exit));
+    assert!(dominator_tree.dominates(header, header));
+    assert!(!dominator_tree.strictly_dominates(header, header));
+    assert!(dominator_tree.strictly_dominates(header, body));
+    assert!(!dominator_tree.dominates(body, exit));
+    assert!(!dominator_tree.dominates(exit, body));
+    // Unreachable blocks are dominated by everything, and dominate nothing
+    assert!(dominator_tree.dominates(body, dead));
+    assert!(!dominator_tree.dominates(dead, exit));
+
+    assert_eq!(dominator_tree.nearest_common_dominator(body, exit), Some(header));
+    assert_eq!(dominator_tree.nearest_common_dominator(entry, body), Some(entry));
+    assert_eq!(dominator_tree.nearest_common_dominator(dead, body), None);
+
+    assert!(dominator_tree.dominance_frontier(entry).is_empty());
+    assert_eq!(dominator_tree.dominance_frontier(header), vec![header]);
+    assert_eq!(dominator_tree.dominance_frontier(body), vec![header]);
+    assert!(dominator_tree.dominance_frontier(exit).is_empty());
+
+    assert!(dominator_tree.instruction_dominates(start, i));
+    assert!(!dominator_tree.instruction_dominates(next, i));
+    assert!(dominator_tree.instruction_dominates(i, condition));
+    assert!(!dominator_tree.instruction_dominates(condition, i));
+    assert!(!dominator_tree.instruction_dominates(condition, condition));
+    assert!(dominator_tree.instruction_dominates(condition, next));
+    assert!(dominator_tree.instruction_dominates(next, dead_add));
+    assert!(!dominator_tree.instruction_dominates(dead_add, ret));
+
+    // Phis use their operands at the end of the incoming blocks
+    assert!(dominator_tree.dominates_use(start, i.get_operand_use(0).unwrap()));
+    assert!(dominator_tree.dominates_use(next, i.get_operand_use(1).unwrap()));
+    assert!(dominator_tree.dominates_use(i, ret.get_operand_use(0).unwrap()));
+    assert!(dominator_tree.dominates_use(i, next.get_operand_use(0).unwrap()));
+    assert!(!dominator_tree.dominates_use(next, condition.get_operand_use(0).unwrap()));
+
+    let post_dominator_tree = PostDominatorTree::compute(function);
+
+    assert_eq!(post_dominator_tree.get_roots(), vec![exit]);
+    assert!(post_dominator_tree.is_reachable(dead));
+    assert_eq!(post_dominator_tree.idom(exit), None);
+    assert_eq!(post_dominator_tree.idom(header), Some(exit));
+    assert_eq!(post_dominator_tree.idom(entry), Some(header));
+    assert_eq!(post_dominator_tree.idom(body), Some(header));
+    assert_eq!(post_dominator_tree.idom(dead), Some(exit));
+    assert_eq!(post_dominator_tree.get_children(exit), vec![header, dead]);
+
+    assert!(post_dominator_tree.dominates(exit, entry));
+    assert!(post_dominator_tree.strictly_dominates(header, body));
+    assert!(!post_dominator_tree.dominates(body, entry));
+    assert_eq!(post_dominator_tree.nearest_common_dominator(body, dead), Some(exit));
+
+    // The loop body is control dependent on the header's branch
+    assert_eq!(post_dominator_tree.dominance_frontier(body), vec![header]);
+    assert_eq!(post_dominator_tree.dominance_frontier(header), vec![header]);
+    assert!(post_dominator_tree.dominance_frontier(entry).is_empty());
+
+    assert!(post_dominator_tree.instruction_dominates(ret, start));
+    assert!(post_dominator_tree.instruction_dominates(condition, i));
+    assert!(!post_dominator_tree.instruction_dominates(i, condition));
+    assert!(!post_dominator_tree.instruction_dominates(next, start));
+}
+
+#[test]
+fn test_post_dominators_without_single_exit() {
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let bool_type = context.bool_type();
+    let fn_type = context.void_type().fn_type(&[bool_type.into()], false);
+    let function = module.add_function("exits", fn_type, None);
+    let entry = context.append_basic_block(function, "entry");
+    let left = context.append_basic_block(function, "left");
+    let right = context.append_basic_block(function, "right");
+    let spin = context.append_basic_block(function, "spin");
+    let condition = function.get_first_param().unwrap().into_int_value();
+
+    builder.position_at_end(entry);
+    builder.build_conditional_branch(condition, left, right);
+    builder.position_at_end(left);
+    builder.build_return(None);
+    builder.position_at_end(right);
+    builder.build_conditional_branch(condition, spin, left);
+    builder.position_at_end(spin);
+    builder.build_unconditional_branch(spin);
+
+    let post_dominator_tree = PostDominatorTree::compute(function);
+
+    assert_eq!(post_dominator_tree.get_roots(), vec![left]);
+    assert!(!post_dominator_tree.is_reachable(spin));
+    assert_eq!(post_dominator_tree.idom(spin), None);
+    assert_eq!(post_dominator_tree.idom(right), Some(left));
+    assert_eq!(post_dominator_tree.idom(entry), Some(left));
+    assert!(post_dominator_tree.dominates(entry, spin));
+
+    builder.position_at_end(spin);
+    spin.get_terminator().unwrap().erase_from_basic_block();
+    builder.build_unreachable();
+
+    let post_dominator_tree = PostDominatorTree::compute(function);
+
+    assert_eq!(post_dominator_tree.get_roots(), vec![left, spin]);
+    assert_eq!(post_dominator_tree.idom(right), None);
+    assert_eq!(post_dominator_tree.idom(entry), None);
+    assert_eq!(post_dominator_tree.nearest_common_dominator(left, spin), None);
+    assert_eq!(post_dominator_tree.dominance_frontier(spin), vec![right]);
+}
+
+#[test]
+fn test_invoke_dominance() {
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[], false);
+    let may_throw = module.add_function("may_throw", fn_type, None);
+    let function = module.add_function("wrapper", fn_type, None);
+    let entry = context.append_basic_block(function, "entry");
+    let normal = context.append_basic_block(function, "normal");
+    let unwind = context.append_basic_block(function, "unwind");
+
+    builder.position_at_end(entry);
+
+    let call_site = builder.build_invoke(may_throw, &[], normal, unwind, "result");
+    let result = call_site.try_as_basic_value().left().unwrap().into_int_value();
+
+    builder.position_at_end(normal);
+
+    let phi = builder.build_phi(i32_type, "phi");
+    let sum = builder.build_int_add(result, result, "sum");
+
+    builder.build_return(Some(&sum));
+    builder.position_at_end(unwind);
+    builder.build_unconditional_branch(normal);
+    phi.add_incoming(&[(&result, entry), (&i32_type.const_zero(), unwind)]);
+
+    let invoke = entry.get_terminator().unwrap();
+    let phi = phi.as_instruction();
+    let sum = sum.as_instruction().unwrap();
+
+    // The normal destination can also be reached through the unwind block
+    let dominator_tree = DominatorTree::compute(function);
+
+    assert!(dominator_tree.dominates(entry, normal));
+    assert!(!dominator_tree.instruction_dominates(invoke, sum));
+    assert!(!dominator_tree.dominates_use(invoke, sum.get_operand_use(0).unwrap()));
+    assert!(dominator_tree.dominates_use(invoke, phi.get_operand_use(0).unwrap()));
+
+    unwind.get_terminator().unwrap().erase_from_basic_block();
+    builder.position_at_end(unwind);
+    builder.build_unreachable();
+    phi.erase_from_basic_block();
+
+    let dominator_tree = DominatorTree::compute(function);
+
+    assert!(dominator_tree.instruction_dominates(invoke, sum));
+    assert!(dominator_tree.dominates_use(invoke, sum.get_operand_use(1).unwrap()));
+    assert!(!dominator_tree.instruction_dominates(invoke, unwind.get_terminator().unwrap()));
+}
+
+#[test]
+fn test_loop_info() {
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let bool_type = context.bool_type();
+    let fn_type = context.void_type().fn_type(&[bool_type.into()], false);
+    let function = module.add_function("nested", fn_type, None);
+    let entry = context.append_basic_block(function, "entry");
+    let outer_header = context.append_basic_block(function, "outer_header");
+    let inner_header = context.append_basic_block(function, "inner_header");
+    let inner_body = context.append_basic_block(function, "inner_body");
+    let outer_latch = context.append_basic_block(function, "outer_latch");
+    let exit = context.append_basic_block(function, "exit");
+    let condition = function.get_first_param().unwrap().into_int_value();
+
+    builder.position_at_end(entry);
+    builder.build_unconditional_branch(outer_header);
+    builder.position_at_end(outer_header);
+    builder.build_conditional_branch(condition, inner_header, exit);
+    builder.position_at_end(inner_header);
+    builder.build_conditional_branch(condition, inner_body, outer_latch);
+    builder.position_at_end(inner_body);
+    builder.build_unconditional_branch(inner_header);
+    builder.position_at_end(outer_latch);
+    builder.build_unconditional_branch(outer_header);
+    builder.position_at_end(exit);
+    builder.build_return(None);
+
+    let loop_info = LoopInfo::compute(function);
+
+    assert_eq!(loop_info.get_loops().len(), 2);
+
+    let outer = &loop_info.get_loops()[0];
+    let inner = &loop_info.get_loops()[1];
+
+    assert_eq!(loop_info.get_top_level_loops(), vec![outer]);
+    assert_eq!(loop_info.get_sub_loops(outer), vec![inner]);
+    assert_eq!(loop_info.get_parent_loop(inner), Some(outer));
+    assert_eq!(loop_info.get_parent_loop(outer), None);
+    assert_eq!(
+        loop_info.get_back_edges(),
+        vec![(outer_latch, outer_header), (inner_body, inner_header)]
+    );
+
+    assert_eq!(outer.get_header(), outer_header);
+    assert_eq!(
+        outer.get_blocks(),
+        &[outer_header, inner_header, outer_latch, inner_body]
+    );
+    assert_eq!(outer.get_latch(), Some(outer_latch));
+    assert_eq!(outer.get_depth(), 1);
+    assert_eq!(outer.get_preheader(), Some(entry));
+    assert_eq!(outer.get_exiting_blocks(), vec![outer_header]);
+    assert_eq!(outer.get_exit_blocks(), vec![exit]);
+    assert!(outer.contains(inner_body));
+    assert!(!outer.contains(exit));
+
+    assert_eq!(inner.get_header(), inner_header);
+    assert_eq!(inner.get_blocks(), &[inner_header, inner_body]);
+    assert_eq!(inner.get_back_edges(), vec![(inner_body, inner_header)]);
+    assert_eq!(inner.get_depth(), 2);
+    // The outer header also branches to the exit, so it can't serve as a preheader
+    assert_eq!(inner.get_preheader(), None);
+    assert_eq!(inner.get_exiting_blocks(), vec![inner_header]);
+    assert_eq!(inner.get_exit_blocks(), vec![outer_latch]);
+
+    assert_eq!(loop_info.get_loop_for(inner_body), Some(inner));
+    assert_eq!(loop_info.get_loop_for(outer_latch), Some(outer));
+    assert_eq!(loop_info.get_loop_for(exit), None);
+    assert_eq!(loop_info.get_loop_depth(inner_header), 2);
+    assert_eq!(loop_info.get_loop_depth(outer_header), 1);
+    assert_eq!(loop_info.get_loop_depth(entry), 0);
+    assert!(loop_info.is_loop_header(inner_header));
+    assert!(!loop_info.is_loop_header(inner_body));
+}
+
+#[test]
+fn test_irreducible_cycles_are_not_loops() {
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let bool_type = context.bool_type();
+    let fn_type = context.void_type().fn_type(&[bool_type.into()], false);
+    let function = module.add_function("irreducible", fn_type, None);
+    let entry = context.append_basic_block(function, "entry");
+    let left = context.append_basic_block(function, "left");
+    let right = context.append_basic_block(function, "right");
+    let exit = context.append_basic_block(function, "exit");
+    let condition = function.get_first_param().unwrap().into_int_value();
+
+    builder.position_at_end(entry);
+    builder.build_conditional_branch(condition, left, right);
+    builder.position_at_end(left);
+    builder.build_conditional_branch(condition, right, exit);
+    builder.position_at_end(right);
+    builder.build_unconditional_branch(left);
+    builder.position_at_end(exit);
+    builder.build_return(None);
+
+    let loop_info = LoopInfo::compute(function);
+
+    assert!(loop_info.get_loops().is_empty());
+    assert!(loop_info.get_back_edges().is_empty());
+    assert_eq!(loop_info.get_loop_depth(left), 0);
+}
+
+#[llvm_versions(7.0..=latest)]
+#[test]
+fn test_loop_metadata() {
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let bool_type = context.bool_type();
+    let fn_type = context.void_type().fn_type(&[bool_type.into()], false);
+    let function = module.add_function("two_latches", fn_type, None);
+    let entry = context.append_basic_block(function, "entry");
+    let header = context.append_basic_block(function, "header");
+    let left = context.append_basic_block(function, "left");
+    let right = context.append_basic_block(function, "right");
+    let exit = context.append_basic_block(function, "exit");
+    let condition = function.get_first_param().unwrap().into_int_value();
+
+    builder.position_at_end(entry);
+    builder.build_unconditional_branch(header);
+    builder.position_at_end(header);
+    builder.build_conditional_branch(condition, left, right);
+    builder.position_at_end(left);
+    builder.build_conditional_branch(condition, header, exit);
+    builder.position_at_end(right);
+    builder.build_unconditional_branch(header);
+    builder.position_at_end(exit);
+    builder.build_return(None);
+
+    let loop_info = LoopInfo::compute(function);
+    let loop_ = &loop_info.get_loops()[0];
+
+    assert_eq!(loop_.get_latches(), &[right, left]);
+    assert_eq!(loop_.get_latch(), None);
+    assert_eq!(loop_.get_loop_metadata(), None);
+
+    let unroll_disable = context.metadata_node(&[context.metadata_string("llvm.loop.unroll.disable").into()]);
+    let loop_id = loop_.set_loop_properties(&[unroll_disable]).unwrap();
+    let loop_kind_id = context.get_kind_id("llvm.loop");
+
+    assert_eq!(loop_.get_loop_metadata(), Some(loop_id));
+    assert_eq!(left.get_terminator().unwrap().get_metadata(loop_kind_id), Some(loop_id));
+    assert_eq!(
+        right.get_terminator().unwrap().get_metadata(loop_kind_id),
+        Some(loop_id)
+    );
+    assert!(header.get_terminator().unwrap().get_metadata(loop_kind_id).is_none());
+    assert_eq!(loop_id.get_node_size(), 2);
+
+    let ir = module.print_to_string().to_string();
+
+    assert!(ir.contains("!llvm.loop !0"), "{}", ir);
+    assert!(ir.contains("!0 = distinct !{!0, !1}"), "{}", ir);
+    assert!(ir.contains("!1 = !{!\"llvm.loop.unroll.disable\"}"), "{}", ir);
+    assert!(module.verify().is_ok());
+
+    // Disagreeing latches have no loop ID
+    right
+        .get_terminator()
+        .unwrap()
+        .set_metadata(context.metadata_node(&[]), loop_kind_id)
+        .unwrap();
+
+    assert_eq!(loop_.get_loop_metadata(), None);
+}
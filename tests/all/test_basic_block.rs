@@ -225,3 +225,245 @@ fn test_get_address() {
     assert!(unsafe { entry_bb.get_address() }.is_none());
     assert!(unsafe { next_bb.get_address() }.is_some());
 }
+
+#[test]
+fn test_instruction_iterators() {
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[i32_type.into()], false);
+    let function = module.add_function("testing", fn_type, None);
+    let entry = context.append_basic_block(function, "entry");
+    let empty = context.append_basic_block(function, "empty");
+    let exit = context.append_basic_block(function, "exit");
+    let param = function.get_first_param().unwrap().into_int_value();
+
+    assert_eq!(empty.get_instructions().next(), None);
+    assert_eq!(empty.get_instructions().next_back(), None);
+
+    builder.position_at_end(entry);
+
+    let add = builder.build_int_add(param, param, "add");
+    let mul = builder.build_int_mul(param, param, "mul");
+    let sub = builder.build_int_sub(mul, param, "sub");
+    let br = builder.build_unconditional_branch(exit);
+
+    builder.position_at_end(exit);
+
+    let ret = builder.build_return(Some(&param));
+
+    let add = add.as_instruction().unwrap();
+    let mul = mul.as_instruction().unwrap();
+    let sub = sub.as_instruction().unwrap();
+
+    assert_eq!(entry.get_instructions().collect::<Vec<_>>(), vec![add, mul, sub, br]);
+    assert_eq!(
+        entry.get_instructions().rev().collect::<Vec<_>>(),
+        vec![br, sub, mul, add]
+    );
+
+    // Meeting in the middle yields every instruction exactly once
+    let mut instructions = entry.get_instructions();
+
+    assert_eq!(instructions.next(), Some(add));
+    assert_eq!(instructions.next_back(), Some(br));
+    assert_eq!(instructions.next_back(), Some(sub));
+    assert_eq!(instructions.next(), Some(mul));
+    assert_eq!(instructions.next(), None);
+    assert_eq!(instructions.next_back(), None);
+
+    assert_eq!(
+        function.get_instructions().collect::<Vec<_>>(),
+        vec![add, mul, sub, br, ret]
+    );
+
+    // Erasing the instruction just yielded doesn't disturb the iteration
+    for instruction in function.get_instructions() {
+        if instruction.get_first_use().is_none() && instruction.get_type().is_int_type() {
+            instruction.erase_from_basic_block();
+        }
+    }
+
+    assert_eq!(entry.get_instructions().collect::<Vec<_>>(), vec![mul, br]);
+
+    for instruction in entry.get_instructions().rev() {
+        if instruction == mul {
+            instruction.erase_from_basic_block();
+        }
+    }
+
+    assert_eq!(function.get_instructions().collect::<Vec<_>>(), vec![br, ret]);
+}
+
+#[test]
+fn test_cfg_queries() {
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = context.void_type().fn_type(&[i32_type.into()], false);
+    let function = module.add_function("loop", fn_type, None);
+
+    assert_eq!(function.get_preorder_blocks().count(), 0);
+
+    let entry = context.append_basic_block(function, "entry");
+    let header = context.append_basic_block(function, "header");
+    let body = context.append_basic_block(function, "body");
+    let exit = context.append_basic_block(function, "exit");
+    let dead = context.append_basic_block(function, "");
+    let param = function.get_first_param().unwrap().into_int_value();
+
+    builder.position_at_end(entry);
+    builder.build_unconditional_branch(header);
+    builder.position_at_end(header);
+    builder.build_switch(
+        param,
+        exit,
+        &[
+            (i32_type.const_int(1, false), body),
+            (i32_type.const_int(2, false), body),
+        ],
+    );
+    builder.position_at_end(body);
+    builder.build_unconditional_branch(header);
+    builder.position_at_end(exit);
+    builder.build_return(None);
+    builder.position_at_end(dead);
+    builder.build_unconditional_branch(exit);
+
+    assert_eq!(entry.get_successors(), vec![header]);
+    assert_eq!(header.get_successors(), vec![exit, body, body]);
+    assert!(exit.get_successors().is_empty());
+
+    let mut header_predecessors = header.get_predecessors();
+
+    header_predecessors.sort_by_key(|block| block.get_name().to_owned());
+
+    assert_eq!(header_predecessors, vec![body, entry]);
+    assert_eq!(body.get_predecessors(), vec![header, header]);
+    assert!(dead.get_predecessors().is_empty());
+    assert_eq!(exit.get_predecessors().len(), 2);
+
+    assert_eq!(
+        function.get_preorder_blocks().collect::<Vec<_>>(),
+        vec![entry, header, exit, body]
+    );
+    assert_eq!(
+        function.get_postorder_blocks().collect::<Vec<_>>(),
+        vec![exit, body, header, entry]
+    );
+    assert_eq!(
+        function.get_reverse_postorder_blocks().collect::<Vec<_>>(),
+        vec![entry, header, body, exit]
+    );
+    assert_eq!(function.get_reverse_postorder_blocks().rev().next(), Some(exit));
+    assert_eq!(function.get_reverse_postorder_blocks().len(), 4);
+
+    let dot = function.print_cfg_to_string();
+
+    assert!(dot.starts_with("digraph \"CFG for 'loop' function\" {\n    label=\"CFG for 'loop' function\";\n"));
+    assert!(dot.contains("    bb0 [shape=box,label=\"entry:\\lbr label %header\\l\"];\n    bb0 -> bb1;\n"));
+    assert!(
+        dot.contains("    bb1 -> bb3 [label=\"0\"];\n    bb1 -> bb2 [label=\"1\"];\n    bb1 -> bb2 [label=\"2\"];\n")
+    );
+    assert!(dot.contains("    bb4 [shape=box,label=\"bb4:\\lbr label %exit\\l\"];\n    bb4 -> bb3;\n"));
+    assert!(dot.ends_with("}\n"));
+
+    let dot = function.print_cfg_only_to_string();
+
+    assert!(dot.contains("    bb2 [shape=box,label=\"body:\"];\n    bb2 -> bb1;\n"));
+    assert!(!dot.contains("ret void"));
+}
+
+#[llvm_versions(3.9..=latest)]
+#[test]
+fn test_split_at() {
+    use std::convert::TryFrom;
+
+    use self::inkwell::values::PhiValue;
+    use self::inkwell::IntPredicate;
+
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[i32_type.into()], false);
+    let function = module.add_function("count", fn_type, None);
+    let entry = context.append_basic_block(function, "entry");
+    let body = context.append_basic_block(function, "body");
+    let exit = context.append_basic_block(function, "exit");
+    let param = function.get_first_param().unwrap().into_int_value();
+
+    builder.position_at_end(entry);
+    builder.build_unconditional_branch(body);
+    builder.position_at_end(body);
+
+    let counter = builder.build_phi(i32_type, "counter");
+    let counter_value = counter.as_basic_value().into_int_value();
+    let doubled = builder.build_int_add(counter_value, counter_value, "doubled");
+    let next = builder.build_int_add(doubled, i32_type.const_int(1, false), "next");
+    let done = builder.build_int_compare(IntPredicate::UGT, next, param, "done");
+
+    builder.build_conditional_branch(done, exit, body);
+    counter.add_incoming(&[(&i32_type.const_zero(), entry), (&next, body)]);
+    builder.position_at_end(exit);
+
+    let result = builder.build_phi(i32_type, "result");
+
+    result.add_incoming(&[(&next, body)]);
+    builder.build_return(Some(&result.as_basic_value()));
+
+    let doubled = doubled.as_instruction().unwrap();
+    let next = next.as_instruction().unwrap();
+    let kind_id = context.get_kind_id("split");
+    let note = context.metadata_node(&[context.metadata_string("kept").into()]);
+    let body_address = unsafe { body.get_address() }.unwrap();
+
+    result.as_instruction().set_metadata(note, kind_id).unwrap();
+
+    assert!(entry.split_at(next, "tail").is_err());
+    assert!(body.split_at(counter.as_instruction(), "tail").is_err());
+
+    let tail = body.split_at(next, "tail").unwrap();
+
+    assert_eq!(tail.get_name().to_str(), Ok("tail"));
+    assert_eq!(body.get_next_basic_block(), Some(tail));
+    assert_eq!(
+        body.get_instructions().map(|i| i.get_opcode()).collect::<Vec<_>>(),
+        vec![InstructionOpcode::Phi, InstructionOpcode::Add, InstructionOpcode::Br]
+    );
+    assert_eq!(tail.get_first_instruction(), Some(next));
+    assert_eq!(body.get_successors(), vec![tail]);
+    assert_eq!(tail.get_successors(), vec![exit, body]);
+
+    // The loop's back edge and the exit now come from the new block, updating the phis in place
+    assert_eq!(
+        PhiValue::try_from(body.get_first_instruction().unwrap()).unwrap(),
+        counter
+    );
+    assert_eq!(
+        PhiValue::try_from(exit.get_first_instruction().unwrap()).unwrap(),
+        result
+    );
+    assert_eq!(result.as_instruction().get_metadata(kind_id), Some(note));
+    assert_eq!(unsafe { body.get_address() }, Some(body_address));
+    assert_eq!(counter.get_name().to_str(), Ok("counter"));
+    assert_eq!(counter.get_incoming(0).unwrap().1, entry);
+    assert_eq!(counter.get_incoming(1).unwrap().1, tail);
+    assert_eq!(result.get_name().to_str(), Ok("result"));
+    assert_eq!(result.get_incoming(0).unwrap().1, tail);
+    assert_eq!(doubled.get_operand(0).unwrap().left(), Some(counter.as_basic_value()));
+    assert!(module.verify().is_ok(), "{}", module.print_to_string().to_string());
+
+    // Splitting at the terminator leaves only a branch behind it
+    let last = tail.split_at(tail.get_terminator().unwrap(), "last").unwrap();
+
+    assert_eq!(tail.get_next_basic_block(), Some(last));
+    assert_eq!(last.get_instructions().count(), 1);
+    assert_eq!(function.get_last_basic_block(), Some(exit));
+
+    assert_eq!(result.get_incoming(0).unwrap().1, last);
+    assert_eq!(result.as_instruction().get_metadata(kind_id), Some(note));
+    assert!(module.verify().is_ok(), "{}", module.print_to_string().to_string());
+}
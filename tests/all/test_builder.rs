@@ -1452,3 +1452,224 @@ fn test_safe_struct_gep() {
     assert!(builder.build_struct_gep(struct_ptr, 1, "struct_gep").is_ok());
     assert!(builder.build_struct_gep(struct_ptr, 2, "struct_gep").is_err());
 }
+
+#[llvm_versions(12.0..=latest)]
+#[test]
+fn test_build_vscale() {
+    let context = Context::create();
+    let module = context.create_module("vscale");
+    let builder = context.create_builder();
+    let i64_type = context.i64_type();
+    let fn_type = i64_type.fn_type(&[], false);
+    let function = module.add_function("f32_lanes", fn_type, None);
+
+    assert!(builder.build_vscale(i64_type, "vscale").is_err());
+
+    builder.position_at_end(context.append_basic_block(function, "entry"));
+
+    let vscale = builder.build_vscale(i64_type, "vscale").unwrap();
+    let lanes = builder.build_int_mul(vscale, i64_type.const_int(4, false), "lanes");
+
+    builder.build_return(Some(&lanes));
+
+    assert!(module.get_function("llvm.vscale.i64").is_some());
+
+    // A differently sized integer type declares another overload
+    let i32_type = context.i32_type();
+    let function = module.add_function("vscale_i32", i32_type.fn_type(&[], false), None);
+
+    builder.position_at_end(context.append_basic_block(function, "entry"));
+
+    let vscale = builder.build_vscale(i32_type, "vscale").unwrap();
+
+    builder.build_return(Some(&vscale));
+
+    assert_eq!(vscale.get_type(), i32_type);
+    assert!(module.get_function("llvm.vscale.i32").is_some());
+    assert!(module.verify().is_ok());
+}
+
+#[cfg(feature = "target-aarch64")]
+#[llvm_versions(12.0..=latest)]
+#[test]
+fn test_build_vscale_for_sve() {
+    use inkwell::targets::{CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetTriple};
+
+    Target::initialize_aarch64(&InitializationConfig::default());
+
+    let context = Context::create();
+    let module = context.create_module("sve");
+    let builder = context.create_builder();
+    let i64_type = context.i64_type();
+    let f32_type = context.f32_type();
+    let vec_type = f32_type.scalable_vec_type(4);
+    let vec_ptr_type = vec_type.ptr_type(AddressSpace::Generic);
+    let fn_type = i64_type.fn_type(&[vec_ptr_type.into(), vec_ptr_type.into()], false);
+    let function = module.add_function("double_vector", fn_type, None);
+
+    builder.position_at_end(context.append_basic_block(function, "entry"));
+
+    let src = function.get_nth_param(0).unwrap().into_pointer_value();
+    let dst = function.get_nth_param(1).unwrap().into_pointer_value();
+    let vector = builder.build_load(src, "vector").into_vector_value();
+    let doubled = builder.build_float_add(vector, vector, "doubled");
+
+    builder.build_store(dst, doubled);
+
+    let vscale = builder.build_vscale(i64_type, "vscale").unwrap();
+    let lanes = builder.build_int_mul(vscale, i64_type.const_int(4, false), "lanes");
+
+    builder.build_return(Some(&lanes));
+
+    assert!(module.verify().is_ok());
+
+    let triple = TargetTriple::create("aarch64-unknown-linux-gnu");
+    let target_machine = Target::from_triple(&triple)
+        .unwrap()
+        .create_target_machine(
+            &triple,
+            "generic",
+            "+sve",
+            OptimizationLevel::Default,
+            RelocMode::Default,
+            CodeModel::Default,
+        )
+        .unwrap();
+
+    module.set_triple(&triple);
+    module.set_data_layout(&target_machine.get_target_data().get_data_layout());
+
+    let buffer = target_machine
+        .write_to_memory_buffer(&module, FileType::Assembly)
+        .unwrap();
+    let assembly = std::str::from_utf8(buffer.as_slice()).unwrap();
+
+    assert!(assembly.contains("double_vector"));
+    assert!(assembly.contains("z0.s"));
+}
+
+#[test]
+fn test_typed_pointers() {
+    use inkwell::types::{IntType, StructType};
+    use inkwell::values::{PointerValue, TypedPointerValue};
+
+    let context = Context::create();
+    let module = context.create_module("typed_pointers");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let i64_type = context.i64_type();
+    let array_type = i32_type.array_type(4);
+    let struct_type = context.struct_type(&[i64_type.into(), array_type.into()], false);
+    let struct_ptr_type = struct_type.ptr_type(AddressSpace::Generic);
+    let fn_type = i64_type.fn_type(&[struct_ptr_type.into(), i64_type.into()], false);
+    let fn_value = module.add_function("sum_third", fn_type, None);
+    let entry = context.append_basic_block(fn_value, "entry");
+
+    builder.position_at_end(entry);
+
+    let param = fn_value.get_first_param().unwrap().into_pointer_value();
+
+    assert!(TypedPointerValue::new(param, array_type).is_err());
+
+    let struct_ptr = TypedPointerValue::new(param, struct_type).unwrap();
+
+    assert_eq!(struct_ptr.get_pointee_type(), struct_type);
+    assert_eq!(PointerValue::from(struct_ptr), param);
+    assert_eq!(
+        TypedPointerValue::try_from(param)
+            .unwrap()
+            .downcast::<StructType>()
+            .unwrap(),
+        struct_ptr
+    );
+    assert!(builder.build_typed_struct_gep(struct_ptr, 2, "oob").is_err());
+
+    // Adds the third array element to the first field, and returns the result
+    let total_ptr = builder.build_typed_struct_gep(struct_ptr, 0, "total_ptr").unwrap();
+    let total_ptr = total_ptr.downcast::<IntType>().unwrap();
+    let array_ptr = builder.build_typed_struct_gep(struct_ptr, 1, "array_ptr").unwrap();
+
+    assert!(array_ptr.downcast::<IntType>().is_err());
+
+    let array_ptr = array_ptr.downcast().unwrap();
+    let first_ptr = unsafe { builder.build_typed_array_gep(array_ptr, i64_type.const_zero(), "first_ptr") };
+    let first_ptr = first_ptr.downcast::<IntType>().unwrap();
+    let third_ptr = unsafe { builder.build_typed_gep(first_ptr, i32_type.const_int(2, false), "third_ptr") };
+
+    assert_eq!(third_ptr.get_pointee_type(), i32_type);
+
+    let third = builder.build_typed_load(third_ptr, "third").unwrap();
+    let third = builder.build_int_s_extend(third, i64_type, "third_ext");
+    let total = builder.build_typed_load(total_ptr, "total").unwrap();
+    let sum = builder.build_int_add(total, third, "sum");
+
+    assert!(builder.build_typed_store(third_ptr, sum).is_err());
+    assert!(builder.build_typed_store(total_ptr, sum).is_ok());
+
+    let local_ptr = builder.build_typed_alloca(i64_type, "local_ptr");
+
+    builder.build_typed_store(local_ptr, sum).unwrap();
+
+    let result = builder.build_typed_load(local_ptr, "result").unwrap();
+
+    builder.build_return(Some(&result));
+
+    assert!(fn_value.verify(true));
+
+    #[repr(C)]
+    struct Data {
+        total: i64,
+        array: [i32; 4],
+    }
+
+    let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    let mut data = Data {
+        total: 10,
+        array: [1, 2, -3, 4],
+    };
+
+    unsafe {
+        let sum_third = execution_engine
+            .get_function::<unsafe extern "C" fn(*mut Data, i64) -> i64>("sum_third")
+            .unwrap();
+
+        assert_eq!(sum_third.call(&mut data, 0), 7);
+    }
+
+    assert_eq!(data.total, 7);
+}
+
+#[test]
+fn test_unchecked_typed_pointers_use_pointee_type() {
+    use inkwell::values::TypedPointerValue;
+
+    let context = Context::create();
+    let module = context.create_module("unchecked");
+    let builder = context.create_builder();
+    let i8_type = context.i8_type();
+    let i32_type = context.i32_type();
+    let struct_type = context.struct_type(&[i8_type.into(), i32_type.into()], false);
+    let fn_type = i32_type.fn_type(&[i8_type.ptr_type(AddressSpace::Generic).into()], false);
+    let fn_value = module.add_function("unchecked", fn_type, None);
+
+    builder.position_at_end(context.append_basic_block(fn_value, "entry"));
+
+    // The parameter is an i8*, but is treated as pointing to the tracked types
+    let param = fn_value.get_first_param().unwrap().into_pointer_value();
+    let int_ptr = unsafe { TypedPointerValue::new_unchecked(param, i32_type) };
+    let struct_ptr = unsafe { TypedPointerValue::new_unchecked(param, struct_type) };
+
+    let next_ptr = unsafe { builder.build_typed_gep(int_ptr, i32_type.const_int(1, false), "next_ptr") };
+    let next = builder.build_typed_load(next_ptr, "next").unwrap();
+    let field_ptr = builder.build_typed_struct_gep(struct_ptr, 1, "field_ptr").unwrap();
+    let field_ptr = field_ptr.downcast::<inkwell::types::IntType>().unwrap();
+    let field = builder.build_typed_load(field_ptr, "field").unwrap();
+    let sum = builder.build_int_add(next, field, "sum");
+
+    assert_eq!(next.get_type(), i32_type);
+
+    builder.build_typed_store(int_ptr, sum).unwrap();
+    builder.build_return(Some(&sum));
+
+    assert!(module.verify().is_ok());
+}
@@ -415,3 +415,98 @@ fn test_global_expressions() {
         format!("expected !dbg but generated gv was {}", gv.print_to_string())
     );
 }
+
+#[test]
+fn test_dbg_value_operands() {
+    let context = Context::create();
+    let module = context.create_module("bin");
+    let builder = context.create_builder();
+    let (dibuilder, compile_unit) = module.create_debug_info_builder(
+        true,
+        DWARFSourceLanguage::C,
+        "source_file",
+        ".",
+        "my llvm compiler frontend",
+        false,
+        "",
+        0,
+        "",
+        DWARFEmissionKind::Full,
+        0,
+        false,
+        false,
+        #[cfg(any(
+            feature = "llvm11-0",
+            feature = "llvm12-0",
+            feature = "llvm13-0",
+            feature = "llvm14-0"
+        ))]
+        "",
+        #[cfg(any(
+            feature = "llvm11-0",
+            feature = "llvm12-0",
+            feature = "llvm13-0",
+            feature = "llvm14-0"
+        ))]
+        "",
+    );
+
+    let ditype = dibuilder
+        .create_basic_type(
+            "int",
+            32_u64,
+            0x05,
+            #[cfg(not(feature = "llvm7-0"))]
+            DIFlags::PUBLIC,
+        )
+        .unwrap();
+    let subroutine_type =
+        dibuilder.create_subroutine_type(compile_unit.get_file(), Some(ditype.as_type()), &[], DIFlags::PUBLIC);
+    let func_scope = dibuilder.create_function(
+        compile_unit.as_debug_info_scope(),
+        "main",
+        None,
+        compile_unit.get_file(),
+        0,
+        subroutine_type,
+        true,
+        true,
+        0,
+        DIFlags::PUBLIC,
+        false,
+    );
+
+    let i32_type = context.i32_type();
+    let fn_val = module.add_function("main", i32_type.fn_type(&[i32_type.into()], false), None);
+    fn_val.set_subprogram(func_scope);
+
+    let entry = context.append_basic_block(fn_val, "entry");
+    builder.position_at_end(entry);
+
+    let param = fn_val.get_first_param().unwrap();
+    let ret = builder.build_return(Some(&param));
+    let var = dibuilder.create_auto_variable(
+        func_scope.as_debug_info_scope(),
+        "x",
+        compile_unit.get_file(),
+        0,
+        ditype.as_type(),
+        true,
+        DIFlags::ZERO,
+        0,
+    );
+    let loc = dibuilder.create_debug_location(&context, 0, 0, func_scope.as_debug_info_scope(), None);
+    let dbg_value = dibuilder.insert_dbg_value_before(param, var, None, loc, ret);
+
+    dibuilder.finalize();
+
+    assert!(module.verify().is_ok());
+
+    // The value, variable and expression are all passed as metadata, leaving only the callee
+    let operands: Vec<_> = dbg_value.operands().collect();
+
+    assert_eq!(dbg_value.get_num_operands(), 4);
+    assert_eq!(operands.len(), 1);
+    assert!(operands[0].left().unwrap().is_pointer_value());
+    assert_eq!(dbg_value.operands().rev().count(), 1);
+}
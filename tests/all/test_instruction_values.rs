@@ -1,6 +1,6 @@
 use inkwell::context::Context;
 use inkwell::types::AnyTypeEnum;
-use inkwell::values::{BasicValue, InstructionOpcode::*};
+use inkwell::values::{AnyValue, BasicValue, BasicValueEnum, InstructionOpcode::*};
 use inkwell::{AddressSpace, AtomicOrdering, AtomicRMWBinOp, FloatPredicate, IntPredicate};
 
 #[test]
@@ -139,6 +139,164 @@ fn test_operands() {
     assert!(module.verify().is_ok());
 }
 
+#[test]
+fn test_operand_and_use_iterators() {
+    let context = Context::create();
+    let module = context.create_module("iters");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[i32_type.into(), i32_type.into()], false);
+    let function = module.add_function("select", fn_type, None);
+    let entry = context.append_basic_block(function, "entry");
+    let then_block = context.append_basic_block(function, "then");
+    let exit = context.append_basic_block(function, "exit");
+
+    builder.position_at_end(entry);
+
+    let x = function.get_nth_param(0).unwrap().into_int_value();
+    let y = function.get_nth_param(1).unwrap().into_int_value();
+    let square = builder.build_int_mul(x, x, "square");
+    let cond = builder.build_int_compare(IntPredicate::SLT, square, y, "cond");
+    let branch = builder.build_conditional_branch(cond, then_block, exit);
+
+    builder.position_at_end(then_block);
+    builder.build_unconditional_branch(exit);
+    builder.position_at_end(exit);
+
+    let phi = builder.build_phi(i32_type, "result");
+
+    phi.add_incoming(&[(&square, entry), (&y, then_block)]);
+    builder.build_return(Some(&phi.as_basic_value()));
+
+    // Operands, from both ends
+    let mul = square.as_instruction().unwrap();
+    let operands: Vec<BasicValueEnum> = mul.operands().map(|operand| operand.left().unwrap()).collect();
+
+    assert_eq!(operands, vec![BasicValueEnum::from(x); 2]);
+
+    let branch_operands: Vec<_> = branch.operands().collect();
+
+    assert_eq!(branch_operands.len(), 3);
+    assert_eq!(branch_operands[0].left(), Some(cond.into()));
+    assert_eq!(branch.operands().rev().count(), 3);
+    assert!(branch_operands
+        .iter()
+        .any(|operand| operand.right() == Some(then_block)));
+    assert!(branch_operands.iter().any(|operand| operand.right() == Some(exit)));
+
+    // Uses and users
+    assert_eq!(x.uses().count(), 2);
+    assert!(x.uses().all(|use_| use_.get_used_value().left() == Some(x.into())));
+    assert!(x.users().all(|user| user.as_instruction_value() == Some(mul)));
+    assert_eq!(square.users().count(), 2);
+    assert!(square
+        .users()
+        .any(|user| user.as_instruction_value() == Some(phi.as_instruction())));
+    assert_eq!(y.users().count(), 2);
+    assert_eq!(exit.users().count(), 2);
+    assert_eq!(then_block.uses().count(), 1);
+    assert_eq!(function.users().count(), 0);
+
+    // Rewriting uses while iterating over them visits every use
+    for use_ in y.uses() {
+        if let Some(instruction) = use_.get_user().as_instruction_value() {
+            assert!(instruction.replace_uses_of_with(y, x));
+        }
+    }
+
+    assert_eq!(y.uses().count(), 0);
+    assert_eq!(x.uses().count(), 4);
+
+    // Replacing operands is scoped to a single instruction
+    let cmp = cond.as_instruction().unwrap();
+
+    assert!(cmp.replace_uses_of_with(x, y));
+    assert!(!cmp.replace_uses_of_with(x, y));
+    assert!(!cmp.replace_uses_of_with(y, context.i64_type().const_zero()));
+    assert_eq!(x.uses().count(), 3);
+    assert!(module.verify().is_ok());
+}
+
+#[llvm_versions(3.9..=latest)]
+#[test]
+fn test_clone_and_move_instructions() {
+    let context = Context::create();
+    let module = context.create_module("moves");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[i32_type.into()], false);
+    let function = module.add_function("f", fn_type, None);
+    let entry = context.append_basic_block(function, "entry");
+    let exit = context.append_basic_block(function, "exit");
+
+    builder.position_at_end(entry);
+
+    let x = function.get_first_param().unwrap().into_int_value();
+    let sum = builder.build_int_add(x, x, "sum");
+    let product = builder.build_int_mul(x, x, "product");
+    let branch = builder.build_unconditional_branch(exit);
+
+    builder.position_at_end(exit);
+
+    let ret = builder.build_return(Some(&sum));
+
+    let add = sum.as_instruction().unwrap();
+    let mul = product.as_instruction().unwrap();
+
+    // Clones are unattached and unnamed until inserted
+    let add_copy = add.clone_instruction();
+
+    assert!(add_copy.get_parent().is_none());
+    assert_eq!(add_copy.get_name().to_str(), Ok(""));
+    assert_eq!(add_copy.get_opcode(), Add);
+    assert_eq!(
+        add_copy.operands().collect::<Vec<_>>(),
+        add.operands().collect::<Vec<_>>()
+    );
+
+    builder.position_before(&ret);
+    builder.insert_instruction(&add_copy, Some("sum_copy"));
+
+    assert_eq!(add_copy.get_parent(), Some(exit));
+    assert_eq!(add_copy.get_next_instruction(), Some(ret));
+
+    // Moving within a block and across blocks keeps names
+    assert!(mul.move_before(add).is_ok());
+    assert_eq!(entry.get_first_instruction(), Some(mul));
+    assert_eq!(mul.get_next_instruction(), Some(add));
+    assert!(mul.move_before(mul).is_ok());
+    assert_eq!(mul.get_next_instruction(), Some(add));
+
+    assert!(mul.move_before(ret).is_ok());
+    assert_eq!(mul.get_parent(), Some(exit));
+    assert_eq!(mul.get_name().to_str(), Ok("product"));
+    assert_eq!(entry.get_first_instruction(), Some(add));
+
+    // Unattached instructions can't be moved before
+    let mul_copy = mul.clone_instruction();
+
+    assert!(add.move_before(mul_copy).is_err());
+
+    // ... but can themselves be moved into place
+    assert!(mul_copy.move_before(branch).is_ok());
+    assert_eq!(mul_copy.get_parent(), Some(entry));
+
+    ret.move_to_end_of(exit);
+    branch.move_to_end_of(entry);
+
+    assert_eq!(exit.get_last_instruction(), Some(ret));
+    assert_eq!(entry.get_last_instruction(), Some(branch));
+
+    mul_copy.erase_from_basic_block();
+
+    assert!(module.verify().is_ok());
+
+    let ir = function.print_to_string().to_string();
+
+    assert!(ir.contains("%sum_copy = add i32 %0, %0"));
+    assert!(ir.contains("%product = mul i32 %0, %0"));
+}
+
 #[test]
 fn test_basic_block_operand() {
     let context = Context::create();
@@ -543,3 +543,740 @@ fn test_double_ee_from_same_module() {
 
     assert!(module.create_interpreter_execution_engine().is_err());
 }
+
+#[llvm_versions(7.0..=latest)]
+#[test]
+fn test_split() {
+    use inkwell::module::Linkage;
+    use inkwell::targets::{CodeModel, FileType, InitializationConfig, RelocMode, TargetMachine};
+    use inkwell::GlobalVisibility;
+
+    use std::thread;
+
+    let context = Context::create();
+    let module = context.create_module("my_module");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[], false);
+
+    let counter = module.add_global(i32_type, None, "counter");
+    counter.set_initializer(&i32_type.const_int(42, false));
+    counter.set_linkage(Linkage::Internal);
+
+    let helper = module.add_function("helper", fn_type, Some(Linkage::Internal));
+    builder.position_at_end(context.append_basic_block(helper, "entry"));
+    let load = builder.build_load(counter.as_pointer_value(), "load");
+    builder.build_return(Some(&load));
+
+    let entry = module.add_function("entry", fn_type, None);
+    builder.position_at_end(context.append_basic_block(entry, "entry"));
+    let call = builder
+        .build_call(helper, &[], "call")
+        .try_as_basic_value()
+        .left()
+        .unwrap();
+    builder.build_return(Some(&call));
+
+    let partitions = module.split(2).unwrap();
+
+    assert_eq!(partitions.len(), 2);
+
+    // The original module is left untouched
+    assert_eq!(module.get_function("helper").unwrap().get_linkage(), Linkage::Internal);
+    assert_eq!(module.get_global("counter").unwrap().get_linkage(), Linkage::Internal);
+
+    let mut definitions = Vec::new();
+
+    for partition in &partitions {
+        assert!(partition.verify().is_ok());
+
+        for name in &["helper", "entry"] {
+            let function = partition.get_function(name).unwrap();
+            let global = function.as_global_value();
+
+            assert_eq!(function.get_linkage(), Linkage::External);
+
+            if !global.is_declaration() {
+                definitions.push(*name);
+            }
+        }
+
+        let counter = partition.get_global("counter").unwrap();
+
+        assert_eq!(counter.get_linkage(), Linkage::External);
+        assert_eq!(counter.get_visibility(), GlobalVisibility::Hidden);
+
+        if !counter.is_declaration() {
+            definitions.push("counter");
+        }
+    }
+
+    definitions.sort_unstable();
+
+    assert_eq!(definitions, ["counter", "entry", "helper"]);
+    assert_eq!(
+        partitions[0]
+            .get_function("helper")
+            .unwrap()
+            .as_global_value()
+            .get_visibility(),
+        GlobalVisibility::Hidden
+    );
+
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let bitcodes: Vec<Vec<u8>> = partitions
+        .iter()
+        .map(|partition| partition.write_bitcode_to_memory().as_slice().to_vec())
+        .collect();
+
+    let workers: Vec<_> = bitcodes
+        .into_iter()
+        .map(|bitcode| {
+            thread::spawn(move || {
+                let context = Context::create();
+                let buffer = MemoryBuffer::create_from_memory_range_copy(&bitcode, "partition");
+                let module = Module::parse_bitcode_from_buffer(&buffer, &context).unwrap();
+                let triple = TargetMachine::get_default_triple();
+                let target = Target::from_triple(&triple).unwrap();
+                let target_machine = target
+                    .create_target_machine(
+                        &triple,
+                        "generic",
+                        "",
+                        OptimizationLevel::None,
+                        RelocMode::PIC,
+                        CodeModel::Default,
+                    )
+                    .unwrap();
+
+                target_machine
+                    .write_to_memory_buffer(&module, FileType::Object)
+                    .unwrap()
+                    .get_size()
+            })
+        })
+        .collect();
+
+    for worker in workers {
+        assert!(worker.join().unwrap() > 0);
+    }
+}
+
+#[llvm_versions(7.0..=latest)]
+#[test]
+fn test_split_keeps_appending_globals_in_first_partition() {
+    use inkwell::module::Linkage;
+
+    let context = Context::create();
+    let module = context.create_module("my_module");
+    let builder = context.create_builder();
+    let fn_type = context.void_type().fn_type(&[], false);
+
+    for name in &["init1", "init2", "init3"] {
+        let function = module.add_function(name, fn_type, None);
+
+        builder.position_at_end(context.append_basic_block(function, "entry"));
+        builder.build_return(None);
+        module.add_global_constructor(function, 65535, None);
+    }
+
+    let partitions = module.split(3).unwrap();
+
+    assert_eq!(partitions[0].get_global_constructors().len(), 3);
+    assert!(partitions[1].get_global("llvm.global_ctors").is_none());
+    assert!(partitions[2].get_global("llvm.global_ctors").is_none());
+
+    for partition in &partitions {
+        assert!(partition.verify().is_ok());
+        assert_eq!(
+            partition.get_function("init1").unwrap().get_linkage(),
+            Linkage::External
+        );
+    }
+}
+
+#[llvm_versions(9.0..=latest)]
+#[test]
+fn test_split_aliases_ifuncs_and_used_arrays() {
+    let context = Context::create();
+    let ir = b"
+        @data = global i32 1
+        @data_alias = alias i32, i32* @data
+        @registration = global i8 0
+        @llvm.used = appending global [2 x i8*] [i8* bitcast (i32* @data_alias to i8*), i8* @registration], section \"llvm.metadata\"
+        @llvm.compiler.used = appending global [1 x i8*] [i8* bitcast (i32 ()* @impl to i8*)], section \"llvm.metadata\"
+
+        @dispatch = ifunc i32 (), i32 ()* ()* @resolver
+
+        define i32 @impl() {
+            ret i32 0
+        }
+
+        define i32 ()* @resolver() {
+            ret i32 ()* @impl
+        }
+
+        define i32 @user() {
+            %1 = load i32, i32* @data_alias
+            %2 = call i32 @dispatch()
+            %3 = add i32 %1, %2
+            ret i32 %3
+        }
+    ";
+    let memory_buffer = MemoryBuffer::create_from_memory_range_copy(ir, "split");
+    let module = context.create_module_from_ir(memory_buffer).unwrap();
+
+    let partitions = module.split(4).unwrap();
+
+    let mut used = Vec::new();
+    let mut compiler_used = Vec::new();
+    let mut aliases = 0;
+    let mut ifuncs = 0;
+
+    for partition in &partitions {
+        assert!(partition.verify().is_ok());
+
+        let ir = partition.print_to_string().to_string();
+        let data = partition.get_global("data").unwrap();
+
+        // An alias only lives next to its aliasee, and is declared everywhere else
+        if ir.contains("@data_alias = alias") {
+            assert!(!data.is_declaration());
+
+            aliases += 1;
+        } else {
+            assert!(partition.get_global("data_alias").unwrap().is_declaration());
+        }
+
+        if ir.contains("@dispatch = ifunc") {
+            assert!(!partition
+                .get_function("resolver")
+                .unwrap()
+                .as_global_value()
+                .is_declaration());
+
+            ifuncs += 1;
+        } else {
+            assert!(partition
+                .get_function("dispatch")
+                .unwrap()
+                .as_global_value()
+                .is_declaration());
+        }
+
+        for global in partition.get_used() {
+            assert!(!global.is_declaration());
+
+            used.push(global.get_name().to_str().unwrap().to_owned());
+        }
+
+        for global in partition.get_compiler_used() {
+            assert!(!global.is_declaration());
+
+            compiler_used.push(global.get_name().to_str().unwrap().to_owned());
+        }
+    }
+
+    used.sort_unstable();
+
+    assert_eq!(aliases, 1);
+    assert_eq!(ifuncs, 1);
+    assert_eq!(used, ["data_alias", "registration"]);
+    assert_eq!(compiler_used, ["impl"]);
+}
+
+#[test]
+fn test_global_constructors_and_destructors() {
+    let context = Context::create();
+    let module = context.create_module("my_module");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let void_type = context.void_type();
+    let fn_type = void_type.fn_type(&[], false);
+
+    assert!(module.get_global_constructors().is_empty());
+    assert!(module.get_global_destructors().is_empty());
+
+    let counter = module.add_global(i32_type, None, "counter");
+    counter.set_initializer(&i32_type.const_zero());
+
+    let init = module.add_function("init", fn_type, None);
+    builder.position_at_end(context.append_basic_block(init, "entry"));
+    builder.build_store(counter.as_pointer_value(), i32_type.const_int(42, false));
+    builder.build_return(None);
+
+    // Constructors don't have to return void
+    let early_init = module.add_function("early_init", i32_type.fn_type(&[], false), None);
+    builder.position_at_end(context.append_basic_block(early_init, "entry"));
+    builder.build_return(Some(&i32_type.const_zero()));
+
+    let fini = module.add_function("fini", fn_type, None);
+    builder.position_at_end(context.append_basic_block(fini, "entry"));
+    builder.build_return(None);
+
+    module.add_global_constructor(init, 65535, None);
+    module.add_global_constructor(early_init, 100, Some(counter));
+    module.add_global_destructor(fini, 65535, None);
+
+    assert!(module.verify().is_ok());
+
+    let constructors = module.get_global_constructors();
+
+    assert_eq!(constructors.len(), 2);
+    assert_eq!(constructors[0].function, init);
+    assert_eq!(constructors[0].priority, 65535);
+    assert_eq!(constructors[0].associated_data, None);
+    assert_eq!(constructors[1].function, early_init);
+    assert_eq!(constructors[1].priority, 100);
+    assert_eq!(constructors[1].associated_data, Some(counter));
+
+    let destructors = module.get_global_destructors();
+
+    assert_eq!(destructors.len(), 1);
+    assert_eq!(destructors[0].function, fini);
+
+    let get = module.add_function("get", i32_type.fn_type(&[], false), None);
+    builder.position_at_end(context.append_basic_block(get, "entry"));
+    let value = builder.build_load(counter.as_pointer_value(), "value");
+    builder.build_return(Some(&value));
+
+    let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    execution_engine.run_static_constructors();
+
+    unsafe {
+        let get = execution_engine
+            .get_function::<unsafe extern "C" fn() -> i32>("get")
+            .unwrap();
+
+        assert_eq!(get.call(), 42);
+    }
+}
+
+#[test]
+fn test_global_constructors_upgrade_two_field_entries() {
+    use inkwell::module::Linkage;
+    use inkwell::AddressSpace;
+
+    let context = Context::create();
+    let module = context.create_module("my_module");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let early_init_type = i32_type.fn_type(&[], false);
+
+    let early_init = module.add_function("early_init", early_init_type, None);
+    builder.position_at_end(context.append_basic_block(early_init, "entry"));
+    builder.build_return(Some(&i32_type.const_zero()));
+
+    let init = module.add_function("init", context.void_type().fn_type(&[], false), None);
+    builder.position_at_end(context.append_basic_block(init, "entry"));
+    builder.build_return(None);
+
+    // An entry in the pre-3.5 format, whose function isn't a `void ()*`
+    let entry_type = context.struct_type(
+        &[i32_type.into(), early_init_type.ptr_type(AddressSpace::Generic).into()],
+        false,
+    );
+    let entry = entry_type.const_named_struct(&[
+        i32_type.const_int(100, false).into(),
+        early_init.as_global_value().as_pointer_value().into(),
+    ]);
+    let entries = entry_type.const_array(&[entry]);
+    let ctors = module.add_global(entries.get_type(), None, "llvm.global_ctors");
+    ctors.set_linkage(Linkage::Appending);
+    ctors.set_initializer(&entries);
+
+    module.add_global_constructor(init, 65535, None);
+
+    assert!(module.verify().is_ok());
+    assert!(module
+        .print_to_string()
+        .to_string()
+        .contains("void ()* bitcast (i32 ()* @early_init to void ()*)"));
+
+    let constructors = module.get_global_constructors();
+
+    assert_eq!(constructors.len(), 2);
+    assert_eq!(constructors[0].function, early_init);
+    assert_eq!(constructors[0].priority, 100);
+    assert_eq!(constructors[1].function, init);
+}
+
+#[test]
+fn test_used_and_compiler_used() {
+    use inkwell::module::Linkage;
+    use inkwell::passes::PassManager;
+
+    let context = Context::create();
+    let module = context.create_module("my_module");
+    let i32_type = context.i32_type();
+    let fn_type = context.void_type().fn_type(&[], false);
+
+    let table = module.add_global(i32_type, None, "table");
+    table.set_initializer(&i32_type.const_zero());
+    table.set_linkage(Linkage::Internal);
+    table.set_section("my_plugins");
+
+    let helper = module.add_function("helper", fn_type, Some(Linkage::Internal));
+    let builder = context.create_builder();
+    builder.position_at_end(context.append_basic_block(helper, "entry"));
+    builder.build_return(None);
+
+    let unused = module.add_global(i32_type, None, "unused");
+    unused.set_initializer(&i32_type.const_zero());
+    unused.set_linkage(Linkage::Internal);
+
+    assert!(module.get_used().is_empty());
+    assert!(module.get_compiler_used().is_empty());
+
+    module.add_used(table);
+    module.add_used(table);
+    module.add_used(helper.as_global_value());
+    module.add_compiler_used(unused);
+
+    assert_eq!(module.get_used(), vec![table, helper.as_global_value()]);
+    assert_eq!(module.get_compiler_used(), vec![unused]);
+
+    let used = module.get_global("llvm.used").unwrap();
+
+    assert_eq!(used.get_linkage(), Linkage::Appending);
+    assert_eq!(used.get_section().to_str(), Ok("llvm.metadata"));
+    assert!(module.verify().is_ok());
+
+    let pass_manager = PassManager::create(());
+
+    pass_manager.add_global_dce_pass();
+    pass_manager.run_on(&module);
+
+    assert!(module.get_global("table").is_some());
+    assert!(module.get_function("helper").is_some());
+    assert!(module.get_global("unused").is_some());
+
+    assert!(module.remove_compiler_used(unused));
+    assert!(!module.remove_compiler_used(unused));
+    assert!(module.get_global("llvm.compiler.used").is_none());
+
+    pass_manager.run_on(&module);
+
+    assert!(module.get_global("unused").is_none());
+
+    assert!(module.remove_used(table));
+    assert_eq!(module.get_used(), vec![helper.as_global_value()]);
+    assert!(module.verify().is_ok());
+}
+
+#[test]
+fn test_get_globals() {
+    let context = Context::create();
+    let module = context.create_module("my_module");
+    let i8_type = context.i8_type();
+    let fn_type = context.void_type().fn_type(&[], false);
+
+    assert_eq!(module.get_globals().count(), 0);
+    assert_eq!(module.get_global_values().count(), 0);
+
+    let function1 = module.add_function("function1", fn_type, None);
+    let global1 = module.add_global(i8_type, None, "global1");
+    let function2 = module.add_function("function2", fn_type, None);
+    let global2 = module.add_global(i8_type, None, "global2");
+
+    assert_eq!(module.get_globals().collect::<Vec<_>>(), vec![global1, global2]);
+    assert_eq!(
+        module.get_global_values().collect::<Vec<_>>(),
+        vec![
+            global1,
+            global2,
+            function1.as_global_value(),
+            function2.as_global_value()
+        ]
+    );
+}
+
+#[llvm_versions(9.0..=latest)]
+#[test]
+fn test_get_global_values_includes_aliases_and_ifuncs() {
+    let context = Context::create();
+    let ir = b"
+        @data = global i32 1
+        @data_alias = alias i32, i32* @data
+        @dispatch = ifunc void (), void ()* ()* @resolver
+
+        define void ()* @resolver() {
+            ret void ()* null
+        }
+    ";
+    let memory_buffer = MemoryBuffer::create_from_memory_range_copy(ir, "global_values");
+    let module = context.create_module_from_ir(memory_buffer).unwrap();
+
+    let names: Vec<_> = module
+        .get_global_values()
+        .map(|global| global.get_name().to_str().unwrap().to_owned())
+        .collect();
+
+    assert_eq!(names, ["data", "resolver", "data_alias", "dispatch"]);
+}
+
+#[llvm_versions(7.0..=latest)]
+#[test]
+fn test_get_named_metadata() {
+    let context = Context::create();
+    let module = context.create_module("my_module");
+    let i32_type = context.i32_type();
+
+    assert_eq!(module.get_named_metadata().count(), 0);
+
+    let md_node1 = context.metadata_node(&[i32_type.const_int(1, false).into()]);
+    let md_node2 = context.metadata_node(&[context.metadata_string("two").into()]);
+
+    module.add_global_metadata("first", &md_node1).unwrap();
+    module.add_global_metadata("second", &md_node1).unwrap();
+    module.add_global_metadata("second", &md_node2).unwrap();
+
+    let named_metadata: Vec<_> = module
+        .get_named_metadata()
+        .map(|(name, operands)| (name.into_string().unwrap(), operands))
+        .collect();
+
+    assert_eq!(
+        named_metadata,
+        vec![
+            ("first".to_owned(), vec![md_node1]),
+            ("second".to_owned(), vec![md_node1, md_node2])
+        ]
+    );
+}
+
+#[test]
+fn test_remove_global_values() {
+    use inkwell::module::RemoveGlobalError;
+    use inkwell::values::BasicValue;
+
+    let context = Context::create();
+    let module = context.create_module("my_module");
+    let other_module = context.create_module("other_module");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[], false);
+
+    // A global which refers to itself may still be removed
+    let i8_ptr_type = context.i8_type().ptr_type(inkwell::AddressSpace::Generic);
+    let self_ref = module.add_global(i8_ptr_type, None, "self_ref");
+    self_ref.set_initializer(&self_ref.as_pointer_value().const_cast(i8_ptr_type));
+
+    assert!(module.remove_global(self_ref).is_ok());
+    assert!(module.get_global("self_ref").is_none());
+
+    // So may a recursive function
+    let recursive = module.add_function("recursive", fn_type, None);
+    builder.position_at_end(context.append_basic_block(recursive, "entry"));
+    let call = builder
+        .build_call(recursive, &[], "call")
+        .try_as_basic_value()
+        .left()
+        .unwrap();
+    builder.build_return(Some(&call));
+
+    assert!(module.remove_function(recursive).is_ok());
+    assert!(module.get_function("recursive").is_none());
+
+    let global = module.add_global(i32_type, None, "global");
+    global.set_initializer(&i32_type.const_zero());
+
+    let callee = module.add_function("callee", fn_type, None);
+    builder.position_at_end(context.append_basic_block(callee, "entry"));
+    let load = builder.build_load(global.as_pointer_value(), "load");
+    builder.build_return(Some(&load));
+
+    let caller = module.add_function("caller", fn_type, None);
+    builder.position_at_end(context.append_basic_block(caller, "entry"));
+    let call = builder
+        .build_call(callee, &[], "call")
+        .try_as_basic_value()
+        .left()
+        .unwrap();
+    builder.build_return(Some(&call));
+
+    assert_eq!(other_module.remove_global(global), Err(RemoveGlobalError::NotInModule));
+    assert_eq!(
+        module.remove_global(global),
+        Err(RemoveGlobalError::HasUsers(vec![load.into()]))
+    );
+    assert_eq!(
+        module.remove_function(callee),
+        Err(RemoveGlobalError::HasUsers(vec![call.into()]))
+    );
+    assert!(module.get_global("global").is_some());
+    assert!(module.get_function("callee").is_some());
+
+    let other_global = module.add_global(i32_type, None, "other_global");
+    other_global.set_initializer(&i32_type.const_zero());
+
+    // Invalid replacements leave the module untouched
+    let foreign_global = other_module.add_global(i32_type, None, "foreign_global");
+
+    assert_eq!(
+        module.replace_and_remove(global, global),
+        Err(RemoveGlobalError::SelfReplacement)
+    );
+    assert_eq!(
+        module.replace_and_remove(global, foreign_global),
+        Err(RemoveGlobalError::NotInModule)
+    );
+    assert_eq!(
+        other_module.replace_and_remove(global, foreign_global),
+        Err(RemoveGlobalError::NotInModule)
+    );
+    assert_eq!(
+        load.as_instruction_value()
+            .unwrap()
+            .get_operand(0)
+            .unwrap()
+            .left()
+            .unwrap(),
+        global.as_pointer_value().as_basic_value_enum()
+    );
+    assert!(foreign_global.get_first_use().is_none());
+
+    assert!(module.replace_and_remove(global, other_global).is_ok());
+    assert!(module.get_global("global").is_none());
+    assert_eq!(
+        load.as_instruction_value()
+            .unwrap()
+            .get_operand(0)
+            .unwrap()
+            .left()
+            .unwrap(),
+        other_global.as_pointer_value().as_basic_value_enum()
+    );
+
+    // Functions of a different type are cast to fit
+    let replacement = module.add_function("replacement", i32_type.fn_type(&[i32_type.into()], false), None);
+
+    assert!(module
+        .replace_and_remove(callee.as_global_value(), replacement.as_global_value())
+        .is_ok());
+    assert!(module.get_function("callee").is_none());
+    assert!(replacement.as_global_value().get_first_use().is_some());
+    assert!(module.verify().is_ok());
+}
+
+#[test]
+fn test_add_function_with_signature() {
+    use self::inkwell::attributes::{Attribute, AttributeLoc};
+    use self::inkwell::types::{FunctionSignature, ParamAttribute};
+    use self::inkwell::AddressSpace;
+
+    let context = Context::create();
+    let module = context.create_module("signature");
+    let i8_type = context.i8_type();
+    let i32_type = context.i32_type();
+    let i8_ptr_type = i8_type.ptr_type(AddressSpace::Generic);
+    let struct_type = context.struct_type(&[i32_type.into(), i32_type.into()], false);
+    let struct_ptr_type = struct_type.ptr_type(AddressSpace::Generic);
+    let signature = FunctionSignature::new(i8_ptr_type)
+        .param_with_attributes(
+            "out",
+            struct_ptr_type,
+            &[ParamAttribute::StructRet(struct_type.into()), ParamAttribute::NoAlias],
+        )
+        .param_with_attributes("arg", struct_ptr_type, &[ParamAttribute::ByVal(struct_type.into())])
+        .param_with_attributes(
+            "buf",
+            i8_ptr_type,
+            &[ParamAttribute::NonNull, ParamAttribute::Align(16)],
+        )
+        .param_with_attributes("flag", i8_type, &[ParamAttribute::ZeroExt])
+        .param("", i32_type)
+        .return_attribute(ParamAttribute::NoAlias)
+        .call_conventions(8)
+        .var_args();
+    let fn_value = module.add_function("declared", &signature, None);
+
+    assert_eq!(fn_value.get_type(), signature.get_fn_type());
+    assert!(fn_value.get_type().is_var_arg());
+    assert_eq!(fn_value.get_call_conventions(), 8);
+
+    let params = fn_value.get_params();
+
+    assert_eq!(params[0].into_pointer_value().get_name().to_str(), Ok("out"));
+    assert_eq!(params[1].into_pointer_value().get_name().to_str(), Ok("arg"));
+    assert_eq!(params[2].into_pointer_value().get_name().to_str(), Ok("buf"));
+    assert_eq!(params[3].into_int_value().get_name().to_str(), Ok("flag"));
+    assert_eq!(params[4].into_int_value().get_name().to_str(), Ok(""));
+
+    let kind_id = Attribute::get_named_enum_kind_id;
+
+    assert_eq!(fn_value.count_attributes(AttributeLoc::Param(0)), 2);
+    assert!(fn_value
+        .get_enum_attribute(AttributeLoc::Param(0), kind_id("noalias"))
+        .is_some());
+    assert_eq!(
+        fn_value
+            .get_enum_attribute(AttributeLoc::Param(1), kind_id("byval"))
+            .unwrap()
+            .get_type_value(),
+        struct_type.into()
+    );
+    assert_eq!(
+        fn_value
+            .get_enum_attribute(AttributeLoc::Param(2), kind_id("align"))
+            .unwrap()
+            .get_enum_value(),
+        16
+    );
+    assert_eq!(fn_value.count_attributes(AttributeLoc::Param(3)), 1);
+    assert_eq!(fn_value.count_attributes(AttributeLoc::Param(4)), 0);
+    assert!(fn_value
+        .get_enum_attribute(AttributeLoc::Return, kind_id("noalias"))
+        .is_some());
+
+    let ir = fn_value.print_to_string().to_string();
+
+    assert!(ir.contains("declare fastcc noalias i8*"), "{}", ir);
+    assert!(ir.contains("sret({ i32, i32 })"), "{}", ir);
+    assert!(ir.contains("i8* nonnull align 16,"), "{}", ir);
+    assert!(ir.contains("i8 zeroext, i32, ..."), "{}", ir);
+
+    // A plain FunctionType still works, including special parameter and return types
+    let token_type = context.token_type();
+    let metadata_type = context.metadata_type();
+    let fn_type = token_type.fn_type(&[metadata_type.into(), i32_type.into()], false);
+    let fn_value = module.add_function("plain", fn_type, None);
+
+    assert_eq!(fn_value.get_type(), fn_type);
+    assert_eq!(fn_value.count_attributes(AttributeLoc::Return), 0);
+}
+
+#[test]
+fn test_add_function_keeps_existing_type() {
+    use self::inkwell::types::FunctionSignature;
+
+    let context = Context::create();
+    let ir = b"
+        declare x86_mmx @llvm.x86.mmx.padd.b(x86_mmx, x86_mmx)
+    ";
+    let memory_buffer = MemoryBuffer::create_from_memory_range_copy(ir, "mmx");
+    let module = context.create_module_from_ir(memory_buffer).unwrap();
+    let fn_type = module.get_function("llvm.x86.mmx.padd.b").unwrap().get_type();
+
+    // Types inkwell can't represent, such as x86_mmx, are copied as is
+    let other_module = context.create_module("other");
+    let function = other_module.add_function("padd", fn_type, None);
+
+    assert_eq!(function.get_type(), fn_type);
+    assert_eq!(FunctionSignature::from(fn_type).get_fn_type(), fn_type);
+
+    // Converted types can still be extended
+    let i32_type = context.i32_type();
+    let signature = FunctionSignature::from(i32_type.fn_type(&[i32_type.into()], false))
+        .param("y", i32_type)
+        .var_args();
+    let function = other_module.add_function("add", signature, None);
+
+    assert_eq!(
+        function.get_type(),
+        i32_type.fn_type(&[i32_type.into(), i32_type.into()], true)
+    );
+    assert_eq!(function.get_nth_param(1).unwrap().get_name().to_str(), Ok("y"));
+}
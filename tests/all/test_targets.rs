@@ -325,6 +325,64 @@ fn test_target_data() {
     TargetData::create("e-m:e-i64:64-f80:128-n8:16:32:64-S128");
 }
 
+#[test]
+fn test_struct_layout() {
+    let context = Context::create();
+    let target_data = TargetData::create("e-m:e-i64:64-f80:128-n8:16:32:64-S128");
+    let i8_type = context.i8_type();
+    let i32_type = context.i32_type();
+    let empty_type = context.struct_type(&[], false);
+    let field_types = [
+        i8_type.into(),
+        i32_type.into(),
+        i8_type.into(),
+        empty_type.into(),
+        context.i64_type().into(),
+    ];
+
+    let struct_type = context.struct_type(&field_types, false);
+    let layout = target_data.struct_layout(&struct_type).unwrap();
+
+    assert_eq!(layout.size(), 24);
+    assert_eq!(layout.alignment(), 8);
+    assert_eq!(layout.field_offsets(), &[0, 4, 8, 9, 16]);
+    assert_eq!(layout.field_offset(4), Some(16));
+    assert_eq!(layout.field_offset(5), None);
+    assert_eq!(layout.field_size(1), Some(4));
+    assert_eq!(layout.field_size(3), Some(0));
+    assert_eq!(layout.padding(), &[1..4, 9..16]);
+    assert_eq!(layout.field_at_offset(0), Some(0));
+    assert_eq!(layout.field_at_offset(2), None);
+    assert_eq!(layout.field_at_offset(7), Some(1));
+    assert_eq!(layout.field_at_offset(8), Some(2));
+    assert_eq!(layout.field_at_offset(9), None);
+    assert_eq!(layout.field_at_offset(23), Some(4));
+    assert_eq!(layout.field_at_offset(24), None);
+
+    for (index, &offset) in layout.field_offsets().iter().enumerate() {
+        assert_eq!(target_data.offset_of_element(&struct_type, index as u32), Some(offset));
+    }
+
+    let packed_type = context.struct_type(&[i8_type.into(), i32_type.into(), i8_type.into()], true);
+    let layout = target_data.struct_layout(&packed_type).unwrap();
+
+    assert_eq!(layout.size(), 6);
+    assert_eq!(layout.alignment(), 1);
+    assert_eq!(layout.field_offsets(), &[0, 1, 5]);
+    assert!(layout.padding().is_empty());
+    assert_eq!(layout.field_at_offset(3), Some(1));
+
+    let tail_padded_type = context.struct_type(&[i32_type.into(), i8_type.into()], false);
+    let layout = target_data.struct_layout(&tail_padded_type).unwrap();
+
+    assert_eq!(layout.size(), 8);
+    assert_eq!(layout.padding(), &[5..8]);
+
+    let opaque_type = context.opaque_struct_type("opaque");
+
+    assert!(target_data.struct_layout(&opaque_type).is_none());
+}
+
 #[test]
 fn test_ptr_sized_int() {
     Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
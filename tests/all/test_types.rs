@@ -403,3 +403,344 @@ fn test_no_vector_zero() {
     let int = context.i32_type();
     int.vec_type(0);
 }
+
+#[test]
+fn test_derive_llvm_type() {
+    use inkwell::types::LlvmType;
+    use inkwell::values::BasicValue;
+    use inkwell::OptimizationLevel;
+    use std::marker::PhantomData;
+
+    #[derive(LlvmType)]
+    #[repr(C)]
+    struct Inner {
+        flag: bool,
+        values: [u16; 3],
+    }
+
+    #[derive(LlvmType)]
+    #[repr(C)]
+    struct Outer<T> {
+        inner: Inner,
+        r#type: T,
+        ptr: *const u8,
+        _marker: PhantomData<T>,
+    }
+
+    #[derive(LlvmType)]
+    #[repr(C, packed)]
+    struct Packed(u8, u32);
+
+    let context = Context::create();
+    let i8_type = context.i8_type();
+    let i16_type = context.i16_type();
+    let f64_type = context.f64_type();
+    let i8_ptr_type = i8_type.ptr_type(AddressSpace::Generic);
+    let inner_type = Inner::struct_type(&context);
+    let outer_type = Outer::<f64>::struct_type(&context);
+
+    assert_eq!(
+        inner_type.get_field_types(),
+        &[i8_type.into(), i16_type.array_type(3).into()]
+    );
+    assert_eq!(
+        outer_type.get_field_types(),
+        &[
+            inner_type.into(),
+            f64_type.into(),
+            i8_ptr_type.into(),
+            context.struct_type(&[], false).into()
+        ]
+    );
+    assert_eq!(Outer::<f64>::llvm_type(&context), outer_type.into());
+    assert_eq!(Inner::FIELD_FLAG, 0);
+    assert_eq!(Inner::FIELD_VALUES, 1);
+    assert_eq!(Outer::<f64>::FIELD_TYPE, 1);
+    assert_eq!(Outer::<f64>::FIELD_PTR, 2);
+    assert!(Packed::struct_type(&context).is_packed());
+    assert_eq!(Packed::FIELD_1, 1);
+
+    let outer = Outer {
+        inner: Inner {
+            flag: true,
+            values: [1, 2, 3],
+        },
+        r#type: 4.5f64,
+        ptr: std::ptr::null(),
+        _marker: PhantomData,
+    };
+    let outer_const = outer.const_struct(&context);
+
+    assert_eq!(outer_const.get_type(), outer_type);
+    assert!(Packed(1, 2).const_struct(&context).get_type().is_packed());
+
+    // Arrays up to 32 elements, and floats copied bit for bit
+    let signaling_nan = f32::from_bits(0x7F80_0001).llvm_const(&context).into_float_value();
+
+    assert_eq!(<[u8; 32]>::llvm_type(&context), i8_type.array_type(32).into());
+    assert_eq!(
+        [7u16; 32].llvm_const(&context).get_type(),
+        i16_type.array_type(32).into()
+    );
+    assert_eq!(signaling_nan.get_bits(), Some(vec![0x7F80_0001]));
+
+    // The generated code must agree with Rust on the layout
+    let module = context.create_module("derive");
+    let builder = context.create_builder();
+    let fn_type = i16_type.fn_type(&[outer_type.ptr_type(AddressSpace::Generic).into()], false);
+    let function = module.add_function("get_second_value", fn_type, None);
+
+    builder.position_at_end(context.append_basic_block(function, "entry"));
+
+    let outer_ptr = function.get_nth_param(0).unwrap().into_pointer_value();
+    let inner_ptr = builder
+        .build_struct_gep(outer_ptr, Outer::<f64>::FIELD_INNER, "inner")
+        .unwrap();
+    let values_ptr = builder
+        .build_struct_gep(inner_ptr, Inner::FIELD_VALUES, "values")
+        .unwrap();
+    let value_ptr = unsafe {
+        builder.build_in_bounds_gep(
+            values_ptr,
+            &[context.i32_type().const_zero(), context.i32_type().const_int(1, false)],
+            "value_ptr",
+        )
+    };
+    let value = builder.build_load(value_ptr, "value");
+
+    builder.build_return(Some(&value));
+
+    let global = module.add_global(outer_type, None, "outer");
+
+    global.set_initializer(&outer_const.as_basic_value_enum());
+
+    assert!(module.verify().is_ok());
+
+    let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    unsafe {
+        let get_second_value = execution_engine
+            .get_function::<unsafe extern "C" fn(*const Outer<f64>) -> u16>("get_second_value")
+            .unwrap();
+
+        assert_eq!(get_second_value.call(&outer), 2);
+    }
+
+    let target_data = execution_engine.get_target_data();
+
+    assert_eq!(
+        target_data.get_abi_size(&outer_type),
+        std::mem::size_of::<Outer<f64>>() as u64
+    );
+    assert_eq!(
+        target_data.get_abi_size(&Packed::struct_type(&context)),
+        std::mem::size_of::<Packed>() as u64
+    );
+}
+
+#[llvm_versions(12.0..=latest)]
+#[test]
+fn test_scalable_vec_type() {
+    use inkwell::types::AnyType;
+
+    let context = Context::create();
+    let i32_type = context.i32_type();
+    let f64_type = context.f64_type();
+    let i8_ptr_type = context.i8_type().ptr_type(AddressSpace::Generic);
+    let fixed_vec_type = i32_type.vec_type(4);
+    let scalable_vec_type = i32_type.scalable_vec_type(4);
+
+    assert!(!fixed_vec_type.is_scalable());
+    assert!(scalable_vec_type.is_scalable());
+    assert_ne!(fixed_vec_type, scalable_vec_type);
+    assert_eq!(scalable_vec_type.get_size(), 4);
+    assert_eq!(scalable_vec_type.get_element_type().into_int_type(), i32_type);
+    assert_eq!(scalable_vec_type.print_to_string().to_str(), Ok("<vscale x 4 x i32>"));
+    assert!(scalable_vec_type.is_sized());
+    assert!(f64_type.scalable_vec_type(2).is_scalable());
+    assert!(i8_ptr_type.scalable_vec_type(1).is_scalable());
+
+    // The size of a scalable vector depends on vscale, so it can't fold into an integer
+    assert_eq!(fixed_vec_type.size_of().unwrap().get_zero_extended_constant(), None);
+    assert_eq!(scalable_vec_type.size_of().unwrap().get_zero_extended_constant(), None);
+    assert!(scalable_vec_type
+        .size_of()
+        .unwrap()
+        .print_to_string()
+        .to_str()
+        .unwrap()
+        .contains("<vscale x 4 x i32>"));
+    assert!(scalable_vec_type.const_zero().is_null());
+}
+
+#[llvm_versions(12.0..=latest)]
+#[test]
+fn test_special_types() {
+    use inkwell::types::{AnyType, AnyTypeEnum, BasicMetadataTypeEnum};
+    use inkwell::values::AnyValue;
+
+    let context = Context::create();
+    let bf16_type = context.bf16_type();
+    let token_type = context.token_type();
+    let label_type = context.label_type();
+    let x86_amx_type = context.x86_amx_type();
+
+    assert_eq!(bf16_type.print_to_string().to_str(), Ok("bfloat"));
+    assert_eq!(
+        bf16_type.const_float(1.5).print_to_string().to_str(),
+        Ok("bfloat 0xR3FC0")
+    );
+    assert!(bf16_type.as_any_type_enum().is_float_type());
+    assert_ne!(bf16_type, context.f16_type());
+
+    assert_eq!(token_type.as_any_type_enum(), AnyTypeEnum::TokenType(token_type));
+    assert_eq!(label_type.as_any_type_enum(), AnyTypeEnum::LabelType(label_type));
+    assert_eq!(x86_amx_type.as_any_type_enum(), AnyTypeEnum::X86AMXType(x86_amx_type));
+    assert_eq!(token_type.print_to_string().to_str(), Ok("token"));
+    assert_eq!(label_type.print_to_string().to_str(), Ok("label"));
+    assert_eq!(x86_amx_type.print_to_string().to_str(), Ok("x86_amx"));
+    assert_eq!(token_type.as_any_type_enum().size_of(), None);
+    assert!(BasicMetadataTypeEnum::from(token_type).is_token_type());
+
+    let token_none = token_type.const_none();
+
+    assert!(token_none.is_none());
+    assert_eq!(token_none.get_type(), token_type);
+    assert!(token_none.as_any_value_enum().is_token_value());
+
+    let fn_type = token_type.fn_type(&[token_type.into(), x86_amx_type.into()], false);
+
+    assert_eq!(fn_type.get_return_type(), None);
+    assert_eq!(fn_type.count_param_types(), 2);
+}
+
+#[llvm_versions(9.0..=latest)]
+#[test]
+fn test_token_values() {
+    use inkwell::intrinsics::Intrinsic;
+    use inkwell::values::AnyValue;
+
+    let context = Context::create();
+    let module = context.create_module("coroutine");
+    let builder = context.create_builder();
+    let i8_ptr_type = context.i8_type().ptr_type(AddressSpace::Generic);
+    let i32_type = context.i32_type();
+    let function = module.add_function("coroutine", i8_ptr_type.fn_type(&[], false), None);
+
+    builder.position_at_end(context.append_basic_block(function, "entry"));
+
+    let coro_id = Intrinsic::find("llvm.coro.id")
+        .unwrap()
+        .get_declaration(&module, &[])
+        .unwrap();
+    let coro_begin = Intrinsic::find("llvm.coro.begin")
+        .unwrap()
+        .get_declaration(&module, &[])
+        .unwrap();
+    let null = i8_ptr_type.const_null();
+    let call = builder.build_call(
+        coro_id,
+        &[i32_type.const_zero().into(), null.into(), null.into(), null.into()],
+        "id",
+    );
+    let id = call
+        .try_as_basic_value()
+        .right()
+        .unwrap()
+        .as_any_value_enum()
+        .into_token_value();
+
+    assert!(!id.is_none());
+    assert_eq!(id.get_name().to_str(), Ok("id"));
+    assert!(id.as_instruction().is_some());
+
+    let handle = builder
+        .build_call(coro_begin, &[id.into(), null.into()], "handle")
+        .try_as_basic_value()
+        .left()
+        .unwrap();
+
+    builder.build_return(Some(&handle));
+
+    assert!(module.verify().is_ok());
+}
+
+#[test]
+fn test_type_display_and_structural_equality() {
+    use inkwell::types::{AnyType, AnyTypeEnum, BasicTypeEnum};
+
+    let context = Context::create();
+    let other_context = Context::create();
+    let i32_type = context.i32_type();
+    let f64_type = context.f64_type();
+
+    assert_eq!(i32_type.to_string(), "i32");
+    assert_eq!(AnyTypeEnum::from(f64_type).to_string(), "double");
+    assert_eq!(
+        BasicTypeEnum::from(i32_type.ptr_type(AddressSpace::Generic)).to_string(),
+        "i32*"
+    );
+    assert_eq!(
+        context.void_type().fn_type(&[i32_type.into()], true).to_string(),
+        "void (i32, ...)"
+    );
+    assert_eq!(f64_type.vec_type(2).array_type(3).to_string(), "[3 x <2 x double>]");
+    assert_eq!(context.metadata_type().to_string(), "metadata");
+
+    let named = context.opaque_struct_type("named");
+
+    named.set_body(&[i32_type.into(), f64_type.into()], false);
+
+    assert_eq!(named.to_string(), "%named = type { i32, double }");
+    assert_eq!(named.print_to_string().to_str(), Ok(named.to_string().as_str()));
+
+    // Struct names are ignored, but not the layout
+    let literal = context.struct_type(&[i32_type.into(), f64_type.into()], false);
+    let packed = context.struct_type(&[i32_type.into(), f64_type.into()], true);
+    let other_context_named = other_context.opaque_struct_type("other_name");
+
+    other_context_named.set_body(
+        &[other_context.i32_type().into(), other_context.f64_type().into()],
+        false,
+    );
+
+    assert!(named.is_structurally_equal(&literal));
+    assert!(named.is_structurally_equal(&other_context_named));
+    assert!(!named.is_structurally_equal(&packed));
+    assert!(!named.is_structurally_equal(&context.opaque_struct_type("opaque")));
+    assert!(!i32_type.is_structurally_equal(&context.i64_type()));
+    assert!(!i32_type
+        .ptr_type(AddressSpace::Generic)
+        .is_structurally_equal(&i32_type.ptr_type(AddressSpace::Global)));
+    assert!(!i32_type.vec_type(4).is_structurally_equal(&i32_type.array_type(4)));
+    assert_eq!(named.structural_hash(), literal.structural_hash());
+    assert_eq!(named.structural_hash(), other_context_named.structural_hash());
+    assert_ne!(named.structural_hash(), packed.structural_hash());
+
+    // Recursive structs must terminate
+    let list = context.opaque_struct_type("list");
+    let other_list = context.opaque_struct_type("other_list");
+    let tree = context.opaque_struct_type("tree");
+
+    list.set_body(&[i32_type.into(), list.ptr_type(AddressSpace::Generic).into()], false);
+    other_list.set_body(
+        &[i32_type.into(), other_list.ptr_type(AddressSpace::Generic).into()],
+        false,
+    );
+    tree.set_body(
+        &[
+            i32_type.into(),
+            tree.ptr_type(AddressSpace::Generic).into(),
+            tree.ptr_type(AddressSpace::Generic).into(),
+        ],
+        false,
+    );
+
+    assert!(list.is_structurally_equal(&other_list));
+    assert!(!list.is_structurally_equal(&tree));
+    assert_eq!(list.structural_hash(), other_list.structural_hash());
+    assert_ne!(list.structural_hash(), tree.structural_hash());
+
+    // The hash is stable, so it may be persisted
+    assert_eq!(i32_type.structural_hash(), 0x3cda_070d_590f_6424);
+}
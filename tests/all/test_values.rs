@@ -1,10 +1,14 @@
-use inkwell::attributes::AttributeLoc;
+use inkwell::attributes::{Attribute, AttributeLoc};
 #[llvm_versions(7.0..=latest)]
 use inkwell::comdat::ComdatSelectionKind;
 use inkwell::context::Context;
 use inkwell::module::Linkage::*;
+use inkwell::targets::TargetData;
 use inkwell::types::{AnyType, StringRadix, VectorType};
-use inkwell::values::{AnyValue, BasicValue, CallableValue, InstructionOpcode::*, FIRST_CUSTOM_METADATA_KIND_ID};
+use inkwell::values::{
+    AnyValue, AnyValueEnum, ArgumentValue, ArrayValue, BasicValue, BasicValueEnum, CallableValue, InstructionOpcode::*,
+    FIRST_CUSTOM_METADATA_KIND_ID,
+};
 use inkwell::{AddressSpace, DLLStorageClass, FloatPredicate, GlobalVisibility, ThreadLocalMode};
 
 use std::convert::TryFrom;
@@ -202,6 +206,61 @@ fn test_set_get_name() {
     // TODO: Test globals, supposedly constant globals work?
 }
 
+#[test]
+fn test_uniform_naming() {
+    fn rename<'ctx, V: BasicValue<'ctx>>(value: V, name: &str) -> String {
+        value.set_name(name);
+        value.get_name().to_string_lossy().into_owned()
+    }
+
+    let context = Context::create();
+    let module = context.create_module("naming");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[i32_type.into()], false);
+    let function = module.add_function("add_one", fn_type, None);
+    let entry = context.append_basic_block(function, "entry");
+
+    builder.position_at_end(entry);
+
+    let param = function.get_first_param().unwrap();
+    let sum = builder.build_int_add(param.into_int_value(), i32_type.const_int(1, false), "sum");
+    let instruction = sum.as_instruction_value().unwrap();
+    let ret = builder.build_return(Some(&sum));
+    let global = module.add_global(i32_type, None, "counter");
+
+    // Params and enums
+    assert_eq!(param.get_name().to_str(), Ok(""));
+    param.set_name("x");
+    assert_eq!(param.get_name().to_str(), Ok("x"));
+    assert_eq!(rename(param, "y"), "y");
+
+    // Instructions
+    assert_eq!(instruction.get_name().to_str(), Ok("sum"));
+    instruction.set_name("total");
+    assert_eq!(sum.get_name().to_str(), Ok("total"));
+    assert_eq!(AnyValue::get_name(&instruction).to_str(), Ok("total"));
+
+    // Instructions without a result can't be named
+    ret.set_name("ret");
+    assert_eq!(ret.get_name().to_str(), Ok(""));
+
+    // Globals
+    assert_eq!(rename(global, "count"), "count");
+    assert_eq!(global.get_name().to_str(), Ok("count"));
+    assert!(module.get_global("count").is_some());
+
+    // Constants stay unnamed
+    assert_eq!(rename(i32_type.const_int(1, false), "one"), "");
+
+    // Basic blocks
+    entry.set_name("start");
+    assert_eq!(entry.get_name().to_str(), Ok("start"));
+
+    assert!(module.verify().is_ok());
+    assert!(function.print_to_string().to_string().contains("start:"));
+}
+
 #[test]
 fn test_undef() {
     let context = Context::create();
@@ -1342,3 +1401,348 @@ fn test_constant_expression() {
     assert!(expr.is_const());
     assert!(!expr.is_constant_int());
 }
+
+#[test]
+fn test_constant_data_arrays() {
+    let context = Context::create();
+    let module = context.create_module("tables");
+    let i8_type = context.i8_type();
+    let i16_type = context.i16_type();
+    let i64_type = context.i64_type();
+
+    let bytes = ArrayValue::from_slice(&context, b"inkwell");
+    let shorts = ArrayValue::from_slice(&context, &[1u16, 0xFFFF]);
+    let ints = ArrayValue::from_slice(&context, &[3u32, 1, 4, 1, 5]);
+    let longs = ArrayValue::from_slice(&context, &[u64::MAX, 0]);
+    let floats = ArrayValue::from_slice(&context, &[1.5f32, -0.0]);
+    let doubles = ArrayValue::from_slice(&context, &[std::f64::consts::PI]);
+
+    assert_eq!(bytes.get_type(), i8_type.array_type(7));
+    assert_eq!(shorts.get_type(), i16_type.array_type(2));
+    assert_eq!(longs.get_type(), i64_type.array_type(2));
+    assert_eq!(floats.get_type(), context.f32_type().array_type(2));
+    assert_eq!(doubles.get_type(), context.f64_type().array_type(1));
+
+    assert_eq!(bytes.as_slice::<u8>(), Some(&b"inkwell"[..]));
+    assert_eq!(shorts.as_slice::<u16>(), Some(&[1, 0xFFFF][..]));
+    assert_eq!(ints.as_slice::<u32>(), Some(&[3, 1, 4, 1, 5][..]));
+    assert_eq!(longs.as_slice::<u64>(), Some(&[u64::MAX, 0][..]));
+    assert_eq!(floats.as_slice::<f32>(), Some(&[1.5, -0.0][..]));
+    assert_eq!(doubles.as_slice::<f64>(), Some(&[std::f64::consts::PI][..]));
+
+    // Floats are copied bit for bit, keeping signaling NaNs and their payloads
+    let nans = ArrayValue::from_slice(&context, &[f32::from_bits(0x7F80_0001), f32::from_bits(0xFFC0_1234)]);
+    let double_nans = ArrayValue::from_slice(&context, &[f64::from_bits(0x7FF0_0000_0000_0001)]);
+    let nan_bits: Vec<u32> = nans
+        .as_slice::<f32>()
+        .unwrap()
+        .iter()
+        .map(|nan| nan.to_bits())
+        .collect();
+
+    assert_eq!(nan_bits, vec![0x7F80_0001, 0xFFC0_1234]);
+    assert_eq!(
+        double_nans.as_slice::<f64>().unwrap()[0].to_bits(),
+        0x7FF0_0000_0000_0001
+    );
+
+    // Element types must match exactly
+    assert_eq!(ints.as_slice::<u64>(), None);
+    assert_eq!(ints.as_slice::<f32>(), None);
+    assert_eq!(doubles.as_slice::<u64>(), None);
+
+    // Arrays built element by element are data arrays too
+    let elements = [i16_type.const_int(7, false), i16_type.const_int(8, false)];
+
+    assert_eq!(i16_type.const_array(&elements).as_slice::<u16>(), Some(&[7, 8][..]));
+
+    // Zero filled arrays become zeroinitializer, and other arrays aren't data arrays
+    let zeros = ArrayValue::from_slice(&context, &[0u32; 4]);
+    let nested = i8_type.array_type(7).const_array(&[bytes, bytes]);
+
+    assert!(zeros.is_null());
+    assert_eq!(zeros.as_slice::<u32>(), None);
+    assert_eq!(nested.as_slice::<u8>(), None);
+    assert!(nested.get_element_as_constant(0).is_none());
+
+    // Single elements
+    let last = ints.get_element_as_constant(4).unwrap().into_int_value();
+    let max = longs.get_element_as_constant(0).unwrap().into_int_value();
+    let pi = doubles.get_element_as_constant(0).unwrap().into_float_value();
+
+    assert_eq!(last.get_zero_extended_constant(), Some(5));
+    assert_eq!(max.get_zero_extended_constant(), Some(u64::MAX));
+    assert_eq!(pi.get_constant(), Some((std::f64::consts::PI, false)));
+    assert!(ints.get_element_as_constant(5).is_none());
+
+    // Large tables
+    let table: Vec<u8> = (0..1 << 20).map(|i| (i * 7) as u8).collect();
+    let table_value = ArrayValue::from_slice(&context, &table);
+    let global = module.add_global(table_value.get_type(), None, "table");
+
+    global.set_initializer(&table_value);
+
+    assert_eq!(table_value.as_slice::<u8>(), Some(&table[..]));
+
+    let wide_table: Vec<u32> = (0..1 << 20).map(|i: u32| i.wrapping_mul(0x9E37_79B9)).collect();
+    let wide_global = module.add_global_data_table(&wide_table, None, "wide_table").unwrap();
+    let wide_bytes = wide_global.get_initializer().unwrap().into_array_value();
+    let round_trip: Vec<u32> = wide_bytes
+        .as_slice::<u8>()
+        .unwrap()
+        .chunks(4)
+        .map(|chunk| u32::from_ne_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect();
+
+    assert_eq!(wide_bytes.get_type(), i8_type.array_type(4 << 20));
+    assert_eq!(round_trip, wide_table);
+    assert_eq!(wide_global.get_alignment(), 4);
+    assert!(wide_global.is_constant());
+    assert!(module.verify().is_ok());
+
+    // Tables are only embedded in modules with the host's byte order
+    let foreign_module = context.create_module("foreign");
+    let foreign_layout = if cfg!(target_endian = "big") { "e" } else { "E" };
+
+    foreign_module.set_data_layout(&TargetData::create(foreign_layout).get_data_layout());
+
+    assert_eq!(
+        foreign_module.add_global_data_table(&[1u32], None, "table"),
+        Err("The module's byte order differs from the host's.")
+    );
+    assert!(foreign_module.get_first_global().is_none());
+
+    let global = module.add_global(ints.get_type(), None, "ints");
+
+    global.set_initializer(&ints);
+
+    assert_eq!(
+        global.print_to_string().to_string(),
+        "@ints = global [5 x i32] [i32 3, i32 1, i32 4, i32 1, i32 5]"
+    );
+}
+
+#[test]
+fn test_wide_int_constants() {
+    let context = Context::create();
+    let i8_type = context.i8_type();
+    let i64_type = context.i64_type();
+    let i65_type = context.custom_width_int_type(65);
+    let i128_type = context.i128_type();
+    let i256_type = context.custom_width_int_type(256);
+
+    let words = [0x0123_4567_89AB_CDEF, 0xFEDC_BA98_7654_3210, 0, u64::MAX];
+    let i256_val = i256_type.const_int_arbitrary_precision(&words);
+
+    assert_eq!(i256_val.get_arbitrary_precision_words(), Some(words.to_vec()));
+    assert_eq!(i256_val.get_zero_extended_constant(), None);
+    assert_eq!(i256_val.get_zero_extended_constant_u128(), None);
+    assert_eq!(i256_val.get_sign_extended_constant_i128(), None);
+
+    // Narrow types still give whole words
+    let i8_val = i8_type.const_int(0xF0, false);
+
+    assert_eq!(i8_val.get_arbitrary_precision_words(), Some(vec![0xF0]));
+    assert_eq!(i8_val.get_zero_extended_constant_u128(), Some(0xF0));
+    assert_eq!(i8_val.get_sign_extended_constant_i128(), Some(-16));
+    assert_eq!(
+        i64_type.const_all_ones().get_arbitrary_precision_words(),
+        Some(vec![u64::MAX])
+    );
+
+    // Bits past the width are dropped
+    let i65_val = i65_type.const_int_arbitrary_precision(&[u64::MAX, u64::MAX]);
+
+    assert_eq!(i65_val.get_arbitrary_precision_words(), Some(vec![u64::MAX, 1]));
+    assert_eq!(i65_val.get_zero_extended_constant_u128(), Some((1 << 65) - 1));
+    assert_eq!(i65_val.get_sign_extended_constant_i128(), Some(-1));
+
+    // Round trips through u128 and i128
+    let big: u128 = 0xDEAD_BEEF_0000_0001_8000_0000_0000_0002;
+    let big_val = i128_type.const_int_arbitrary_precision(&[big as u64, (big >> 64) as u64]);
+
+    assert_eq!(big_val.get_zero_extended_constant_u128(), Some(big));
+    assert_eq!(big_val.get_sign_extended_constant_i128(), Some(big as i128));
+    assert_eq!(
+        i128_type
+            .const_int_arbitrary_precision(&[0, 1 << 63])
+            .get_sign_extended_constant_i128(),
+        Some(i128::MIN)
+    );
+
+    // Non constants have no value
+    let module = context.create_module("wide");
+    let fn_type = context.void_type().fn_type(&[i128_type.into()], false);
+    let function = module.add_function("f", fn_type, None);
+    let param = function.get_first_param().unwrap().into_int_value();
+
+    assert_eq!(param.get_arbitrary_precision_words(), None);
+    assert_eq!(param.get_zero_extended_constant_u128(), None);
+    assert_eq!(param.get_sign_extended_constant_i128(), None);
+}
+
+#[test]
+fn test_float_bit_patterns() {
+    let context = Context::create();
+    let f16_type = context.f16_type();
+    let f32_type = context.f32_type();
+    let f64_type = context.f64_type();
+    let f80_type = context.x86_f80_type();
+    let f128_type = context.f128_type();
+    let ppc_f128_type = context.ppc_f128_type();
+
+    assert_eq!(f16_type.get_bit_width(), 16);
+    assert_eq!(f32_type.get_bit_width(), 32);
+    assert_eq!(f64_type.get_bit_width(), 64);
+    assert_eq!(f80_type.get_bit_width(), 80);
+    assert_eq!(f128_type.get_bit_width(), 128);
+
+    // Bits read back exactly
+    assert_eq!(f16_type.const_float(1.).get_bits(), Some(vec![0x3C00]));
+    assert_eq!(f32_type.const_float(0.5).get_bits(), Some(vec![0x3F00_0000]));
+    assert_eq!(
+        f64_type.const_float(std::f64::consts::E).get_bits(),
+        Some(vec![std::f64::consts::E.to_bits()])
+    );
+    assert_eq!(f80_type.const_float(1.).get_bits(), Some(vec![1 << 63, 0x3FFF]));
+    assert_eq!(
+        f128_type.const_float(-1.).get_bits(),
+        Some(vec![0, 0xBFFF_0000_0000_0000])
+    );
+
+    // NaN payloads survive
+    let nan = f64_type.const_float_from_bits(&[0x7FF8_0000_DEAD_BEEF]);
+
+    assert_eq!(nan.get_bits(), Some(vec![0x7FF8_0000_DEAD_BEEF]));
+    assert_eq!(nan.print_to_string().to_string(), "double 0x7FF80000DEADBEEF");
+
+    // Values an f64 can't represent
+    let f80_val = f80_type.const_float_from_bits(&[(1 << 63) | 1, 0x3FFF]);
+    let f128_val = f128_type.const_float_from_bits(&[0x1234, 0x4000_0000_0000_0000]);
+
+    assert_eq!(f80_val.get_bits(), Some(vec![(1 << 63) | 1, 0x3FFF]));
+    assert_eq!(f80_val.get_constant(), Some((1., true)));
+    assert_eq!(f128_val.get_bits(), Some(vec![0x1234, 0x4000_0000_0000_0000]));
+    assert_eq!(
+        f128_val.print_to_string().to_string(),
+        "fp128 0xL00000000000012344000000000000000"
+    );
+
+    // Missing words are zero and extra bits are ignored
+    assert_eq!(f128_type.const_float_from_bits(&[7]).get_bits(), Some(vec![7, 0]));
+    assert_eq!(
+        f32_type.const_float_from_bits(&[0x1_3F80_0000, 9]).get_constant(),
+        Some((1., false))
+    );
+
+    // ppc_f128 words are the high and low order doubles, and round trip exactly
+    let ppc_one = ppc_f128_type.const_float_from_bits(&[1.0f64.to_bits(), 0]);
+    let ppc_words = [1.0f64.to_bits(), (-(2.0f64.powi(-60))).to_bits()];
+    let ppc_val = ppc_f128_type.const_float_from_bits(&ppc_words);
+    let ppc_nan = ppc_f128_type.const_float_from_bits(&[0x7FF8_0000_DEAD_BEEF, 0x8000_0000_0000_0001, 3]);
+
+    assert_eq!(ppc_one.get_constant(), Some((1., false)));
+    assert_eq!(ppc_one.get_bits(), Some(vec![1.0f64.to_bits(), 0]));
+    assert_eq!(ppc_val.get_bits(), Some(ppc_words.to_vec()));
+    assert_eq!(
+        ppc_val.print_to_string().to_string(),
+        "ppc_fp128 0xM3FF0000000000000BC30000000000000"
+    );
+    assert_eq!(
+        ppc_nan.get_bits(),
+        Some(vec![0x7FF8_0000_DEAD_BEEF, 0x8000_0000_0000_0001])
+    );
+    assert_eq!(
+        ppc_f128_type.const_float(1.).get_bits(),
+        Some(vec![1.0f64.to_bits(), 0])
+    );
+    assert_eq!(ppc_f128_type.const_zero().get_bits(), Some(vec![0, 0]));
+}
+
+#[llvm_versions(3.9..=latest)]
+#[test]
+fn test_argument_values() {
+    let context = Context::create();
+    let module = context.create_module("args");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let i32_ptr_type = i32_type.ptr_type(AddressSpace::Generic);
+    let fn_type = i32_type.fn_type(&[i32_ptr_type.into(), i32_type.into()], false);
+    let function = module.add_function("load_add", fn_type, None);
+    let entry = context.append_basic_block(function, "entry");
+
+    builder.position_at_end(entry);
+
+    let ptr = function.get_nth_param(0).unwrap();
+    let loaded = builder.build_load(ptr.into_pointer_value(), "loaded");
+    let sum = builder.build_int_add(
+        loaded.into_int_value(),
+        function.get_nth_param(1).unwrap().into_int_value(),
+        "sum",
+    );
+
+    builder.build_return(Some(&sum));
+
+    let arguments = function.get_arguments();
+
+    assert_eq!(arguments.len(), 2);
+    assert_eq!(function.get_nth_argument(1), Some(arguments[1]));
+    assert!(function.get_nth_argument(2).is_none());
+
+    // From an arbitrary operand back to its position
+    let add = sum.as_instruction().unwrap();
+    let positions: Vec<_> = add
+        .operands()
+        .map(|operand| ArgumentValue::try_from(operand.left().unwrap()).map(|arg| arg.get_index()))
+        .collect();
+
+    assert_eq!(positions, vec![Err(()), Ok(1)]);
+    assert!(ArgumentValue::try_from(AnyValueEnum::from(function)).is_err());
+
+    let ptr_arg = ArgumentValue::try_from(ptr).unwrap();
+
+    assert_eq!(ptr_arg, arguments[0]);
+    assert_eq!(ptr_arg.get_parent_function(), function);
+    assert_eq!(ptr_arg.get_type(), i32_ptr_type.into());
+    assert_eq!(ptr_arg.as_basic_value_enum(), ptr);
+    assert_eq!(BasicValueEnum::from(ptr_arg), ptr);
+
+    ptr_arg.set_name("ptr");
+
+    assert_eq!(ptr.into_pointer_value().get_name().to_str(), Ok("ptr"));
+    assert_eq!(ptr_arg.uses().count(), 1);
+
+    // Attributes only touch their own argument
+    let kind_id = |name| Attribute::get_named_enum_kind_id(name);
+    let nonnull = context.create_enum_attribute(kind_id("nonnull"), 0);
+    let noundef = context.create_enum_attribute(kind_id("noundef"), 0);
+    let string_attribute = context.create_string_attribute("my_key", "my_val");
+
+    ptr_arg.add_attribute(nonnull);
+    ptr_arg.add_attribute(string_attribute);
+    arguments[1].add_attribute(noundef);
+
+    assert_eq!(ptr_arg.count_attributes(), 2);
+    assert_eq!(ptr_arg.get_enum_attribute(kind_id("nonnull")), Some(nonnull));
+    assert_eq!(ptr_arg.get_enum_attribute(kind_id("noundef")), None);
+    assert_eq!(ptr_arg.get_string_attribute("my_key"), Some(string_attribute));
+    assert_eq!(arguments[1].attributes(), vec![noundef]);
+    assert_eq!(function.count_attributes(AttributeLoc::Param(1)), 1);
+
+    ptr_arg.remove_enum_attribute(kind_id("nonnull"));
+    ptr_arg.remove_string_attribute("my_key");
+
+    assert_eq!(ptr_arg.count_attributes(), 0);
+
+    // Alignment
+    assert_eq!(ptr_arg.get_alignment(), None);
+
+    ptr_arg.set_alignment(16);
+
+    assert_eq!(ptr_arg.get_alignment(), Some(16));
+    assert!(module.verify().is_ok());
+    assert!(function
+        .print_to_string()
+        .to_string()
+        .starts_with("define i32 @load_add(i32* align 16 %ptr, i32 noundef %0)"));
+}